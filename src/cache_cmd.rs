@@ -0,0 +1,126 @@
+//! `rtk cache`: inspect and reset the tracking history database.
+
+use crate::tracking::{self, Retention, Tracker};
+use crate::utils::theme;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Print where the history database lives (whether or not it exists yet)
+pub fn path() -> Result<()> {
+    println!("{}", tracking::get_db_path()?.display());
+    Ok(())
+}
+
+/// Prune the history now and report what was removed
+pub fn prune(days: Option<u32>, max_entries: Option<usize>) -> Result<()> {
+    let (removed, retention) = apply_retention(days, max_entries)?;
+    println!(
+        "{} Pruned {} entr{} (keeping {} days, at most {} entries)",
+        theme().ok,
+        removed,
+        if removed == 1 { "y" } else { "ies" },
+        retention.days,
+        retention.max_entries
+    );
+    Ok(())
+}
+
+/// Prune silently, overriding the configured retention where given
+pub fn apply_retention(
+    days: Option<u32>,
+    max_entries: Option<usize>,
+) -> Result<(usize, Retention)> {
+    let configured = Retention::from_config();
+    let retention = Retention {
+        days: days.unwrap_or(configured.days),
+        max_entries: max_entries.unwrap_or(configured.max_entries),
+    };
+    let tracker = Tracker::new().context("Failed to initialize tracking database")?;
+    Ok((tracker.prune(retention)?, retention))
+}
+
+/// Delete the history database, asking first unless `yes`
+pub fn clear(yes: bool, verbose: u8) -> Result<()> {
+    let db_path = tracking::get_db_path()?;
+    if verbose > 0 {
+        eprintln!("History database: {}", db_path.display());
+    }
+
+    if !db_path.exists() {
+        println!("History already empty ({})", db_path.display());
+        return Ok(());
+    }
+
+    if !yes && !confirm(&db_path)? {
+        println!("Aborted, history kept");
+        return Ok(());
+    }
+
+    let removed = remove_history(&db_path)?;
+    println!(
+        "{} Cleared history: removed {}",
+        theme().ok,
+        removed
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    Ok(())
+}
+
+/// Remove the database and SQLite's `-wal`/`-shm` sidecars, returning what was deleted
+fn remove_history(db_path: &Path) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    for suffix in ["", "-wal", "-shm"] {
+        let mut name = db_path.as_os_str().to_owned();
+        name.push(suffix);
+        let path = PathBuf::from(name);
+        if !path.exists() {
+            continue;
+        }
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+        removed.push(path);
+    }
+    Ok(removed)
+}
+
+/// Prompt on stderr (stdout may be piped); non-interactive runs default to No
+fn confirm(db_path: &Path) -> Result<bool> {
+    use std::io::{self, BufRead, IsTerminal};
+
+    eprintln!("Delete tracking history at {}? [y/N] ", db_path.display());
+
+    if !io::stdin().is_terminal() {
+        eprintln!("(non-interactive mode, defaulting to N; pass --yes to skip this prompt)");
+        return Ok(false);
+    }
+
+    let mut line = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .context("Failed to read user input")?;
+
+    let response = line.trim().to_lowercase();
+    Ok(response == "y" || response == "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_history_with_sidecars() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("history.db");
+        std::fs::write(&db, "db").unwrap();
+        std::fs::write(dir.path().join("history.db-wal"), "wal").unwrap();
+
+        let removed = remove_history(&db).unwrap();
+        assert_eq!(removed, vec![db.clone(), dir.path().join("history.db-wal")]);
+        assert!(!db.exists());
+        assert!(remove_history(&db).unwrap().is_empty());
+    }
+}
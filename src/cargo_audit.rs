@@ -0,0 +1,523 @@
+//! `rtk cargo audit`: RustSec advisories grouped by severity.
+//!
+//! Parses both the human report (`Crate:`/`Version:`/`ID:` blocks) and
+//! `cargo audit --json`, then prints one summary line plus one line per advisory.
+
+use crate::cargo_cmd::FilterOptions;
+use crate::tracking;
+use crate::utils::{exit_code, normalize_newlines, strip_ansi};
+use anyhow::{Context, Result};
+use std::str::FromStr;
+
+/// Advisory severity, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum AuditSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl FromStr for AuditSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(AuditSeverity::Low),
+            "medium" => Ok(AuditSeverity::Medium),
+            "high" => Ok(AuditSeverity::High),
+            "critical" => Ok(AuditSeverity::Critical),
+            _ => Err(format!(
+                "Unknown severity: {} (low, medium, high, critical)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for AuditSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            AuditSeverity::Low => "low",
+            AuditSeverity::Medium => "medium",
+            AuditSeverity::High => "high",
+            AuditSeverity::Critical => "critical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl AuditSeverity {
+    /// CVSS qualitative rating; a 0.0 score has no rating
+    fn from_score(score: f64) -> Option<Self> {
+        match score {
+            s if s >= 9.0 => Some(AuditSeverity::Critical),
+            s if s >= 7.0 => Some(AuditSeverity::High),
+            s if s >= 4.0 => Some(AuditSeverity::Medium),
+            s if s > 0.0 => Some(AuditSeverity::Low),
+            _ => None,
+        }
+    }
+}
+
+/// One vulnerable crate reported by cargo audit
+#[derive(Debug, Clone, PartialEq)]
+struct Advisory {
+    id: String,
+    package: String,
+    version: String,
+    title: String,
+    /// `None` when the advisory has no CVSS score ("unrated")
+    severity: Option<AuditSeverity>,
+}
+
+#[derive(Debug, Default)]
+struct AuditReport {
+    vulnerabilities: Vec<Advisory>,
+    /// Informational advisories (unmaintained, yanked, unsound)
+    warnings: usize,
+    crates_scanned: Option<usize>,
+}
+
+impl AuditReport {
+    /// Whether any vulnerability reaches `threshold`; unrated ones always count
+    fn fails(&self, threshold: AuditSeverity) -> bool {
+        self.vulnerabilities
+            .iter()
+            .any(|v| v.severity.is_none_or(|s| s >= threshold))
+    }
+}
+
+pub fn run(
+    args: &[String],
+    fail_on: AuditSeverity,
+    opts: &FilterOptions,
+    verbose: u8,
+) -> Result<()> {
+    opts.reject_json("cargo audit")?;
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = opts.cargo_command();
+    cmd.arg("audit");
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    if verbose > 0 {
+        eprintln!("Running: cargo audit {}", args.join(" "));
+    }
+
+    let output = cmd.output().context("Failed to run cargo audit")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let status = exit_code(&output.status);
+
+    let report = parse_json(&stdout).or_else(|| parse_text(&normalize_newlines(&strip_ansi(&raw))));
+    let filtered = match &report {
+        Some(report) => format_report(report, opts),
+        // Not an audit report (e.g. cargo-audit not installed): keep cargo's own words
+        None => normalize_newlines(&strip_ansi(&raw)).trim().to_string(),
+    };
+    println!("{}", opts.render(&filtered));
+
+    timer.track(
+        &format!("cargo audit {}", args.join(" ")),
+        &format!("rtk cargo audit {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    let code = match &report {
+        Some(report) if report.fails(fail_on) => 1,
+        Some(_) => 0,
+        None => status,
+    };
+    if code != 0 {
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
+
+/// Parse `cargo audit --json`
+fn parse_json(stdout: &str) -> Option<AuditReport> {
+    let json: serde_json::Value = serde_json::from_str(stdout.trim()).ok()?;
+    let vulnerabilities = json.get("vulnerabilities")?;
+
+    let mut report = AuditReport {
+        crates_scanned: json
+            .pointer("/lockfile/dependency-count")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as usize),
+        ..AuditReport::default()
+    };
+
+    for entry in vulnerabilities
+        .get("list")
+        .and_then(|l| l.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let field = |ptr: &str| {
+            entry
+                .pointer(ptr)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string()
+        };
+        report.vulnerabilities.push(Advisory {
+            id: field("/advisory/id"),
+            package: field("/package/name"),
+            version: field("/package/version"),
+            title: field("/advisory/title"),
+            severity: entry
+                .pointer("/advisory/cvss")
+                .and_then(|v| v.as_str())
+                .and_then(cvss_score)
+                .and_then(AuditSeverity::from_score),
+        });
+    }
+
+    if let Some(warnings) = json.get("warnings").and_then(|w| w.as_object()) {
+        report.warnings = warnings
+            .values()
+            .filter_map(|kind| kind.as_array())
+            .map(|list| list.len())
+            .sum();
+    }
+
+    Some(report)
+}
+
+/// Parse the human report: blank-line separated `Key: value` blocks starting with `Crate:`
+fn parse_text(output: &str) -> Option<AuditReport> {
+    let mut report = AuditReport::default();
+    let mut seen_scan = false;
+    let mut block: Vec<(String, String)> = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Scanning ") {
+            seen_scan = true;
+            report.crates_scanned = trimmed
+                .split('(')
+                .nth(1)
+                .and_then(|rest| rest.split_whitespace().next())
+                .and_then(|n| n.parse().ok());
+            continue;
+        }
+        if trimmed.is_empty() {
+            flush_block(&mut block, &mut report);
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if !key.is_empty() && !key.contains(' ') {
+                // A new advisory can follow the previous one without a blank line
+                if key == "Crate" {
+                    flush_block(&mut block, &mut report);
+                }
+                block.push((key.to_string(), value.trim().to_string()));
+            }
+        }
+    }
+    flush_block(&mut block, &mut report);
+
+    if seen_scan || !report.vulnerabilities.is_empty() || report.warnings > 0 {
+        Some(report)
+    } else {
+        None
+    }
+}
+
+/// Turn one `Key: value` block into an advisory (or warning), then clear it
+fn flush_block(block: &mut Vec<(String, String)>, report: &mut AuditReport) {
+    let get = |key: &str| {
+        block
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default()
+    };
+    if block.iter().any(|(k, _)| k == "Crate") {
+        if block.iter().any(|(k, _)| k == "Warning") {
+            report.warnings += 1;
+        } else {
+            report.vulnerabilities.push(Advisory {
+                id: get("ID"),
+                package: get("Crate"),
+                version: get("Version"),
+                title: get("Title"),
+                severity: parse_severity_field(&get("Severity")),
+            });
+        }
+    }
+    block.clear();
+}
+
+/// `Severity:  9.8 (critical)` → Critical
+fn parse_severity_field(value: &str) -> Option<AuditSeverity> {
+    if let Some(word) = value.split(['(', ')']).nth(1) {
+        if let Ok(severity) = word.parse() {
+            return Some(severity);
+        }
+    }
+    value
+        .split_whitespace()
+        .next()
+        .and_then(|score| score.parse::<f64>().ok())
+        .and_then(AuditSeverity::from_score)
+}
+
+/// CVSS v3.x base score from a vector like `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`
+fn cvss_score(vector: &str) -> Option<f64> {
+    let metric = |name: &str| {
+        vector
+            .split('/')
+            .find_map(|part| part.strip_prefix(name)?.strip_prefix(':'))
+    };
+    let changed = metric("S")? == "C";
+
+    let av = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let ac = match metric("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let pr = match (metric("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let ui = match metric("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let cia = |name: &str| match metric(name) {
+        Some("H") => Some(0.56),
+        Some("L") => Some(0.22),
+        Some("N") => Some(0.0),
+        _ => None,
+    };
+    let iss = 1.0 - (1.0 - cia("C")?) * (1.0 - cia("I")?) * (1.0 - cia("A")?);
+
+    let impact = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02_f64).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * av * ac * pr * ui;
+    let base = if changed {
+        (1.08 * (impact + exploitability)).min(10.0)
+    } else {
+        (impact + exploitability).min(10.0)
+    };
+    // CVSS "round up" to one decimal, tolerant of float noise
+    Some(((base * 10.0) - 1e-9).ceil() / 10.0)
+}
+
+fn format_report(report: &AuditReport, opts: &FilterOptions) -> String {
+    let warnings = if report.warnings > 0 {
+        format!(
+            ", {} warning{}",
+            report.warnings,
+            if report.warnings == 1 { "" } else { "s" }
+        )
+    } else {
+        String::new()
+    };
+
+    if report.vulnerabilities.is_empty() {
+        let scanned = report
+            .crates_scanned
+            .map(|n| format!(" ({} crates scanned)", n))
+            .unwrap_or_default();
+        return format!(
+            "{} cargo audit: no vulnerabilities{}{}",
+            opts.theme.ok, scanned, warnings
+        );
+    }
+
+    let mut sorted = report.vulnerabilities.clone();
+    sorted.sort_by_key(|v| std::cmp::Reverse(v.severity));
+
+    let mut counts: Vec<String> = [
+        AuditSeverity::Critical,
+        AuditSeverity::High,
+        AuditSeverity::Medium,
+        AuditSeverity::Low,
+    ]
+    .iter()
+    .filter_map(|level| {
+        let n = sorted.iter().filter(|v| v.severity == Some(*level)).count();
+        (n > 0).then(|| format!("{} {}", n, level))
+    })
+    .collect();
+    let unrated = sorted.iter().filter(|v| v.severity.is_none()).count();
+    if unrated > 0 {
+        counts.push(format!("{} unrated", unrated));
+    }
+
+    let mut result = format!("cargo audit: {}{}\n", counts.join(", "), warnings);
+    for advisory in sorted.iter().take(opts.max_errors) {
+        result.push_str(&format!(
+            "{} {} {} — {}\n",
+            advisory.id, advisory.package, advisory.version, advisory.title
+        ));
+    }
+    if sorted.len() > opts.max_errors {
+        result.push_str(&format!(
+            "... +{} more advisories\n",
+            sorted.len() - opts.max_errors
+        ));
+    }
+
+    result.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT_REPORT: &str = r#"    Fetching advisory database from `https://github.com/RustSec/advisory-db.git`
+      Loaded 612 security advisories (from /home/user/.cargo/advisory-db)
+    Updating crates.io index
+    Scanning Cargo.lock for vulnerabilities (250 crate dependencies)
+Crate:     time
+Version:   0.1.45
+Title:     Potential segfault in the time crate
+Date:      2020-11-18
+ID:        RUSTSEC-2020-0071
+URL:       https://rustsec.org/advisories/RUSTSEC-2020-0071
+Severity:  2.9 (low)
+Solution:  Upgrade to >=0.2.23
+Dependency tree:
+time 0.1.45
+└── chrono 0.4.19
+
+Crate:     openssl
+Version:   0.10.0
+Title:     Use after free in X509 verification
+Date:      2024-01-01
+ID:        RUSTSEC-2024-0001
+URL:       https://rustsec.org/advisories/RUSTSEC-2024-0001
+Severity:  9.8 (critical)
+Solution:  Upgrade to >=0.10.60
+
+Crate:     ansi_term
+Version:   0.12.1
+Warning:   unmaintained
+Title:     ansi_term is Unmaintained
+Date:      2021-08-18
+ID:        RUSTSEC-2021-0139
+
+error: 2 vulnerabilities found!
+warning: 1 allowed warning found
+"#;
+
+    #[test]
+    fn test_parse_text_report() {
+        let report = parse_text(TEXT_REPORT).unwrap();
+        assert_eq!(report.vulnerabilities.len(), 2);
+        assert_eq!(report.warnings, 1);
+        assert_eq!(report.crates_scanned, Some(250));
+        assert_eq!(report.vulnerabilities[0].id, "RUSTSEC-2020-0071");
+        assert_eq!(
+            report.vulnerabilities[1].severity,
+            Some(AuditSeverity::Critical)
+        );
+    }
+
+    #[test]
+    fn test_format_report_groups_by_severity() {
+        let report = parse_text(TEXT_REPORT).unwrap();
+        let result = format_report(&report, &FilterOptions::default());
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(lines[0], "cargo audit: 1 critical, 1 low, 1 warning");
+        assert_eq!(
+            lines[1],
+            "RUSTSEC-2024-0001 openssl 0.10.0 — Use after free in X509 verification"
+        );
+        assert!(lines[2].starts_with("RUSTSEC-2020-0071 time 0.1.45"));
+    }
+
+    #[test]
+    fn test_clean_scan() {
+        let output = "    Scanning Cargo.lock for vulnerabilities (87 crate dependencies)\n";
+        let report = parse_text(output).unwrap();
+        assert_eq!(
+            format_report(&report, &FilterOptions::default()),
+            "✓ cargo audit: no vulnerabilities (87 crates scanned)"
+        );
+        assert!(!report.fails(AuditSeverity::Low));
+    }
+
+    #[test]
+    fn test_parse_json_report() {
+        let json = r#"{
+  "lockfile": {"dependency-count": 120},
+  "vulnerabilities": {"found": true, "count": 1, "list": [{
+    "advisory": {"id": "RUSTSEC-2023-0044", "package": "openssl", "title": "`openssl` `X509VerifyParamRef::set_host` buffer over-read",
+                 "cvss": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H"},
+    "package": {"name": "openssl", "version": "0.10.54"}
+  }]},
+  "warnings": {"unmaintained": [{}, {}]}
+}"#;
+        let report = parse_json(json).unwrap();
+        assert_eq!(report.crates_scanned, Some(120));
+        assert_eq!(report.warnings, 2);
+        let advisory = &report.vulnerabilities[0];
+        assert_eq!(advisory.package, "openssl");
+        assert_eq!(advisory.version, "0.10.54");
+        assert_eq!(advisory.severity, Some(AuditSeverity::High));
+    }
+
+    #[test]
+    fn test_cvss_score() {
+        assert_eq!(
+            cvss_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Some(9.8)
+        );
+        assert_eq!(
+            cvss_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H"),
+            Some(7.5)
+        );
+        assert_eq!(
+            cvss_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"),
+            Some(6.1)
+        );
+        assert_eq!(cvss_score("not a vector"), None);
+    }
+
+    #[test]
+    fn test_fail_threshold() {
+        let report = parse_text(TEXT_REPORT).unwrap();
+        assert!(report.fails(AuditSeverity::Critical));
+
+        let low_only = AuditReport {
+            vulnerabilities: vec![report.vulnerabilities[0].clone()],
+            ..AuditReport::default()
+        };
+        assert!(low_only.fails(AuditSeverity::Low));
+        assert!(!low_only.fails(AuditSeverity::Medium));
+    }
+
+    #[test]
+    fn test_not_an_audit_report() {
+        assert!(parse_text("error: no such command: `audit`\n").is_none());
+        assert!(parse_json("error: no such command").is_none());
+    }
+}
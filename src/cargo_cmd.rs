@@ -1,10 +1,22 @@
+use crate::cargo_filters::{
+    crate_warning_summary, filter_cargo_build, filter_cargo_clippy, filter_cargo_install,
+    filter_cargo_test, is_noise_line, parse_failed_tests, split_sccache_stats,
+};
+use crate::cargo_report::{self, OutputFormat};
+use crate::config::Config;
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{exit_code, normalize_newlines, strip_ansi, Spinner, Theme};
 use anyhow::{Context, Result};
+use colored::Colorize;
 use std::collections::HashMap;
 use std::ffi::OsString;
-use std::process::Command;
+use std::fmt;
+use std::io::{BufRead, BufReader, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
 use std::sync::OnceLock;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum CargoCommand {
@@ -14,294 +26,2176 @@ pub enum CargoCommand {
     Check,
     Install,
     Nextest,
+    Publish,
+    Clean,
+    Run,
+    Search,
+    Uninstall,
+    Vendor,
+    Machete,
+    /// `cargo llvm-cov`; exits nonzero when total line coverage is under the threshold
+    LlvmCov {
+        threshold: Option<f64>,
+    },
 }
 
-pub fn run(cmd: CargoCommand, args: &[String], verbose: u8) -> Result<()> {
+/// Which cargo filter to apply to captured output (`rtk filter <kind>`)
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum FilterKind {
+    Build,
+    Check,
+    Test,
+    Clippy,
+    Install,
+    Nextest,
+    Publish,
+    Clean,
+    /// Sniff the content to pick one of the above
+    Auto,
+}
+
+impl FromStr for FilterKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "build" => Ok(FilterKind::Build),
+            "check" => Ok(FilterKind::Check),
+            "test" => Ok(FilterKind::Test),
+            "clippy" => Ok(FilterKind::Clippy),
+            "install" => Ok(FilterKind::Install),
+            "nextest" => Ok(FilterKind::Nextest),
+            "publish" => Ok(FilterKind::Publish),
+            "clean" => Ok(FilterKind::Clean),
+            "auto" => Ok(FilterKind::Auto),
+            _ => Err(format!(
+                "Unknown filter kind: {} (build, check, test, clippy, install, nextest, publish, clean, auto)",
+                s
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for FilterKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            FilterKind::Build => "build",
+            FilterKind::Check => "check",
+            FilterKind::Test => "test",
+            FilterKind::Clippy => "clippy",
+            FilterKind::Install => "install",
+            FilterKind::Nextest => "nextest",
+            FilterKind::Publish => "publish",
+            FilterKind::Clean => "clean",
+            FilterKind::Auto => "auto",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FilterKind {
+    /// Resolve `Auto` by sniffing characteristic lines; other kinds are returned as-is
+    pub fn resolve(self, output: &str) -> Self {
+        if self != FilterKind::Auto {
+            return self;
+        }
+        if is_nextest_output(output) {
+            FilterKind::Nextest
+        } else if output.contains("test result:")
+            || output.contains(r#""type": "suite""#)
+            || output
+                .lines()
+                .any(|l| l.starts_with("running ") && l.trim_end().ends_with("tests"))
+        {
+            FilterKind::Test
+        } else if output
+            .lines()
+            .any(|l| l.trim_start().starts_with("Packaging "))
+        {
+            FilterKind::Publish
+        } else if output.lines().any(|l| {
+            let t = l.trim_start();
+            t.starts_with("Removed ") || t.starts_with("Removing ")
+        }) {
+            FilterKind::Clean
+        } else if output.contains("[clippy::") || output.contains("clippy::") {
+            FilterKind::Clippy
+        } else if output.lines().any(|l| {
+            let t = l.trim_start();
+            t.starts_with("Installing")
+                || t.starts_with("Installed")
+                || t.starts_with("Ignored package")
+        }) {
+            FilterKind::Install
+        } else {
+            FilterKind::Build
+        }
+    }
+
+    fn filter_fn(self) -> fn(&str, &FilterOptions) -> String {
+        match self {
+            FilterKind::Build | FilterKind::Check | FilterKind::Auto => filter_cargo_build,
+            FilterKind::Test => filter_cargo_test,
+            FilterKind::Clippy => filter_cargo_clippy,
+            FilterKind::Install => filter_cargo_install,
+            FilterKind::Nextest => filter_cargo_nextest,
+            FilterKind::Publish => filter_cargo_publish,
+            FilterKind::Clean => filter_cargo_clean,
+        }
+    }
+}
+
+/// When to colorize the rendered summary (`--rtk-color auto|always|never`)
+#[derive(Debug, Clone, Copy, PartialEq, Default, clap::ValueEnum)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("Unknown color choice: {} (auto, always, never)", s)),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// `auto` colors only when stdout is a TTY and `NO_COLOR` is unset
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Display options shared by all cargo filters.
+///
+/// Resolved from config (global `config.toml` < project `.rtk.toml`),
+/// then overridden by CLI flags in `main.rs`.
+#[derive(Debug, Clone)]
+pub struct FilterOptions {
+    /// Max error blocks shown by build/check/install
+    pub max_errors: usize,
+    /// Max characters kept per test failure
+    pub failure_width: usize,
+    /// Glyphs for summaries and separators (ASCII with `--ascii`, `RTK_ASCII=1` or `emoji = false`)
+    pub theme: Theme,
+    /// Colorize the printed summary (never affects tracked/filtered text)
+    pub color: bool,
+    /// Extra machine-readable output appended to the summary (`--format`)
+    pub format: OutputFormat,
+    /// Print only the one-line verdict (`--summary-only`)
+    pub summary_only: bool,
+    /// Skip filtering and print cargo's output verbatim (`--raw`, `RTK_RAW=1`)
+    pub raw: bool,
+    /// Append new/fixed/unchanged diagnostics compared with the previous run (`--diff`)
+    pub diff: bool,
+    /// Max `|` code-context lines kept per error block (`--context`, default all)
+    pub context: Option<usize>,
+    /// Drop warning blocks, noting how many were hidden (`--errors-only`)
+    pub errors_only: bool,
+    /// Drop error blocks, noting how many were hidden (`--warnings-only`, build/check)
+    pub warnings_only: bool,
+    /// Keep warning blocks alongside errors instead of only once errors are fixed (`--with-warnings`)
+    pub with_warnings: bool,
+    /// Only diagnostics whose `-->` file matches this glob (`--only`)
+    pub only: Option<String>,
+    /// Rustup toolchain from a leading `+nightly` argument, passed first to cargo
+    pub toolchain: Option<String>,
+    /// Cargo executable to run (`--cargo-bin`); falls back to `$CARGO`, then `cargo`
+    pub cargo_bin: Option<PathBuf>,
+    /// Render `-->` locations as absolute paths instead of workspace-relative (`--abs-paths`)
+    pub abs_paths: bool,
+    /// Show only the first error block with the total count (`--first`)
+    pub first: bool,
+    /// Append the first paragraph of `rustc --explain` for each error code (`--explain`)
+    pub explain: bool,
+    /// Kill cargo and its children after this long (`--timeout`)
+    pub timeout: Option<Timeout>,
+    /// Prefix the summary with the `$ cargo ...` line that produced it (`--include-command`)
+    pub include_command: bool,
+    /// Append `[rtk: 4.2 KB -> 340 B, 92%]` to the summary line (`--show-savings`)
+    pub show_savings: bool,
+    /// Also write cargo's untouched output to the history dir, noting the path (`--save-raw`)
+    pub save_raw: bool,
+    /// Also write the filtered result to this file, without color (`--output`)
+    pub output: Option<PathBuf>,
+    /// Write to `output` only, printing nothing on stdout (`--output-only`)
+    pub output_only: bool,
+    /// Clippy: show the first suggested fix per rule as `-`/`+` lines (`cargo clippy --suggest`)
+    pub suggest: bool,
+    /// Exit nonzero when build/check/clippy report more warnings than this, even if cargo
+    /// succeeded (`--fail-on-warnings` is 0, `--fail-over N`)
+    pub fail_over: Option<usize>,
+    /// Lines and blocks matching any of these are never capped or hidden (`[display] keep`)
+    pub keep: Vec<regex::Regex>,
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        Self {
+            max_errors: 15,
+            failure_width: 200,
+            theme: Theme::unicode(),
+            color: false,
+            format: OutputFormat::Text,
+            summary_only: false,
+            raw: false,
+            diff: false,
+            context: None,
+            errors_only: false,
+            warnings_only: false,
+            with_warnings: false,
+            only: None,
+            toolchain: None,
+            cargo_bin: None,
+            abs_paths: false,
+            first: false,
+            explain: false,
+            timeout: None,
+            include_command: false,
+            show_savings: false,
+            save_raw: false,
+            output: None,
+            output_only: false,
+            suggest: false,
+            fail_over: None,
+            keep: Vec::new(),
+        }
+    }
+}
+
+/// Compile `[display] keep` entries: `re:<regex>` as a regex, anything else as a literal.
+/// Invalid regexes are reported and skipped rather than failing every command.
+pub(crate) fn keep_patterns(patterns: &[String]) -> Vec<regex::Regex> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            let source = match pattern.strip_prefix("re:") {
+                Some(re) => re.to_string(),
+                None => regex::escape(pattern),
+            };
+            match regex::Regex::new(&source) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    eprintln!("rtk: ignoring invalid keep pattern {:?}: {}", pattern, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Failure truncation when nothing sets it: the terminal width on a TTY, 200 when piped
+fn default_failure_width() -> usize {
+    failure_width_for(
+        std::io::stdout().is_terminal(),
+        terminal_size::terminal_size().map(|(w, _)| w.0 as usize),
+    )
+}
+
+fn failure_width_for(is_tty: bool, columns: Option<usize>) -> usize {
+    match columns {
+        // Below ~40 columns a failure message is unreadable anyway
+        Some(n) if is_tty => n.max(40),
+        _ => 200,
+    }
+}
+
+impl FilterOptions {
+    /// Build options from the effective config and env, falling back to defaults
+    pub fn from_config() -> Self {
+        let config = Config::load_or_default();
+        let mut opts = Self {
+            max_errors: config.display.max_errors,
+            failure_width: config
+                .display
+                .failure_width
+                .unwrap_or_else(default_failure_width),
+            theme: crate::utils::theme(),
+            color: config.display.colors && ColorChoice::Auto.enabled(),
+            format: OutputFormat::detect(),
+            keep: keep_patterns(&config.display.keep),
+            ..Default::default()
+        };
+        opts.apply_env(|name| std::env::var(name).ok());
+        opts
+    }
+
+    /// Env overrides on top of config (for tools that control rtk's argv): `RTK_RAW`,
+    /// `RTK_MAX_ERRORS`, `RTK_FAILURE_WIDTH`. Unparsable numbers are ignored.
+    /// `RTK_ASCII` is resolved once for the whole process by [`crate::utils::init_theme`].
+    fn apply_env<F>(&mut self, var: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if var("RTK_RAW").as_deref() == Some("1") {
+            self.raw = true;
+        }
+        if let Some(n) = var("RTK_MAX_ERRORS").and_then(|v| v.trim().parse().ok()) {
+            self.max_errors = n;
+        }
+        if let Some(n) = var("RTK_FAILURE_WIDTH").and_then(|v| v.trim().parse().ok()) {
+            self.failure_width = n;
+        }
+    }
+
+    /// Whether `text` (a line or a whole diagnostic block) matches a `keep` pattern
+    pub(crate) fn keeps(&self, text: &str) -> bool {
+        self.keep.iter().any(|re| re.is_match(text))
+    }
+
+    /// `Command` for the configured cargo executable, with `+toolchain` as its first argument.
+    ///
+    /// A toolchain skips `$CARGO`: that is one toolchain's binary, and only the rustup
+    /// proxy understands `+toolchain`.
+    pub fn cargo_command(&self) -> Command {
+        let env = match self.toolchain {
+            Some(_) => None,
+            None => std::env::var_os("CARGO"),
+        };
+        let mut cmd = Command::new(cargo_program(self.cargo_bin.as_deref(), env));
+        if let Some(toolchain) = &self.toolchain {
+            cmd.arg(format!("+{}", toolchain));
+        }
+        cmd
+    }
+
+    /// `cargo +nightly` or `cargo`, for tracked and displayed command lines
+    fn cargo_display(&self) -> String {
+        match &self.toolchain {
+            Some(toolchain) => format!("cargo +{}", toolchain),
+            None => "cargo".to_string(),
+        }
+    }
+
+    /// Success marker prefixed to summary lines
+    pub(crate) fn check(&self) -> &'static str {
+        self.theme.ok
+    }
+
+    /// Separator line under summary headers
+    pub(crate) fn separator(&self) -> &'static str {
+        self.theme.separator
+    }
+
+    /// `--format json` is a document of rustc diagnostics; commands without them refuse it
+    /// instead of printing text under a json label
+    pub fn reject_json(&self, command: &str) -> Result<()> {
+        if self.format == OutputFormat::Json {
+            anyhow::bail!(
+                "--format json is not supported by rtk {}; use --format jsonl or --format json-summary",
+                command
+            );
+        }
+        Ok(())
+    }
+
+    /// Render filtered output for display: colorized when enabled, plain otherwise
+    /// (documents such as SARIF are never colorized)
+    pub(crate) fn render(&self, filtered: &str) -> String {
+        if self.color && !self.format.is_document() {
+            colorize(filtered, &self.theme)
+        } else {
+            filtered.to_string()
+        }
+    }
+}
+
+/// Colorize a rendered summary: green success lines, red error headers, yellow warnings
+fn colorize(filtered: &str, theme: &Theme) -> String {
+    static COUNT_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let count_re = COUNT_RE.get_or_init(|| {
+        regex::Regex::new(r"\b(\d+) (errors?|warnings?)\b").expect("invalid count regex")
+    });
+
+    colored::control::set_override(true);
+
+    filtered
+        .lines()
+        .map(|line| {
+            if line.starts_with(theme.ok) {
+                line.green().to_string()
+            } else if line.starts_with("error") {
+                line.red().bold().to_string()
+            } else if line.starts_with("warning") {
+                line.yellow().to_string()
+            } else if line.starts_with("cargo ") {
+                count_re
+                    .replace_all(line, |caps: &regex::Captures| {
+                        let text = &caps[0];
+                        if &caps[1] == "0" {
+                            text.to_string()
+                        } else if caps[2].starts_with("error") {
+                            text.red().bold().to_string()
+                        } else {
+                            text.yellow().to_string()
+                        }
+                    })
+                    .to_string()
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A filter for the captured output of one cargo subcommand
+pub trait Filter: Sync {
+    /// The cargo subcommand it filters (`build`, `test`, ...)
+    fn name(&self) -> &str;
+    /// Other names the subcommand is invoked by (`b` for `build`)
+    fn aliases(&self) -> &[&str] {
+        &[]
+    }
+    fn filter(&self, raw: &str, opts: &FilterOptions) -> String;
+}
+
+/// One of rtk's own filter functions registered under its subcommand
+struct BuiltinFilter {
+    name: &'static str,
+    aliases: &'static [&'static str],
+    filter: fn(&str, &FilterOptions) -> String,
+}
+
+impl Filter for BuiltinFilter {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn aliases(&self) -> &[&str] {
+        self.aliases
+    }
+
+    fn filter(&self, raw: &str, opts: &FilterOptions) -> String {
+        (self.filter)(raw, opts)
+    }
+}
+
+/// Subcommands whose whole job is capture, filter, print (`cargo run`, `search` and
+/// `llvm-cov` need more than a filter and are dispatched in [`run`] directly)
+static FILTERS: &[BuiltinFilter] = &[
+    BuiltinFilter {
+        name: "build",
+        aliases: &["b"],
+        filter: filter_cargo_build,
+    },
+    BuiltinFilter {
+        name: "check",
+        aliases: &["c"],
+        filter: filter_cargo_build,
+    },
+    BuiltinFilter {
+        name: "test",
+        aliases: &["t"],
+        filter: filter_cargo_test,
+    },
+    BuiltinFilter {
+        name: "clippy",
+        aliases: &[],
+        filter: filter_cargo_clippy,
+    },
+    BuiltinFilter {
+        name: "install",
+        aliases: &[],
+        filter: filter_cargo_install,
+    },
+    BuiltinFilter {
+        name: "nextest",
+        aliases: &[],
+        filter: filter_cargo_nextest,
+    },
+    BuiltinFilter {
+        name: "publish",
+        aliases: &[],
+        filter: filter_cargo_publish,
+    },
+    BuiltinFilter {
+        name: "clean",
+        aliases: &[],
+        filter: filter_cargo_clean,
+    },
+    BuiltinFilter {
+        name: "uninstall",
+        aliases: &[],
+        filter: filter_cargo_uninstall,
+    },
+    BuiltinFilter {
+        name: "vendor",
+        aliases: &[],
+        filter: filter_cargo_vendor,
+    },
+    BuiltinFilter {
+        name: "machete",
+        aliases: &[],
+        filter: filter_cargo_machete,
+    },
+];
+
+/// The registered filter for `subcommand` (by name or alias)
+pub fn find_filter(subcommand: &str) -> Option<&'static dyn Filter> {
+    FILTERS
+        .iter()
+        .find(|f| f.name() == subcommand || f.aliases().contains(&subcommand))
+        .map(|f| f as &dyn Filter)
+}
+
+/// Capture `cargo <filter name> <args>` and print it through `filter`
+fn run_registered(
+    filter: &dyn Filter,
+    args: &[String],
+    opts: &FilterOptions,
+    verbose: u8,
+) -> Result<()> {
+    run_cargo_filtered(filter.name(), args, opts, verbose, |raw, opts| {
+        filter.filter(raw, opts)
+    })
+}
+
+impl CargoCommand {
+    /// The cargo subcommand this runs
+    fn name(&self) -> &'static str {
+        match self {
+            CargoCommand::Build => "build",
+            CargoCommand::Test => "test",
+            CargoCommand::Clippy => "clippy",
+            CargoCommand::Check => "check",
+            CargoCommand::Install => "install",
+            CargoCommand::Nextest => "nextest",
+            CargoCommand::Publish => "publish",
+            CargoCommand::Clean => "clean",
+            CargoCommand::Run => "run",
+            CargoCommand::Search => "search",
+            CargoCommand::Uninstall => "uninstall",
+            CargoCommand::Vendor => "vendor",
+            CargoCommand::Machete => "machete",
+            CargoCommand::LlvmCov { .. } => "llvm-cov",
+        }
+    }
+}
+
+pub fn run(cmd: CargoCommand, args: &[String], opts: &FilterOptions, verbose: u8) -> Result<()> {
     match cmd {
-        CargoCommand::Build => run_build(args, verbose),
-        CargoCommand::Test => run_test(args, verbose),
-        CargoCommand::Clippy => run_clippy(args, verbose),
-        CargoCommand::Check => run_check(args, verbose),
-        CargoCommand::Install => run_install(args, verbose),
-        CargoCommand::Nextest => run_nextest(args, verbose),
+        CargoCommand::Run => run_run(args, opts, verbose),
+        CargoCommand::Search => run_search(args, opts, verbose),
+        CargoCommand::LlvmCov { threshold } => run_llvm_cov(args, threshold, opts, verbose),
+        other => match find_filter(other.name()) {
+            Some(filter) => run_registered(filter, args, opts, verbose),
+            None => anyhow::bail!("No filter registered for cargo {}", other.name()),
+        },
     }
 }
 
-/// Generic cargo command runner with filtering
-fn run_cargo_filtered<F>(subcommand: &str, args: &[String], verbose: u8, filter_fn: F) -> Result<()>
-where
-    F: Fn(&str) -> String,
-{
-    let timer = tracking::TimedExecution::start();
+/// Cargo executable: `--cargo-bin`, else `$CARGO` (set by cargo for its children), else `cargo`
+fn cargo_program(flag: Option<&Path>, env: Option<OsString>) -> OsString {
+    flag.map(|path| path.as_os_str().to_owned())
+        .or(env.filter(|bin| !bin.is_empty()))
+        .unwrap_or_else(|| OsString::from("cargo"))
+}
+
+/// Captured result of one cargo invocation
+pub struct CargoOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+    /// Killed by `--timeout` (`exit_code` is then [`TIMEOUT_EXIT_CODE`])
+    pub timed_out: bool,
+}
 
-    let mut cmd = Command::new("cargo");
-    cmd.arg(subcommand);
-    for arg in args {
-        cmd.arg(arg);
+/// Exit status after `--timeout` kills cargo, as GNU `timeout` uses
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Wall-clock limit for one cargo invocation (`--timeout`): `500ms`, `90s`, `10m`, `1h`;
+/// a bare number is seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timeout(pub Duration);
+
+impl FromStr for Timeout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+        let n: u64 = number
+            .parse()
+            .map_err(|_| format!("Invalid timeout: {} (e.g. 90s, 10m, 1h)", s))?;
+        let duration = match unit {
+            "ms" => Duration::from_millis(n),
+            "" | "s" => Duration::from_secs(n),
+            "m" => Duration::from_secs(n * 60),
+            "h" => Duration::from_secs(n * 3600),
+            _ => return Err(format!("Invalid timeout unit: {} (ms, s, m, h)", unit)),
+        };
+        if duration.is_zero() {
+            return Err("Timeout must be greater than zero".to_string());
+        }
+        Ok(Timeout(duration))
     }
+}
+
+impl fmt::Display for Timeout {
+    /// Largest whole unit: `1h`, `10m`, `90s`, `500ms`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = self.0.as_millis();
+        match ms {
+            _ if ms.is_multiple_of(3_600_000) => write!(f, "{}h", ms / 3_600_000),
+            _ if ms.is_multiple_of(60_000) => write!(f, "{}m", ms / 60_000),
+            _ if ms.is_multiple_of(1000) => write!(f, "{}s", ms / 1000),
+            _ => write!(f, "{}ms", ms),
+        }
+    }
+}
 
+impl CargoOutput {
+    /// stdout and stderr joined, as fed to the filters
+    pub fn raw(&self) -> String {
+        format!("{}\n{}", self.stdout, self.stderr)
+    }
+}
+
+/// Run `cargo <subcommand> <args>` to completion, capturing both streams.
+///
+/// `target_dir` overrides `CARGO_TARGET_DIR` (used to run stages concurrently without lock contention).
+pub fn capture(
+    subcommand: &str,
+    args: &[String],
+    target_dir: Option<&Path>,
+    opts: &FilterOptions,
+    verbose: u8,
+) -> Result<CargoOutput> {
+    capture_streaming(subcommand, args, target_dir, opts, verbose, None)
+}
+
+/// The `cargo <subcommand> <args>` command whose output gets filtered.
+///
+/// `CARGO_TERM_COLOR=never` keeps the captured text plain (filters match on
+/// `starts_with("error[")`), except with `--raw`, where cargo's own color choice is kept.
+fn capture_command(
+    subcommand: &str,
+    args: &[String],
+    target_dir: Option<&Path>,
+    opts: &FilterOptions,
+) -> Command {
+    let mut cmd = opts.cargo_command();
+    cmd.arg(subcommand).args(args);
+    if !opts.raw {
+        cmd.env("CARGO_TERM_COLOR", "never");
+    }
+    if let Some(dir) = target_dir {
+        cmd.env("CARGO_TARGET_DIR", dir);
+    }
+    cmd
+}
+
+/// [`capture`], also handing each stderr line to `on_stderr_line` as cargo prints it
+fn capture_streaming(
+    subcommand: &str,
+    args: &[String],
+    target_dir: Option<&Path>,
+    opts: &FilterOptions,
+    verbose: u8,
+    on_stderr_line: Option<&mut dyn FnMut(&str)>,
+) -> Result<CargoOutput> {
+    let mut cmd = capture_command(subcommand, args, target_dir, opts);
     if verbose > 0 {
         eprintln!("Running: cargo {} {}", subcommand, args.join(" "));
     }
 
+    if opts.timeout.is_some() || on_stderr_line.is_some() {
+        return capture_child(
+            cmd,
+            subcommand,
+            opts.timeout.map(|t| t.0),
+            on_stderr_line.unwrap_or(&mut |_| {}),
+        );
+    }
+
     let output = cmd
         .output()
         .with_context(|| format!("Failed to run cargo {}", subcommand))?;
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    Ok(CargoOutput {
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        exit_code: exit_code(&output.status),
+        timed_out: false,
+    })
+}
+
+/// Like `Command::output`, but stderr is read line by line (each handed to `on_stderr_line`)
+/// and, with a `timeout`, cargo and everything it started are killed once it passes.
+///
+/// With a timeout, cargo leads its own process group on Unix so test binaries and build
+/// scripts die with it (this also means Ctrl-C in the terminal reaches only rtk, not cargo).
+fn capture_child(
+    mut cmd: Command,
+    subcommand: &str,
+    timeout: Option<Duration>,
+    on_stderr_line: &mut dyn FnMut(&str),
+) -> Result<CargoOutput> {
+    use std::io::BufRead;
+    use std::process::Stdio;
+    use std::sync::mpsc::{self, RecvTimeoutError};
+
+    const POLL: Duration = Duration::from_millis(50);
+
+    #[cfg(unix)]
+    if timeout.is_some() {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run cargo {}", subcommand))?;
+
+    // Drain both pipes concurrently so a chatty cargo never blocks on a full buffer
+    let stdout_pipe = child.stdout.take();
+    let stdout = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = stdout_pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        String::from_utf8_lossy(&buf).into_owned()
+    });
+    let (tx, rx) = mpsc::channel::<String>();
+    let stderr_pipe = child.stderr.take();
+    std::thread::spawn(move || {
+        let Some(pipe) = stderr_pipe else {
+            return;
+        };
+        let mut reader = std::io::BufReader::new(pipe);
+        let mut buf = Vec::new();
+        while reader.read_until(b'\n', &mut buf).is_ok_and(|n| n > 0) {
+            if tx.send(String::from_utf8_lossy(&buf).into_owned()).is_err() {
+                break;
+            }
+            buf.clear();
+        }
+    });
+
+    let deadline = timeout.map(|t| std::time::Instant::now() + t);
+    let mut stderr = String::new();
+    let mut stderr_open = true;
+    let (status, timed_out) = loop {
+        if stderr_open {
+            match rx.recv_timeout(POLL) {
+                Ok(line) => {
+                    on_stderr_line(line.trim_end_matches(['\n', '\r']));
+                    stderr.push_str(&line);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => stderr_open = false,
+            }
+        } else if let Some(status) = child.try_wait()? {
+            break (Some(status), false);
+        } else {
+            std::thread::sleep(POLL);
+        }
+        if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+            kill_process_tree(&mut child);
+            stderr.extend(rx.try_iter());
+            break (child.wait().ok(), true);
+        }
+    };
+
+    Ok(CargoOutput {
+        stdout: stdout.join().unwrap_or_default(),
+        stderr,
+        exit_code: match status {
+            Some(status) if !timed_out => exit_code(&status),
+            _ => TIMEOUT_EXIT_CODE,
+        },
+        timed_out,
+    })
+}
+
+/// Kill `child` and its descendants: its process group on Unix, `taskkill /T` on Windows
+fn kill_process_tree(child: &mut std::process::Child) {
+    #[cfg(unix)]
+    let killed = Command::new("kill")
+        .args(["-s", "KILL", "--", &format!("-{}", child.id())])
+        .status()
+        .is_ok_and(|s| s.success());
+    #[cfg(windows)]
+    let killed = Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &child.id().to_string()])
+        .status()
+        .is_ok_and(|s| s.success());
+    #[cfg(not(any(unix, windows)))]
+    let killed = false;
+
+    if !killed {
+        let _ = child.kill();
+    }
+}
+
+/// Report a `--timeout` kill, record the run, and exit with [`TIMEOUT_EXIT_CODE`]
+fn exit_timed_out(
+    timer: &tracking::TimedExecution,
+    cmd: &str,
+    rtk_cmd: &str,
+    raw: &str,
+    opts: &FilterOptions,
+) -> ! {
+    let message = match opts.timeout {
+        Some(timeout) => format!("rtk: command timed out after {} (killed)", timeout),
+        None => "rtk: command timed out (killed)".to_string(),
+    };
+    eprintln!("{}", message);
+    timer.track(cmd.trim(), rtk_cmd.trim(), raw, &message);
+    std::process::exit(TIMEOUT_EXIT_CODE);
+}
+
+/// With `--diff`, compare this run's diagnostic fingerprints to the previous `--diff` run
+/// and record them as the next baseline.
+///
+/// Snapshots are kept per project directory and command for build/check/clippy. Runs
+/// without `--diff`, or with `tracking.enabled = false`, write nothing, so the first
+/// `--diff` only records the baseline.
+fn diagnostics_diff(
+    subcommand: &str,
+    args: &[String],
+    raw: &str,
+    opts: &FilterOptions,
+) -> Option<String> {
+    if !opts.diff
+        || opts.format.is_document()
+        || !matches!(subcommand, "build" | "check" | "clippy")
+        || !tracking::is_enabled()
+    {
+        return None;
+    }
+
+    let clean = normalize_newlines(&strip_ansi(raw));
+    let mut current: Vec<String> = cargo_report::parse_diagnostics(&clean)
+        .iter()
+        .map(cargo_report::fingerprint)
+        .collect();
+    current.sort();
+    current.dedup();
+
+    let project = project_key()?;
+    let kind = format!("cargo {} {}", subcommand, args.join(" "))
+        .trim()
+        .to_string();
+    let tracker = tracking::Tracker::new().ok()?;
+    let previous = tracker.load_snapshot(&project, &kind).ok().flatten();
+    let _ = tracker.save_snapshot(&project, &kind, &current);
+
+    Some(match previous {
+        Some(previous) => {
+            let diff = cargo_report::diff_fingerprints(&previous, &current);
+            let rendered = cargo_report::format_diff(&diff, opts.max_errors);
+            if opts.summary_only {
+                rendered.lines().next().unwrap_or_default().to_string()
+            } else {
+                rendered
+            }
+        }
+        None => format!(
+            "diff: no previous run recorded ({} diagnostics saved as baseline)",
+            current.len()
+        ),
+    })
+}
+
+/// Snapshot kind holding the names of the tests that failed in the last `cargo test`
+const FAILED_TESTS_SNAPSHOT: &str = "cargo test failed";
+
+fn project_key() -> Option<String> {
+    Some(std::env::current_dir().ok()?.display().to_string())
+}
+
+/// Remember which tests failed so `rtk cargo test --failed` can rerun just those
+fn record_failed_tests(raw: &str) {
+    let failed = parse_failed_tests(&normalize_newlines(&strip_ansi(raw)));
+    if let (Some(project), Ok(tracker)) = (project_key(), tracking::Tracker::new()) {
+        let _ = tracker.save_snapshot(&project, FAILED_TESTS_SNAPSHOT, &failed);
+    }
+}
+
+/// Cargo test args that rerun only the tests that failed last time (`--failed`).
+///
+/// Names go after `--` as exact libtest filters. Without a recorded failure the
+/// args are returned unchanged, so everything runs.
+pub fn failed_test_args(args: &[String]) -> Vec<String> {
+    let failed = project_key()
+        .and_then(|project| {
+            tracking::Tracker::new()
+                .ok()?
+                .load_snapshot(&project, FAILED_TESTS_SNAPSHOT)
+                .ok()
+                .flatten()
+        })
+        .unwrap_or_default();
+
+    if failed.is_empty() {
+        eprintln!("note: no previous test failures recorded, running all tests");
+        return args.to_vec();
+    }
+    eprintln!("Rerunning {} failed test(s)", failed.len());
+    with_test_filters(args, &failed)
+}
+
+fn with_test_filters(args: &[String], names: &[String]) -> Vec<String> {
+    let mut result = args.to_vec();
+    if !result.iter().any(|a| a == "--") {
+        result.push("--".to_string());
+    }
+    result.push("--exact".to_string());
+    result.extend(names.iter().cloned());
+    result
+}
+
+/// Filter captured output with the filter for `kind`
+pub fn filter(kind: FilterKind, raw: &str, opts: &FilterOptions) -> String {
+    apply_filter(raw, opts, sarif_tool(&kind.to_string()), kind.filter_fn())
+}
+
+/// Generic cargo command runner with filtering
+fn run_cargo_filtered<F>(
+    subcommand: &str,
+    args: &[String],
+    opts: &FilterOptions,
+    verbose: u8,
+    filter_fn: F,
+) -> Result<()>
+where
+    F: Fn(&str, &FilterOptions) -> String,
+{
+    let timer = tracking::TimedExecution::start();
+
+    // `--format jsonl` prints each diagnostic record as soon as cargo finishes reporting it
+    let streaming = opts.format == OutputFormat::Jsonl && !opts.raw && !opts.output_only;
+    // Output is buffered until cargo exits; show signs of life on an interactive stderr.
+    // Streamed records are those signs already, and a redrawn line would garble them.
+    let spinner =
+        (!streaming).then(|| Spinner::start(&format!("cargo {}", subcommand), &opts.theme));
+    let mut stream = cargo_report::JsonlStream::default();
+    let mut emit = |line: &str| {
+        for record in stream.push_line(&strip_ansi(line)) {
+            println!("{}", record);
+        }
+    };
+    let CargoOutput {
+        stdout,
+        stderr,
+        exit_code,
+        timed_out,
+    } = capture_streaming(
+        subcommand,
+        args,
+        None,
+        opts,
+        verbose,
+        streaming.then_some(&mut emit as &mut dyn FnMut(&str)),
+    )?;
+    if streaming {
+        for record in stream.finish() {
+            println!("{}", record);
+        }
+    }
+    if let Some(spinner) = spinner {
+        spinner.finish();
+    }
     let raw = format!("{}\n{}", stdout, stderr);
+    let tracked = format!("{} {} {}", opts.cargo_display(), subcommand, args.join(" "));
+    if opts.save_raw {
+        // stderr, so it never ends up inside a JSON/SARIF document or `--output` file
+        match tracking::save_raw(&tracked, &raw) {
+            Ok(path) => eprintln!("rtk: raw output saved to {}", path.display()),
+            Err(e) => eprintln!("rtk: {:#}", e),
+        }
+    }
+
+    if timed_out {
+        exit_timed_out(&timer, &tracked, &format!("rtk {}", tracked), &raw, opts);
+    }
+
+    // Escape hatch for filter bugs: pass both streams through untouched, still tracked
+    if opts.raw {
+        return print_raw(&timer, &tracked, &stdout, &stderr, exit_code);
+    }
+
+    let mut filtered = apply_filter(&raw, opts, sarif_tool(subcommand), filter_fn);
+    // A filter that swallowed everything must never hide a real error behind a blank summary
+    if filtered.trim().is_empty() && !raw.trim().is_empty() {
+        eprintln!("rtk: filter produced no output, showing raw");
+        return print_raw(&timer, &tracked, &stdout, &stderr, exit_code);
+    }
+    if let Some(diff) = diagnostics_diff(subcommand, args, &raw, opts) {
+        filtered = format!("{}\n\n{}", filtered, diff);
+    }
+    if let Some(packages) = package_summary(subcommand, args, &raw, opts) {
+        filtered = format!("{}\n\n{}", filtered, packages);
+    }
+    if subcommand == "test" {
+        record_failed_tests(&raw);
+    }
+
+    // Document formats (SARIF) keep stdout parseable: the tee hint goes to stderr
+    let mut rendered = opts.render(&filtered);
+    if opts.format == OutputFormat::JsonSummary {
+        rendered = cargo_report::counts_record(
+            subcommand,
+            &normalize_newlines(&strip_ansi(&raw)),
+            &filtered,
+            timer.elapsed_ms(),
+        );
+    }
+    if streaming {
+        // The diagnostic records were already streamed; only the summary record is left
+        rendered = rendered.lines().last().unwrap_or("").to_string();
+    }
+    if opts.show_savings && !opts.format.is_document() {
+        rendered = append_savings(&rendered, raw.len(), filtered.len());
+    }
+    if opts.include_command && !opts.format.is_document() {
+        rendered = format!(
+            "{}\n{}",
+            command_line(opts.toolchain.as_deref(), subcommand, args),
+            rendered
+        );
+    }
+    // A failed write is reported now but only changes the exit status when cargo succeeded
+    let mut write_failed = false;
+    if let Some(path) = &opts.output {
+        // Streamed records never made it into `rendered`; the file gets all of them
+        let document = if streaming {
+            filtered.clone()
+        } else {
+            strip_ansi(&rendered)
+        };
+        if let Err(e) = write_output(path, &document) {
+            eprintln!("rtk: {:#}", e);
+            write_failed = true;
+        }
+    }
+    match crate::tee::tee_and_hint(&raw, &format!("cargo_{}", subcommand), exit_code) {
+        Some(hint) if opts.format.is_document() || opts.output_only => {
+            if !opts.output_only {
+                println!("{}", rendered);
+            }
+            eprintln!("{}", hint);
+        }
+        Some(hint) => println!("{}\n{}", rendered, hint),
+        None if opts.output_only => {}
+        None => println!("{}", rendered),
+    }
+
+    timer.track(&tracked, &format!("rtk {}", tracked), &raw, &filtered);
+
+    // Propagate cargo's exact status (101 for test failures, 128+N for signals)
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+    if let Some(message) = warning_gate(subcommand, &raw, opts.fail_over) {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    }
+    if write_failed {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// `--output`: the filtered result plus a trailing newline, creating parent directories
+fn write_output(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, format!("{}\n", content.trim_end()))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// `--fail-on-warnings` / `--fail-over N` for build, check and clippy: the failure message
+/// when the run reported more than `limit` warnings
+fn warning_gate(subcommand: &str, raw: &str, limit: Option<usize>) -> Option<String> {
+    let limit = limit?;
+    if !matches!(subcommand, "build" | "check" | "clippy") {
+        return None;
+    }
+    let warnings = cargo_report::parse_diagnostics(&normalize_newlines(&strip_ansi(raw)))
+        .iter()
+        .filter(|issue| issue.severity == crate::parser::LintSeverity::Warning)
+        .count();
+    if warnings <= limit {
+        return None;
+    }
+    Some(if limit == 0 {
+        format!(
+            "rtk: failing on {} warning{} (--fail-on-warnings)",
+            warnings,
+            if warnings == 1 { "" } else { "s" }
+        )
+    } else {
+        format!(
+            "rtk: {} warnings, over the limit of {} (--fail-over)",
+            warnings, limit
+        )
+    })
+}
+
+/// `--show-savings`: ` [rtk: 4.2 KB -> 340 B, 92%]` on the first line of `rendered`, from
+/// the same raw and filtered text the run is tracked with
+fn append_savings(rendered: &str, raw_bytes: usize, filtered_bytes: usize) -> String {
+    let saved = if raw_bytes == 0 {
+        0
+    } else {
+        100 - (filtered_bytes.min(raw_bytes) * 100).div_ceil(raw_bytes)
+    };
+    let note = format!(
+        " [rtk: {} -> {}, {}%]",
+        format_bytes(raw_bytes),
+        format_bytes(filtered_bytes),
+        saved
+    );
+    match rendered.split_once('\n') {
+        Some((first, rest)) => format!("{}{}\n{}", first, note, rest),
+        None => format!("{}{}", rendered, note),
+    }
+}
+
+/// `340 B`, `4.2 KB`, `1.3 MB`
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// `$ cargo build --release`, shell-quoted so it can be copy-pasted (`--include-command`)
+fn command_line(toolchain: Option<&str>, subcommand: &str, args: &[String]) -> String {
+    let mut words: Vec<OsString> = toolchain
+        .map(|t| format!("+{}", t).into())
+        .into_iter()
+        .collect();
+    words.push(OsString::from(subcommand));
+    words.extend(args.iter().map(OsString::from));
+    format!("$ cargo {}", tracking::args_display(&words))
+}
+
+/// Pass cargo's streams through untouched (still tracked), exiting with its status
+fn print_raw(
+    timer: &tracking::TimedExecution,
+    command: &str,
+    stdout: &str,
+    stderr: &str,
+    exit_code: i32,
+) -> Result<()> {
+    print!("{}", stdout);
+    eprint!("{}", stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    timer.track(command, &format!("rtk {}", command), &raw, &raw);
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+/// Run a filter on captured cargo output.
+///
+/// ANSI codes are stripped first: with `CARGO_TERM_COLOR=always` (or a pseudo-TTY)
+/// lines start with `\x1b[...` and every `starts_with("error[")` check would miss.
+/// CRLF line endings (Windows) are normalized to LF in the same pass.
+/// The raw text (with codes) is still what gets tee'd and tracked.
+/// Non-text formats append to (GitHub annotations) or replace (SARIF) the summary.
+fn apply_filter<F>(raw: &str, opts: &FilterOptions, tool: &str, filter_fn: F) -> String
+where
+    F: Fn(&str, &FilterOptions) -> String,
+{
+    let clean = normalize_newlines(&strip_ansi(raw));
+    let mut filtered = filter_fn(&clean, opts);
+    if let Some(root) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| workspace_root(&cwd))
+    {
+        filtered = normalize_paths(&filtered, &root, opts.abs_paths);
+    }
+    if opts.explain {
+        filtered.push_str(&format_explanations(&error_codes(&clean), rustc_explain));
+    }
+    // The lock line itself is noise, but a long wait is what explains a slow run
+    if waited_on_file_lock(&clean) && !filtered.is_empty() {
+        filtered.push_str(&format!("\n{} waited on file lock", opts.theme.warn));
+    }
+    if opts.summary_only {
+        filtered = summary_line(&filtered, opts.check());
+    }
+    cargo_report::format_output(opts.format, tool, &filtered, &clean)
+}
+
+/// `E0308`-style codes from `error[E0308]` headers, each once, in order of appearance
+fn error_codes(output: &str) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+    for line in output.lines() {
+        let Some(code) = line
+            .strip_prefix("error[")
+            .and_then(|rest| rest.split_once(']'))
+            .map(|(code, _)| code)
+        else {
+            continue;
+        };
+        if !codes.iter().any(|c| c == code) {
+            codes.push(code.to_string());
+        }
+    }
+    codes
+}
+
+/// `EXPLANATIONS (n):` section with one `CODE: summary` line per code that has one
+fn format_explanations<F>(codes: &[String], explain: F) -> String
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let lines: Vec<String> = codes
+        .iter()
+        .filter_map(|code| explain(code).map(|text| format!("{}: {}", code, text)))
+        .collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!("\n\nEXPLANATIONS ({}):\n{}", lines.len(), lines.join("\n"))
+}
+
+/// First paragraph of `rustc --explain <code>`, fetched once per code per process
+/// (`rtk ci` and `rtk watch` filter several runs in one invocation)
+fn rustc_explain(code: &str) -> Option<String> {
+    static CACHE: OnceLock<std::sync::Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(cached) = cache.lock().ok()?.get(code) {
+        return cached.clone();
+    }
+
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let explanation = Command::new(rustc)
+        .args(["--explain", code])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| first_paragraph(&String::from_utf8_lossy(&output.stdout)))
+        .filter(|text| !text.is_empty());
+    cache
+        .lock()
+        .ok()?
+        .insert(code.to_string(), explanation.clone());
+    explanation
+}
+
+/// The opening paragraph of an explanation, joined onto one line
+fn first_paragraph(text: &str) -> String {
+    text.trim_start()
+        .lines()
+        .take_while(|line| !line.trim().is_empty())
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Workspace root: the nearest ancestor whose `Cargo.toml` declares `[workspace]`,
+/// else the nearest directory with a `Cargo.toml` (single-crate project).
+fn workspace_root(start: &Path) -> Option<PathBuf> {
+    let mut nearest = None;
+    for dir in start.ancestors() {
+        let manifest = dir.join("Cargo.toml");
+        let Ok(content) = std::fs::read_to_string(&manifest) else {
+            continue;
+        };
+        if content
+            .lines()
+            .any(|line| line.trim_start().starts_with("[workspace"))
+        {
+            return Some(dir.to_path_buf());
+        }
+        nearest.get_or_insert_with(|| dir.to_path_buf());
+    }
+    nearest
+}
+
+/// Rewrite `--> path:line:col` locations relative to `root` (or absolute with `absolute`).
+///
+/// Cargo prints member paths relative to the workspace root already; absolute paths
+/// outside the root (registry/git dependencies, std) are left as they are.
+fn normalize_paths(filtered: &str, root: &Path, absolute: bool) -> String {
+    static LOCATION_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let location_re = LOCATION_RE.get_or_init(|| {
+        regex::Regex::new(r"^(\s*--> )(.+?)(:\d+(?::\d+)?)?$").expect("invalid location regex")
+    });
+
+    filtered
+        .lines()
+        .map(|line| {
+            let Some(caps) = location_re.captures(line) else {
+                return line.to_string();
+            };
+            let path = Path::new(&caps[2]);
+            let rewritten = if absolute && path.is_relative() {
+                root.join(path)
+            } else if !absolute && path.is_absolute() {
+                match path.strip_prefix(root) {
+                    Ok(relative) => relative.to_path_buf(),
+                    Err(_) => return line.to_string(),
+                }
+            } else {
+                return line.to_string();
+            };
+            format!(
+                "{}{}{}",
+                &caps[1],
+                rewritten.display(),
+                caps.get(3).map_or("", |m| m.as_str())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Pick the one-line verdict out of a filtered summary (`--summary-only`).
+///
+/// Filters put their verdict in different places (first line for build/clippy,
+/// last line for nextest failures, `test result:` for cargo test), so look for
+/// the first `✓ cargo ...`/`cargo ...:` line, then a failing `test result:`.
+fn summary_line(filtered: &str, check: &str) -> String {
+    let lines: Vec<&str> = filtered.lines().map(str::trim).collect();
+    lines
+        .iter()
+        .find(|l| l.starts_with(check) || l.starts_with("cargo "))
+        .or_else(|| lines.iter().find(|l| l.starts_with("test result: FAILED")))
+        .or_else(|| lines.iter().find(|l| l.starts_with("test result:")))
+        .or_else(|| lines.iter().find(|l| !l.is_empty()))
+        .map(|l| l.to_string())
+        .unwrap_or_default()
+}
+
+/// SARIF driver name for a cargo subcommand
+fn sarif_tool(subcommand: &str) -> &'static str {
+    if subcommand == "clippy" {
+        "clippy"
+    } else {
+        "cargo"
+    }
+}
+
+/// Filter already-captured cargo output read from stdin (`rtk filter build < log`)
+pub fn run_stdin(kind: FilterKind, opts: &FilterOptions, verbose: u8) -> Result<()> {
+    let mut raw = String::new();
+    std::io::stdin()
+        .read_to_string(&mut raw)
+        .context("Failed to read stdin")?;
+    filter_captured(kind, &raw, "stdin", opts, verbose)
+}
+
+/// Filter a saved cargo log (`rtk filter build --input cargo.log`)
+pub fn run_file(kind: FilterKind, path: &Path, opts: &FilterOptions, verbose: u8) -> Result<()> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let raw = String::from_utf8_lossy(&bytes);
+    filter_captured(kind, &raw, &path.display().to_string(), opts, verbose)
+}
+
+fn filter_captured(
+    kind: FilterKind,
+    raw: &str,
+    source: &str,
+    opts: &FilterOptions,
+    verbose: u8,
+) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let kind = kind.resolve(&strip_ansi(raw));
+    if verbose > 0 {
+        eprintln!("Filtering {} as cargo {}", source, kind);
+    }
+
+    let filtered = if opts.raw {
+        raw.to_string()
+    } else {
+        filter(kind, raw, opts)
+    };
+    if opts.raw {
+        print!("{}", filtered);
+    } else if opts.format == OutputFormat::JsonSummary {
+        let clean = normalize_newlines(&strip_ansi(raw));
+        let command = kind.to_string();
+        println!(
+            "{}",
+            cargo_report::counts_record(&command, &clean, &filtered, timer.elapsed_ms())
+        );
+    } else {
+        println!("{}", opts.render(&filtered));
+    }
+
+    timer.track(
+        &format!("cargo {} ({})", kind, source),
+        &format!("rtk filter {}", kind),
+        raw,
+        &filtered,
+    );
+
+    Ok(())
+}
+
+fn run_llvm_cov(
+    args: &[String],
+    threshold: Option<f64>,
+    opts: &FilterOptions,
+    verbose: u8,
+) -> Result<()> {
+    let below = std::cell::Cell::new(false);
+    run_cargo_filtered("llvm-cov", args, opts, verbose, |output, opts| {
+        let (filtered, under) = filter_cargo_llvm_cov(output, opts, threshold);
+        below.set(under);
+        filtered
+    })?;
+    // cargo itself succeeded; the threshold is what gates the PR
+    if below.get() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_search(args: &[String], opts: &FilterOptions, verbose: u8) -> Result<()> {
+    let limit = search_limit(args);
+    run_cargo_filtered("search", args, opts, verbose, |output, opts| {
+        filter_cargo_search(output, opts, limit)
+    })
+}
+
+/// `--limit N` / `--limit=N` from the search arguments (cargo's default is 10)
+fn search_limit(args: &[String]) -> usize {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let value = match arg.strip_prefix("--limit=") {
+            Some(value) => Some(value),
+            None if arg == "--limit" => iter.next().map(String::as_str),
+            None => None,
+        };
+        if let Some(n) = value.and_then(|v| v.parse().ok()) {
+            return n;
+        }
+    }
+    10
+}
+
+/// `cargo run`: compile through the build filter, then hand the terminal to the program.
+///
+/// Only the compile is captured. The built executable (found in cargo's JSON artifact
+/// messages) is then started directly with inherited stdin/stdout/stderr, so interactive
+/// and piped programs behave as without rtk and cargo never replays the warnings.
+/// When the executable is ambiguous (several bins, no `--bin`), `cargo run --quiet` picks.
+fn run_run(args: &[String], opts: &FilterOptions, verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+    let (cargo_args, program_args) = split_program_args(args);
+
+    // Rendered diagnostics stay on stderr for the filter; stdout becomes the JSON stream
+    let mut build_args = cargo_args.to_vec();
+    build_args.push("--message-format=json-render-diagnostics".to_string());
+    let spinner = Spinner::start("cargo build", &opts.theme);
+    let output = capture("build", &build_args, None, opts, verbose)?;
+    spinner.finish();
+    if output.timed_out {
+        exit_timed_out(
+            &timer,
+            &format!("cargo run {}", args.join(" ")),
+            &format!("rtk cargo run {}", args.join(" ")),
+            &output.stderr,
+            opts,
+        );
+    }
+
+    let filtered = apply_filter(&output.stderr, opts, "cargo", filter_cargo_build);
+    let cmd = format!("cargo run {}", args.join(" "));
+    timer.track(
+        cmd.trim(),
+        &format!("rtk cargo run {}", args.join(" ")),
+        &output.stderr,
+        &filtered,
+    );
+
+    if output.exit_code != 0 {
+        println!("{}", opts.render(&filtered));
+        std::process::exit(output.exit_code);
+    }
+    // Compiled with warnings: the summary line only, on stderr to keep the program's stdout clean
+    if !filtered.starts_with(opts.check()) {
+        eprintln!("{}", filtered.lines().next().unwrap_or(""));
+    }
+
+    let example = cargo_args.iter().any(|a| a.starts_with("--example"));
+    let mut cmd = match run_target(&output.stdout, example) {
+        Some(target) => {
+            let mut cmd = Command::new(&target.executable);
+            if let Some(dir) = target.manifest_dir {
+                cmd.env("CARGO_MANIFEST_DIR", dir);
+            }
+            cmd.args(program_args);
+            cmd
+        }
+        None => {
+            let mut cmd = opts.cargo_command();
+            cmd.arg("run");
+            // cargo rejects a repeated --quiet, and --quiet together with --verbose
+            if !cargo_args
+                .iter()
+                .any(|a| ["-q", "--quiet", "-v", "-vv", "--verbose"].contains(&a.as_str()))
+            {
+                cmd.arg("--quiet");
+            }
+            cmd.args(cargo_args).arg("--").args(program_args);
+            cmd
+        }
+    };
+    if verbose > 0 {
+        eprintln!("Running: {:?}", cmd);
+    }
+    let status = cmd.status().context("Failed to run the program")?;
+
+    let code = exit_code(&status);
+    if code != 0 {
+        std::process::exit(code);
+    }
+    Ok(())
+}
+
+/// The program `cargo run` would start, from `--message-format json` artifact messages
+#[derive(Debug, PartialEq)]
+struct RunTarget {
+    executable: PathBuf,
+    manifest_dir: Option<PathBuf>,
+}
+
+/// The single bin (or example, with `--example`) executable built; `None` when ambiguous
+fn run_target(json_stream: &str, example: bool) -> Option<RunTarget> {
+    let kind = if example { "example" } else { "bin" };
+    let mut targets: Vec<RunTarget> = Vec::new();
+    for line in json_stream.lines().filter(|l| l.starts_with('{')) {
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if message["reason"] != "compiler-artifact" {
+            continue;
+        }
+        let is_kind = message["target"]["kind"]
+            .as_array()
+            .is_some_and(|kinds| kinds.iter().any(|k| k == kind));
+        let Some(executable) = message["executable"].as_str().filter(|_| is_kind) else {
+            continue;
+        };
+        let target = RunTarget {
+            executable: PathBuf::from(executable),
+            manifest_dir: message["manifest_path"]
+                .as_str()
+                .and_then(|p| Path::new(p).parent())
+                .map(Path::to_path_buf),
+        };
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    match targets.len() {
+        1 => targets.pop(),
+        _ => None,
+    }
+}
+
+/// Split `cargo run` args at `--` into cargo's own and the program's
+fn split_program_args(args: &[String]) -> (&[String], &[String]) {
+    match args.iter().position(|a| a == "--") {
+        Some(i) => (&args[..i], &args[i + 1..]),
+        None => (args, &[]),
+    }
+}
+
+/// Another cargo process held the package cache or build directory lock
+fn waited_on_file_lock(output: &str) -> bool {
+    output.lines().any(|line| {
+        line.trim_start()
+            .starts_with("Blocking waiting for file lock")
+    })
+}
+
+/// Push a completed failure block (header + body) into the failures list, then clear the buffers.
+fn flush_failure_block(header: &mut String, body: &mut Vec<String>, failures: &mut Vec<String>) {
+    if header.is_empty() {
+        return;
+    }
+    let mut block = header.clone();
+    if !body.is_empty() {
+        block.push('\n');
+        block.push_str(&body.join("\n"));
+    }
+    failures.push(block);
+    header.clear();
+    body.clear();
+}
+
+/// Coverage percentages of one row of the llvm-cov summary table (`None` for `-`)
+#[derive(Debug, Default, PartialEq)]
+struct Coverage {
+    regions: Option<f64>,
+    functions: Option<f64>,
+    lines: Option<f64>,
+    branches: Option<f64>,
+}
+
+/// Filter cargo llvm-cov output - the `TOTAL` row as `✓ coverage: 84.2% lines, 79.1% regions`.
+///
+/// The per-file table is dropped; with a threshold, files whose line coverage is under it
+/// are listed and the summary is marked failed. Returns whether total line coverage is
+/// under the threshold. Without a summary table (tests failed before the report) the
+/// output goes through the test filter instead.
+fn filter_cargo_llvm_cov(
+    output: &str,
+    opts: &FilterOptions,
+    threshold: Option<f64>,
+) -> (String, bool) {
+    let Some((total, files)) = parse_llvm_cov_table(output) else {
+        return (filter_cargo_test(output, opts), false);
+    };
+
+    let mut parts: Vec<String> = Vec::new();
+    for (label, pct) in [
+        ("lines", total.lines),
+        ("regions", total.regions),
+        ("functions", total.functions),
+        ("branches", total.branches),
+    ] {
+        if let Some(pct) = pct {
+            parts.push(format!("{:.1}% {}", pct, label));
+        }
+    }
+
+    let below_total = threshold.is_some_and(|t| total.lines.is_some_and(|pct| pct < t));
+    let mut lines = vec![match threshold {
+        Some(t) if below_total => format!(
+            "{} coverage: {} (below {}% threshold)",
+            opts.theme.fail,
+            parts.join(", "),
+            t
+        ),
+        _ => format!("{} coverage: {}", opts.check(), parts.join(", ")),
+    }];
+
+    if let Some(t) = threshold {
+        let low: Vec<String> = files
+            .iter()
+            .filter_map(|(file, cov)| {
+                cov.lines
+                    .filter(|pct| *pct < t)
+                    .map(|pct| format!("  {} {:.1}% lines", file, pct))
+            })
+            .collect();
+        if !low.is_empty() {
+            lines.push(format!("{} files below {}%:", low.len(), t));
+            lines.extend(low);
+        }
+    }
+    (lines.join("\n"), below_total)
+}
+
+/// Parse the llvm-cov summary table into the `TOTAL` row and the per-file rows.
+///
+/// Columns are found by name from the `Filename  Regions  Missed Regions  Cover ...` header
+/// (each `Cover` belongs to the metric before it; functions use `Executed`), so tables
+/// without branch columns parse the same way.
+fn parse_llvm_cov_table(output: &str) -> Option<(Coverage, Vec<(String, Coverage)>)> {
+    static SPLIT_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let split = SPLIT_RE.get_or_init(|| regex::Regex::new(r"\s{2,}").unwrap());
+
+    let mut lines = output.lines();
+    let header = lines.find(|l| l.trim_start().starts_with("Filename"))?;
+    let columns: Vec<&str> = split.split(header.trim()).collect();
+    let column = |metric: &str| {
+        let missed = format!("Missed {}", metric);
+        columns
+            .iter()
+            .position(|c| *c == missed)
+            .map(|i| i + 1)
+            .filter(|i| columns.get(*i) == Some(&"Cover"))
+    };
+    let regions = column("Regions");
+    let lines_col = column("Lines");
+    let branches = column("Branches");
+    let functions = columns.iter().position(|c| *c == "Executed");
+
+    let mut total = None;
+    let mut files = Vec::new();
+    for line in lines {
+        let cells: Vec<&str> = line.split_whitespace().collect();
+        if cells.len() != columns.len() {
+            continue;
+        }
+        let pct = |i: Option<usize>| {
+            i.and_then(|i| cells[i].strip_suffix('%'))
+                .and_then(|p| p.parse::<f64>().ok())
+        };
+        let cov = Coverage {
+            regions: pct(regions),
+            functions: pct(functions),
+            lines: pct(lines_col),
+            branches: pct(branches),
+        };
+        if cells[0] == "TOTAL" {
+            total = Some(cov);
+        } else {
+            files.push((cells[0].to_string(), cov));
+        }
+    }
+    total.map(|total| (total, files))
+}
+
+/// Filter cargo uninstall output - `✓ cargo uninstall: foo (removed /path)` per package.
+///
+/// An unknown package (`error: package ID specification `foo` did not match any packages`)
+/// becomes `foo is not installed`; any other error is kept in full.
+fn filter_cargo_uninstall(output: &str, opts: &FilterOptions) -> String {
+    let mut removed: Vec<String> = Vec::new();
+    let mut missing: Vec<String> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(path) = trimmed.strip_prefix("Removing ") {
+            removed.push(path.to_string());
+        } else if let Some(rest) = trimmed.strip_prefix("error: package ID specification `") {
+            if let Some((name, _)) = rest.split_once('`') {
+                missing.push(name.to_string());
+            }
+        } else if line.starts_with("error") || (!errors.is_empty() && !trimmed.is_empty()) {
+            errors.push(line.to_string());
+        }
+    }
+
+    let mut lines: Vec<String> = removed
+        .iter()
+        .map(|path| {
+            let name = std::path::Path::new(path)
+                .file_stem()
+                .map_or(path.as_str(), |s| s.to_str().unwrap_or(path));
+            format!(
+                "{} cargo uninstall: {} (removed {})",
+                opts.check(),
+                name,
+                path
+            )
+        })
+        .collect();
+    lines.extend(missing.iter().map(|name| {
+        format!(
+            "{} cargo uninstall: {} is not installed",
+            opts.theme.fail, name
+        )
+    }));
+    if !errors.is_empty() {
+        lines.push(format!("cargo uninstall: failed\n{}", errors.join("\n")));
+    }
+    if lines.is_empty() {
+        return format!("{} cargo uninstall", opts.check());
+    }
+    lines.join("\n")
+}
+
+/// Filter cargo vendor output - the crate count, then the `[source...]` config verbatim.
+///
+/// The per-crate `Vendoring foo v1.0.0 (...) to vendor/foo` lines collapse into the count;
+/// the `.cargo/config.toml` snippet cargo prints on stdout is the one part kept as-is.
+fn filter_cargo_vendor(output: &str, opts: &FilterOptions) -> String {
+    let mut vendored = 0;
+    let mut dir: Option<String> = None;
+    let mut config: Vec<&str> = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("Vendoring ") {
+            vendored += 1;
+            if dir.is_none() {
+                // "... to vendor/anyhow" -> "vendor"
+                dir = rest
+                    .rsplit_once(" to ")
+                    .and_then(|(_, path)| std::path::Path::new(path).parent())
+                    .map(|p| p.display().to_string());
+            }
+        } else if trimmed.starts_with("[source") || (!config.is_empty() && !trimmed.is_empty()) {
+            if let Some(path) = trimmed
+                .strip_prefix("directory = \"")
+                .and_then(|rest| rest.strip_suffix('"'))
+            {
+                dir = Some(path.to_string());
+            }
+            config.push(trimmed);
+        } else if line.starts_with("error") || (!errors.is_empty() && !trimmed.is_empty()) {
+            errors.push(line.to_string());
+        }
+    }
+
+    if !errors.is_empty() {
+        return format!("cargo vendor: failed\n{}", errors.join("\n"));
+    }
+
+    let mut result = format!(
+        "{} cargo vendor: {} crates vendored to {}/",
+        opts.check(),
+        vendored,
+        dir.as_deref().unwrap_or("vendor").trim_end_matches('/')
+    );
+    if !config.is_empty() {
+        result.push_str("\nAdd to .cargo/config.toml:\n");
+        // Keep the blank line between the two tables
+        result.push_str(&config.join("\n").replace("\n[source", "\n\n[source"));
+    }
+    result
+}
+
+/// Filter cargo machete output - `foo: unused serde, regex` per crate under a total.
+///
+/// cargo-machete lists each crate as `foo -- path/Cargo.toml:` followed by its unused
+/// dependencies, one per indented line; it exits 1 when it finds any, which is propagated.
+fn filter_cargo_machete(output: &str, opts: &FilterOptions) -> String {
+    let mut crates: Vec<(String, Vec<String>)> = Vec::new();
+    let mut in_crate = false;
+    let mut errors: Vec<&str> = Vec::new();
+
+    for line in output.lines() {
+        if let Some((name, _)) = line
+            .strip_suffix(':')
+            .and_then(|header| header.split_once(" -- "))
+            .filter(|(name, _)| !name.contains(char::is_whitespace))
+        {
+            crates.push((name.to_string(), Vec::new()));
+            in_crate = true;
+        } else if in_crate && line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            if let Some((_, deps)) = crates.last_mut() {
+                deps.push(line.trim().to_string());
+            }
+        } else {
+            in_crate = false;
+            if line.starts_with("error") {
+                errors.push(line);
+            }
+        }
+    }
 
-    let exit_code = output
-        .status
-        .code()
-        .unwrap_or(if output.status.success() { 0 } else { 1 });
-    let filtered = filter_fn(&raw);
+    let unused: usize = crates.iter().map(|(_, deps)| deps.len()).sum();
+    if unused == 0 {
+        if !errors.is_empty() {
+            return format!("cargo machete: failed\n{}", errors.join("\n"));
+        }
+        return format!("{} cargo machete: no unused dependencies", opts.check());
+    }
 
-    if let Some(hint) = crate::tee::tee_and_hint(&raw, &format!("cargo_{}", subcommand), exit_code)
-    {
-        println!("{}\n{}", filtered, hint);
-    } else {
-        println!("{}", filtered);
+    let mut lines = vec![format!(
+        "cargo machete: {} unused dependenc{} in {} crate{}",
+        unused,
+        if unused == 1 { "y" } else { "ies" },
+        crates.len(),
+        if crates.len() == 1 { "" } else { "s" }
+    )];
+    for (name, deps) in &crates {
+        lines.push(format!("{}: unused {}", name, deps.join(", ")));
     }
+    lines.join("\n")
+}
 
-    timer.track(
-        &format!("cargo {} {}", subcommand, args.join(" ")),
-        &format!("rtk cargo {} {}", subcommand, args.join(" ")),
-        &raw,
-        &filtered,
-    );
+/// Filter cargo clean output - one line with the files removed and space freed.
+///
+/// `-v` lists every `Removing <path>`; the closing `Removed N files, 1.2GiB total`
+/// (or `Summary ...` with `--dry-run`) carries the totals when cargo prints it.
+fn filter_cargo_clean(output: &str, opts: &FilterOptions) -> String {
+    let mut removing = 0;
+    let mut totals: Option<(String, String)> = None;
+    let mut dry_run = false;
+    let mut errors: Vec<String> = Vec::new();
 
-    if !output.status.success() {
-        std::process::exit(exit_code);
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Removing ") {
+            removing += 1;
+        } else if let Some(rest) = trimmed
+            .strip_prefix("Removed ")
+            .or_else(|| trimmed.strip_prefix("Summary "))
+        {
+            // "1234 files, 1.2GiB total"
+            let (files, size) = rest.split_once(", ").unwrap_or((rest, ""));
+            let size = size.trim_end_matches(" total").to_string();
+            totals = Some((files.to_string(), size));
+        } else if trimmed.starts_with("warning:") && trimmed.contains("--dry-run") {
+            dry_run = true;
+        } else if line.starts_with("error") || (!errors.is_empty() && !trimmed.is_empty()) {
+            errors.push(line.to_string());
+        }
     }
 
-    Ok(())
-}
-
-fn run_build(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("build", args, verbose, filter_cargo_build)
-}
+    if !errors.is_empty() {
+        return format!("cargo clean: failed\n{}", errors.join("\n"));
+    }
 
-fn run_test(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("test", args, verbose, filter_cargo_test)
+    let (files, size) = match totals {
+        Some((files, size)) => (files, size),
+        None if removing > 0 => (format!("{} files", removing), String::new()),
+        None => return format!("{} cargo clean", opts.check()),
+    };
+    let (label, verb) = if dry_run {
+        (" (dry-run)", "would remove")
+    } else {
+        ("", "removed")
+    };
+    if size.is_empty() {
+        format!("{} cargo clean{}: {} {}", opts.check(), label, verb, files)
+    } else {
+        format!(
+            "{} cargo clean{}: {} {} ({} {})",
+            opts.check(),
+            label,
+            verb,
+            files,
+            size,
+            if dry_run { "total" } else { "freed" }
+        )
+    }
 }
 
-fn run_clippy(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("clippy", args, verbose, filter_cargo_clippy)
-}
+/// Filter cargo search output - one aligned `name = "x.y.z"  # description` row per crate.
+///
+/// cargo pads each row to the longest name on the page and wraps long descriptions;
+/// rows are re-aligned here and capped at `limit`, keeping the `... and N more` footer.
+fn filter_cargo_search(output: &str, opts: &FilterOptions, limit: usize) -> String {
+    let mut rows: Vec<(String, String)> = Vec::new();
+    let mut more: usize = 0;
+    let mut errors: Vec<String> = Vec::new();
 
-fn run_check(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("check", args, verbose, filter_cargo_build)
-}
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("... and ") {
+            // "... and 1234 crates more (use --limit N to see more)"
+            more += rest
+                .split_whitespace()
+                .next()
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or(0);
+        } else if line.starts_with("error") || (!errors.is_empty() && !trimmed.is_empty()) {
+            errors.push(line.to_string());
+        } else if let Some((spec, desc)) = search_row(trimmed) {
+            rows.push((spec, desc));
+        } else if let Some(last) = rows.last_mut().filter(|_| line.starts_with(' ')) {
+            // Continuation of a wrapped description
+            if !trimmed.is_empty() && !trimmed.starts_with("note:") {
+                let text = trimmed.trim_start_matches('#').trim();
+                last.1 = format!("{} {}", last.1, text).trim().to_string();
+            }
+        }
+    }
 
-fn run_install(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("install", args, verbose, filter_cargo_install)
-}
+    if !errors.is_empty() {
+        return format!("cargo search: failed\n{}", errors.join("\n"));
+    }
+    if rows.is_empty() {
+        return format!("{} cargo search: no matching crates", opts.check());
+    }
 
-fn run_nextest(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("nextest", args, verbose, filter_cargo_nextest)
+    if rows.len() > limit {
+        more += rows.len() - limit;
+        rows.truncate(limit);
+    }
+    let width = rows
+        .iter()
+        .map(|(spec, _)| spec.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|(spec, desc)| {
+            if desc.is_empty() {
+                spec.clone()
+            } else {
+                format!("{:<width$}  # {}", spec, desc, width = width)
+            }
+        })
+        .collect();
+    if more > 0 {
+        lines.push(format!("... and {} more", more));
+    }
+    lines.join("\n")
 }
 
-/// Format crate name + version into a display string
-fn format_crate_info(name: &str, version: &str, fallback: &str) -> String {
-    if name.is_empty() {
-        fallback.to_string()
-    } else if version.is_empty() {
-        name.to_string()
-    } else {
-        format!("{} {}", name, version)
-    }
+/// `name = "x.y.z"    # description` -> (`name = "x.y.z"`, description)
+fn search_row(line: &str) -> Option<(String, String)> {
+    static ROW: OnceLock<regex::Regex> = OnceLock::new();
+    let re = ROW.get_or_init(|| {
+        regex::Regex::new(r#"^([A-Za-z0-9_-]+) = "([^"]+)"\s*(?:#\s?(.*))?$"#).unwrap()
+    });
+    let caps = re.captures(line)?;
+    Some((
+        format!("{} = \"{}\"", &caps[1], &caps[2]),
+        caps.get(3).map_or("", |m| m.as_str()).trim().to_string(),
+    ))
 }
 
-/// Filter cargo install output - strip dep compilation, keep installed/replaced/errors
-fn filter_cargo_install(output: &str) -> String {
-    let mut errors: Vec<String> = Vec::new();
-    let mut error_count = 0;
-    let mut compiled = 0;
+/// Filter cargo publish output - confirm the packaged crate or surface what blocked it.
+///
+/// Packaging/Verifying/Uploading phases collapse into one line; publish errors
+/// (dirty tree, missing metadata, verify build failures) are kept in full.
+fn filter_cargo_publish(output: &str, opts: &FilterOptions) -> String {
+    let (output, _) = split_sccache_stats(output);
+    let output = output.as_str();
+    let mut crate_info = String::new();
+    let mut packaged = String::new();
+    let mut dry_run = false;
+    let mut errors: Vec<Vec<String>> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut published: Vec<String> = Vec::new();
     let mut in_error = false;
-    let mut current_error = Vec::new();
-    let mut installed_crate = String::new();
-    let mut installed_version = String::new();
-    let mut replaced_lines: Vec<String> = Vec::new();
-    let mut already_installed = false;
-    let mut ignored_line = String::new();
 
     for line in output.lines() {
-        let trimmed = line.trim_start();
+        let trimmed = line.trim();
 
-        // Strip noise: dep compilation, downloading, locking, etc.
-        if trimmed.starts_with("Compiling") {
-            compiled += 1;
-            continue;
-        }
-        if trimmed.starts_with("Downloading")
-            || trimmed.starts_with("Downloaded")
-            || trimmed.starts_with("Locking")
-            || trimmed.starts_with("Updating")
-            || trimmed.starts_with("Adding")
-            || trimmed.starts_with("Finished")
-            || trimmed.starts_with("Blocking waiting for file lock")
+        // Phase lines: remember the crate, drop the noise
+        if let Some(rest) = trimmed
+            .strip_prefix("Packaging ")
+            .or_else(|| trimmed.strip_prefix("Verifying "))
+            .or_else(|| trimmed.strip_prefix("Uploading "))
         {
-            continue;
-        }
-
-        // Keep: Installing line (extract crate name + version)
-        if trimmed.starts_with("Installing") {
-            let rest = trimmed.strip_prefix("Installing").unwrap_or("").trim();
-            if !rest.is_empty() && !rest.starts_with('/') {
-                if let Some((name, version)) = rest.split_once(' ') {
-                    installed_crate = name.to_string();
-                    installed_version = version.to_string();
-                } else {
-                    installed_crate = rest.to_string();
-                }
+            if crate_info.is_empty() {
+                crate_info = rest.split(" (").next().unwrap_or(rest).to_string();
             }
+            in_error = false;
             continue;
         }
-
-        // Keep: Installed line (extract crate + version if not already set)
-        if trimmed.starts_with("Installed") {
-            let rest = trimmed.strip_prefix("Installed").unwrap_or("").trim();
-            if !rest.is_empty() && installed_crate.is_empty() {
-                let mut parts = rest.split_whitespace();
-                if let (Some(name), Some(version)) = (parts.next(), parts.next()) {
-                    installed_crate = name.to_string();
-                    installed_version = version.to_string();
-                }
-            }
+        if let Some(rest) = trimmed.strip_prefix("Packaged ") {
+            packaged = rest.split(" (").next().unwrap_or(rest).to_string();
+            in_error = false;
             continue;
         }
-
-        // Keep: Replacing/Replaced lines
-        if trimmed.starts_with("Replacing") || trimmed.starts_with("Replaced") {
-            replaced_lines.push(trimmed.to_string());
+        if is_noise_line(line) {
+            in_error = false;
             continue;
         }
 
-        // Keep: "Ignored package" (already up to date)
-        if trimmed.starts_with("Ignored package") {
-            already_installed = true;
-            ignored_line = trimmed.to_string();
+        // Real publish: keep the confirmation and any docs.rs pointer
+        if trimmed.starts_with("Uploaded ")
+            || trimmed.starts_with("Published ")
+            || trimmed.contains("docs.rs")
+        {
+            published.push(trimmed.to_string());
+            in_error = false;
             continue;
         }
 
-        // Keep: actionable warnings (e.g., "be sure to add `/path` to your PATH")
-        // Skip summary lines like "warning: `crate` generated N warnings"
-        if line.starts_with("warning:") {
-            if !(line.contains("generated") && line.contains("warning")) {
-                replaced_lines.push(line.to_string());
+        if trimmed.starts_with("warning:") {
+            in_error = false;
+            if trimmed.contains("aborting upload due to dry run") {
+                dry_run = true;
+            } else if !(trimmed.contains("generated") && trimmed.contains("warning")) {
+                warnings.push(trimmed.to_string());
             }
             continue;
         }
 
-        // Detect error blocks
-        if line.starts_with("error[") || line.starts_with("error:") {
+        if line.starts_with("error") {
             if line.contains("aborting due to") || line.contains("could not compile") {
+                in_error = false;
                 continue;
             }
-            if in_error && !current_error.is_empty() {
-                errors.push(current_error.join("\n"));
-                current_error.clear();
-            }
-            error_count += 1;
+            errors.push(vec![line.to_string()]);
             in_error = true;
-            current_error.push(line.to_string());
-        } else if in_error {
-            if line.trim().is_empty() && current_error.len() > 3 {
-                errors.push(current_error.join("\n"));
-                current_error.clear();
-                in_error = false;
-            } else {
-                current_error.push(line.to_string());
-            }
+            continue;
         }
-    }
-
-    if !current_error.is_empty() {
-        errors.push(current_error.join("\n"));
-    }
 
-    // Already installed / up to date
-    if already_installed {
-        let info = ignored_line.split('`').nth(1).unwrap_or(&ignored_line);
-        return format!("✓ cargo install: {} already installed", info);
+        // Error bodies can contain blank lines (e.g. the dirty-files list); keep
+        // everything up to the next phase/status line, minus the blanks.
+        if in_error && !trimmed.is_empty() {
+            if let Some(block) = errors.last_mut() {
+                block.push(line.to_string());
+            }
+        }
     }
 
-    // Errors
-    if error_count > 0 {
-        let crate_info = format_crate_info(&installed_crate, &installed_version, "");
-        let deps_info = if compiled > 0 {
-            format!(", {} deps compiled", compiled)
-        } else {
-            String::new()
-        };
-
-        let mut result = String::new();
-        if crate_info.is_empty() {
-            result.push_str(&format!(
-                "cargo install: {} error{}{}\n",
-                error_count,
-                if error_count > 1 { "s" } else { "" },
-                deps_info
-            ));
-        } else {
+    let name = if crate_info.is_empty() {
+        "package".to_string()
+    } else {
+        crate_info
+    };
+
+    if !errors.is_empty() {
+        let mut result = format!(
+            "cargo publish: {} error{} ({})\n",
+            errors.len(),
+            if errors.len() > 1 { "s" } else { "" },
+            name
+        );
+        result.push_str(opts.separator());
+        result.push('\n');
+        for block in errors.iter().take(opts.max_errors) {
+            result.push_str(&block.join("\n"));
+            result.push_str("\n\n");
+        }
+        if errors.len() > opts.max_errors {
             result.push_str(&format!(
-                "cargo install: {} error{} ({}{})\n",
-                error_count,
-                if error_count > 1 { "s" } else { "" },
-                crate_info,
-                deps_info
+                "... +{} more issues\n",
+                errors.len() - opts.max_errors
             ));
         }
-        result.push_str("═══════════════════════════════════════\n");
-
-        for (i, err) in errors.iter().enumerate().take(15) {
-            result.push_str(err);
-            result.push('\n');
-            if i < errors.len() - 1 {
-                result.push('\n');
-            }
-        }
-
-        if errors.len() > 15 {
-            result.push_str(&format!("\n... +{} more issues\n", errors.len() - 15));
-        }
-
         return result.trim().to_string();
     }
 
-    // Success
-    let crate_info = format_crate_info(&installed_crate, &installed_version, "package");
-
-    let mut result = format!(
-        "✓ cargo install ({}, {} deps compiled)",
-        crate_info, compiled
-    );
-
-    for line in &replaced_lines {
+    let mut result = if dry_run {
+        let size = if packaged.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", packaged)
+        };
+        format!(
+            "{} cargo publish (dry-run): {} ready{}",
+            opts.check(),
+            name,
+            size
+        )
+    } else {
+        format!("{} cargo publish: {}", opts.check(), name)
+    };
+    for line in warnings.iter().chain(published.iter()) {
         result.push_str(&format!("\n  {}", line));
     }
-
     result
 }
 
-/// Push a completed failure block (header + body) into the failures list, then clear the buffers.
-fn flush_failure_block(header: &mut String, body: &mut Vec<String>, failures: &mut Vec<String>) {
-    if header.is_empty() {
-        return;
-    }
-    let mut block = header.clone();
-    if !body.is_empty() {
-        block.push('\n');
-        block.push_str(&body.join("\n"));
-    }
-    failures.push(block);
-    header.clear();
-    body.clear();
+/// Whether captured output comes from cargo-nextest rather than libtest
+/// (`Starting N tests across M binaries` / `Summary [ 0.1s] N tests run`)
+pub(crate) fn is_nextest_output(output: &str) -> bool {
+    output.lines().map(str::trim_start).any(|l| {
+        l.starts_with("Summary [")
+            || (l.starts_with("Starting ")
+                && (l.contains(" tests across ") || l.contains(" test across ")))
+    })
 }
 
 /// Filter cargo nextest output - show failures + compact summary
-fn filter_cargo_nextest(output: &str) -> String {
+pub(crate) fn filter_cargo_nextest(output: &str, opts: &FilterOptions) -> String {
+    let (output, _) = split_sccache_stats(output);
+    let output = output.as_str();
     static SUMMARY_RE: OnceLock<regex::Regex> = OnceLock::new();
     let summary_re = SUMMARY_RE.get_or_init(|| {
         regex::Regex::new(
@@ -328,13 +2222,7 @@ fn filter_cargo_nextest(output: &str) -> String {
         let trimmed = line.trim();
 
         // Strip compilation noise
-        if trimmed.starts_with("Compiling")
-            || trimmed.starts_with("Downloading")
-            || trimmed.starts_with("Downloaded")
-            || trimmed.starts_with("Finished")
-            || trimmed.starts_with("Locking")
-            || trimmed.starts_with("Updating")
-        {
+        if is_noise_line(line) {
             continue;
         }
 
@@ -462,7 +2350,12 @@ fn filter_cargo_nextest(output: &str) -> String {
             } else {
                 format!("{}, {}s", binary_text, duration)
             };
-            return format!("✓ cargo nextest: {} ({})", parts.join(", "), meta);
+            return format!(
+                "{} cargo nextest: {} ({})",
+                opts.check(),
+                parts.join(", "),
+                meta
+            );
         }
 
         // With failures - show failure details then summary
@@ -519,858 +2412,1355 @@ fn filter_cargo_nextest(output: &str) -> String {
     String::new()
 }
 
-/// Filter cargo build/check output - strip "Compiling"/"Checking" lines, keep errors + summary
-fn filter_cargo_build(output: &str) -> String {
-    let mut errors: Vec<String> = Vec::new();
-    let mut warnings = 0;
-    let mut error_count = 0;
-    let mut compiled = 0;
-    let mut in_error = false;
-    let mut current_error = Vec::new();
-
-    for line in output.lines() {
-        if line.trim_start().starts_with("Compiling") || line.trim_start().starts_with("Checking") {
-            compiled += 1;
-            continue;
-        }
-        if line.trim_start().starts_with("Downloading")
-            || line.trim_start().starts_with("Downloaded")
-        {
-            continue;
-        }
-        if line.trim_start().starts_with("Finished") {
-            continue;
-        }
-
-        // Detect error/warning blocks
-        if line.starts_with("error[") || line.starts_with("error:") {
-            // Skip "error: aborting due to" summary lines
-            if line.contains("aborting due to") || line.contains("could not compile") {
-                continue;
-            }
-            if in_error && !current_error.is_empty() {
-                errors.push(current_error.join("\n"));
-                current_error.clear();
-            }
-            error_count += 1;
-            in_error = true;
-            current_error.push(line.to_string());
-        } else if line.starts_with("warning:")
-            && line.contains("generated")
-            && line.contains("warning")
-        {
-            // "warning: `crate` generated N warnings" summary line
-            continue;
-        } else if line.starts_with("warning:") || line.starts_with("warning[") {
-            if in_error && !current_error.is_empty() {
-                errors.push(current_error.join("\n"));
-                current_error.clear();
-            }
-            warnings += 1;
-            in_error = true;
-            current_error.push(line.to_string());
-        } else if in_error {
-            if line.trim().is_empty() && current_error.len() > 3 {
-                errors.push(current_error.join("\n"));
-                current_error.clear();
-                in_error = false;
-            } else {
-                current_error.push(line.to_string());
+/// Packages named by `-p foo`, `-pfoo`, `--package foo` or `--package=foo` (`foo@1.2` → `foo`)
+fn selected_packages(args: &[String]) -> Vec<String> {
+    let mut packages: Vec<String> = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let spec = match arg.as_str() {
+            "--" => break,
+            "-p" | "--package" => iter.next().cloned(),
+            _ => arg
+                .strip_prefix("--package=")
+                .or_else(|| arg.strip_prefix("-p"))
+                .filter(|spec| !spec.is_empty())
+                .map(str::to_string),
+        };
+        if let Some(spec) = spec {
+            let name = spec.split('@').next().unwrap_or(&spec).to_string();
+            if !packages.contains(&name) {
+                packages.push(name);
             }
         }
     }
-
-    if !current_error.is_empty() {
-        errors.push(current_error.join("\n"));
-    }
-
-    if error_count == 0 && warnings == 0 {
-        return format!("✓ cargo build ({} crates compiled)", compiled);
-    }
-
-    let mut result = String::new();
-    result.push_str(&format!(
-        "cargo build: {} errors, {} warnings ({} crates)\n",
-        error_count, warnings, compiled
-    ));
-    result.push_str("═══════════════════════════════════════\n");
-
-    for (i, err) in errors.iter().enumerate().take(15) {
-        result.push_str(err);
-        result.push('\n');
-        if i < errors.len() - 1 {
-            result.push('\n');
-        }
-    }
-
-    if errors.len() > 15 {
-        result.push_str(&format!("\n... +{} more issues\n", errors.len() - 15));
-    }
-
-    result.trim().to_string()
+    packages
 }
 
-/// Aggregated test results for compact display
-#[derive(Debug, Default, Clone)]
-struct AggregatedTestResult {
-    passed: usize,
-    failed: usize,
-    ignored: usize,
-    measured: usize,
-    filtered_out: usize,
-    suites: usize,
-    duration_secs: f64,
-    has_duration: bool,
+/// `Packages:` section for build/check/clippy with two or more `-p`: errors and warnings per
+/// package, from cargo's `could not compile `foo`` and `generated N warnings` lines
+fn package_summary(
+    subcommand: &str,
+    args: &[String],
+    raw: &str,
+    opts: &FilterOptions,
+) -> Option<String> {
+    if !matches!(subcommand, "build" | "check" | "clippy") || opts.format.is_document() {
+        return None;
+    }
+    let packages = selected_packages(args);
+    if packages.len() < 2 {
+        return None;
+    }
+    let tally = package_counts(&normalize_newlines(&strip_ansi(raw)));
+    Some(format_package_summary(&packages, &tally, &opts.theme))
 }
 
-impl AggregatedTestResult {
-    /// Parse a test result summary line
-    /// Format: "test result: ok. 15 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s"
-    fn parse_line(line: &str) -> Option<Self> {
-        static RE: OnceLock<regex::Regex> = OnceLock::new();
-        let re = RE.get_or_init(|| {
-            regex::Regex::new(
-                r"test result: (\w+)\.\s+(\d+) passed;\s+(\d+) failed;\s+(\d+) ignored;\s+(\d+) measured;\s+(\d+) filtered out(?:;\s+finished in ([\d.]+)s)?"
-            ).unwrap()
-        });
-
-        let caps = re.captures(line)?;
-        let status = caps.get(1)?.as_str();
-
-        // Only aggregate if status is "ok" (all tests passed)
-        if status != "ok" {
-            return None;
-        }
-
-        let passed = caps.get(2)?.as_str().parse().ok()?;
-        let failed = caps.get(3)?.as_str().parse().ok()?;
-        let ignored = caps.get(4)?.as_str().parse().ok()?;
-        let measured = caps.get(5)?.as_str().parse().ok()?;
-        let filtered_out = caps.get(6)?.as_str().parse().ok()?;
-
-        let (duration_secs, has_duration) = if let Some(duration_match) = caps.get(7) {
-            (duration_match.as_str().parse().unwrap_or(0.0), true)
-        } else {
-            (0.0, false)
-        };
-
-        Some(Self {
-            passed,
-            failed,
-            ignored,
-            measured,
-            filtered_out,
-            suites: 1,
-            duration_secs,
-            has_duration,
-        })
-    }
-
-    /// Merge another test result into this one
-    fn merge(&mut self, other: &Self) {
-        self.passed += other.passed;
-        self.failed += other.failed;
-        self.ignored += other.ignored;
-        self.measured += other.measured;
-        self.filtered_out += other.filtered_out;
-        self.suites += other.suites;
-        self.duration_secs += other.duration_secs;
-        self.has_duration = self.has_duration && other.has_duration;
-    }
-
-    /// Format as compact single line
-    fn format_compact(&self) -> String {
-        let mut parts = vec![format!("{} passed", self.passed)];
+/// (package, errors, warnings) for every package cargo reported on
+fn package_counts(output: &str) -> Vec<(String, usize, usize)> {
+    static FAILED_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let failed_re = FAILED_RE.get_or_init(|| {
+        regex::Regex::new(
+            r"could not compile `([^`]+)`.*?due to (\d+) previous errors?(?:; (\d+) warnings? emitted)?",
+        )
+        .expect("invalid could-not-compile regex")
+    });
 
-        if self.ignored > 0 {
-            parts.push(format!("{} ignored", self.ignored));
+    let mut tally: Vec<(String, usize, usize)> = Vec::new();
+    let mut add = |name: &str, errors: usize, warnings: usize| match tally
+        .iter_mut()
+        .find(|(n, _, _)| n == name)
+    {
+        Some((_, e, w)) => {
+            *e += errors;
+            *w += warnings;
         }
-        if self.filtered_out > 0 {
-            parts.push(format!("{} filtered out", self.filtered_out));
+        None => tally.push((name.to_string(), errors, warnings)),
+    };
+    for line in output.lines() {
+        if let Some(caps) = failed_re.captures(line) {
+            let count = |i: usize| caps.get(i).and_then(|m| m.as_str().parse().ok());
+            add(&caps[1], count(2).unwrap_or(0), count(3).unwrap_or(0));
+        } else if let Some((name, warnings)) = crate_warning_summary(line) {
+            add(&name, 0, warnings);
         }
+    }
+    tally
+}
 
-        let counts = parts.join(", ");
-
-        let suite_text = if self.suites == 1 {
-            "1 suite".to_string()
-        } else {
-            format!("{} suites", self.suites)
+fn format_package_summary(
+    packages: &[String],
+    tally: &[(String, usize, usize)],
+    theme: &Theme,
+) -> String {
+    let mut lines = vec!["Packages:".to_string()];
+    for package in packages {
+        let (errors, warnings) = tally
+            .iter()
+            .find(|(name, _, _)| name == package)
+            .map_or((0, 0), |(_, e, w)| (*e, *w));
+        let line = match (errors, warnings) {
+            (0, 0) => format!("  {} {}: ok", theme.ok, package),
+            (0, w) => format!("  {} {}: {} warnings", theme.warn, package, w),
+            (e, w) => format!("  {} {}: {} errors, {} warnings", theme.fail, package, e, w),
         };
-
-        if self.has_duration {
-            format!(
-                "✓ cargo test: {} ({}, {:.2}s)",
-                counts, suite_text, self.duration_secs
-            )
-        } else {
-            format!("✓ cargo test: {} ({})", counts, suite_text)
-        }
+        lines.push(line);
     }
+    lines.join("\n")
 }
 
-/// Filter cargo test output - show failures + summary only
-fn filter_cargo_test(output: &str) -> String {
-    let mut failures: Vec<String> = Vec::new();
-    let mut summary_lines: Vec<String> = Vec::new();
-    let mut in_failure_section = false;
-    let mut current_failure = Vec::new();
-
-    for line in output.lines() {
-        // Skip compilation lines
-        if line.trim_start().starts_with("Compiling")
-            || line.trim_start().starts_with("Downloading")
-            || line.trim_start().starts_with("Downloaded")
-            || line.trim_start().starts_with("Finished")
-        {
-            continue;
-        }
-
-        // Skip "running N tests" and individual "test ... ok" lines
-        if line.starts_with("running ") || (line.starts_with("test ") && line.ends_with("... ok")) {
-            continue;
-        }
-
-        // Detect failures section
-        if line == "failures:" {
-            in_failure_section = true;
-            continue;
-        }
-
-        if in_failure_section {
-            if line.starts_with("test result:") {
-                in_failure_section = false;
-                summary_lines.push(line.to_string());
-            } else if line.starts_with("    ") || line.starts_with("---- ") {
-                current_failure.push(line.to_string());
-            } else if line.trim().is_empty() && !current_failure.is_empty() {
-                failures.push(current_failure.join("\n"));
-                current_failure.clear();
-            } else if !line.trim().is_empty() {
-                current_failure.push(line.to_string());
-            }
-        }
-
-        // Capture test result summary
-        if !in_failure_section && line.starts_with("test result:") {
-            summary_lines.push(line.to_string());
-        }
+/// Runs an unsupported cargo subcommand: through a registered filter when one matches
+/// (e.g. the `b`/`c`/`t` aliases), otherwise by passing it through directly
+pub fn run_passthrough(args: &[OsString], opts: &FilterOptions, verbose: u8) -> Result<()> {
+    // `rtk cargo +nightly build`: clap sees `+nightly` as the subcommand
+    if let Some(toolchain) = args
+        .first()
+        .and_then(|a| a.to_str())
+        .and_then(|a| a.strip_prefix('+'))
+    {
+        let opts = FilterOptions {
+            toolchain: Some(toolchain.to_string()),
+            ..opts.clone()
+        };
+        return run_passthrough(&args[1..], &opts, verbose);
     }
+    if let Some(filter) = args.first().and_then(|a| find_filter(&a.to_string_lossy())) {
+        let rest: Vec<String> = args[1..]
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        return run_registered(filter, &rest, opts, verbose);
+    }
+    opts.reject_json(&format!(
+        "cargo {}",
+        args.first()
+            .map(|a| a.to_string_lossy())
+            .unwrap_or_default()
+    ))?;
+    let timer = tracking::TimedExecution::start();
 
-    if !current_failure.is_empty() {
-        failures.push(current_failure.join("\n"));
+    if verbose > 0 {
+        eprintln!("cargo passthrough: {:?}", args);
     }
-
-    let mut result = String::new();
-
-    if failures.is_empty() && !summary_lines.is_empty() {
-        // All passed - try to aggregate
-        let mut aggregated: Option<AggregatedTestResult> = None;
-        let mut all_parsed = true;
-
-        for line in &summary_lines {
-            if let Some(parsed) = AggregatedTestResult::parse_line(line) {
-                if let Some(ref mut agg) = aggregated {
-                    agg.merge(&parsed);
-                } else {
-                    aggregated = Some(parsed);
-                }
-            } else {
-                all_parsed = false;
-                break;
-            }
-        }
-
-        // If all lines parsed successfully and we have at least one suite, return compact format
-        if all_parsed {
-            if let Some(agg) = aggregated {
-                if agg.suites > 0 {
-                    return agg.format_compact();
-                }
+    let mut cmd = opts.cargo_command();
+    cmd.args(args);
+    if let Some(color) = passthrough_color(
+        opts.color,
+        args,
+        std::env::var_os("CARGO_TERM_COLOR").is_some(),
+    ) {
+        cmd.env("CARGO_TERM_COLOR", color);
+    }
+    // stderr is relayed as it arrives, only watched for cargo's `no such command` error
+    let mut child = cmd
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run cargo")?;
+    let mut unknown = None;
+    if let Some(stderr) = child.stderr.take() {
+        let mut reader = BufReader::new(stderr);
+        let mut line = Vec::new();
+        while reader.read_until(b'\n', &mut line).unwrap_or(0) > 0 {
+            let _ = std::io::stderr().write_all(&line);
+            if unknown.is_none() {
+                unknown = no_such_command(&strip_ansi(&String::from_utf8_lossy(&line)));
             }
+            line.clear();
         }
-
-        // Fallback: use original behavior if regex failed
-        for line in &summary_lines {
-            result.push_str(&format!("✓ {}\n", line));
-        }
-        return result.trim().to_string();
-    }
-
-    if !failures.is_empty() {
-        result.push_str(&format!("FAILURES ({}):\n", failures.len()));
-        result.push_str("═══════════════════════════════════════\n");
-        for (i, failure) in failures.iter().enumerate().take(10) {
-            result.push_str(&format!("{}. {}\n", i + 1, truncate(failure, 200)));
-        }
-        if failures.len() > 10 {
-            result.push_str(&format!("\n... +{} more failures\n", failures.len() - 10));
-        }
-        result.push('\n');
     }
+    let status = child.wait().context("Failed to wait for cargo")?;
 
-    for line in &summary_lines {
-        result.push_str(&format!("{}\n", line));
-    }
+    let command = format!("{} {}", opts.cargo_display(), tracking::args_display(args));
+    timer.track_passthrough(&command, &format!("rtk {} (passthrough)", command));
 
-    if result.trim().is_empty() {
-        // Fallback: show last meaningful lines
-        let meaningful: Vec<&str> = output
-            .lines()
-            .filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with("Compiling"))
-            .collect();
-        for line in meaningful.iter().rev().take(5).rev() {
-            result.push_str(&format!("{}\n", line));
+    if !status.success() {
+        if let Some(name) = unknown {
+            eprintln!("{}", unknown_subcommand(&name, opts));
         }
+        std::process::exit(exit_code(&status));
     }
-
-    result.trim().to_string()
+    Ok(())
 }
 
-/// Filter cargo clippy output - group warnings by lint rule
-fn filter_cargo_clippy(output: &str) -> String {
-    let mut by_rule: HashMap<String, Vec<String>> = HashMap::new();
-    let mut error_count = 0;
-    let mut warning_count = 0;
-
-    // Parse clippy output lines
-    // Format: "warning: description\n  --> file:line:col\n  |\n  | code\n"
-    let mut current_rule = String::new();
-
-    for line in output.lines() {
-        // Skip compilation lines
-        if line.trim_start().starts_with("Compiling")
-            || line.trim_start().starts_with("Checking")
-            || line.trim_start().starts_with("Downloading")
-            || line.trim_start().starts_with("Downloaded")
-            || line.trim_start().starts_with("Finished")
-        {
-            continue;
-        }
-
-        // "warning: unused variable [unused_variables]" or "warning: description [clippy::rule_name]"
-        if (line.starts_with("warning:") || line.starts_with("warning["))
-            || (line.starts_with("error:") || line.starts_with("error["))
-        {
-            // Skip summary lines: "warning: `rtk` (bin) generated 5 warnings"
-            if line.contains("generated") && line.contains("warning") {
-                continue;
-            }
-            // Skip "error: aborting" / "error: could not compile"
-            if line.contains("aborting due to") || line.contains("could not compile") {
-                continue;
-            }
-
-            let is_error = line.starts_with("error");
-            if is_error {
-                error_count += 1;
-            } else {
-                warning_count += 1;
-            }
-
-            // Extract rule name from brackets
-            current_rule = if let Some(bracket_start) = line.rfind('[') {
-                if let Some(bracket_end) = line.rfind(']') {
-                    line[bracket_start + 1..bracket_end].to_string()
-                } else {
-                    line.to_string()
-                }
-            } else {
-                // No bracket: use the message itself as the rule
-                let prefix = if is_error { "error: " } else { "warning: " };
-                line.strip_prefix(prefix).unwrap_or(line).to_string()
-            };
-        } else if line.trim_start().starts_with("--> ") {
-            let location = line.trim_start().trim_start_matches("--> ").to_string();
-            if !current_rule.is_empty() {
-                by_rule
-                    .entry(current_rule.clone())
-                    .or_default()
-                    .push(location);
-            }
-        }
-    }
+/// The subcommand name in cargo's `error: no such command: `buidl`` line
+fn no_such_command(line: &str) -> Option<String> {
+    let rest = line.trim().strip_prefix("error: no such command: `")?;
+    rest.split_once('`').map(|(name, _)| name.to_string())
+}
 
-    if error_count == 0 && warning_count == 0 {
-        return "✓ cargo clippy: No issues found".to_string();
+/// Built-in cargo subcommands, suggestion candidates for a mistyped one
+const CARGO_BUILTINS: &[&str] = &[
+    "add",
+    "bench",
+    "clean",
+    "config",
+    "doc",
+    "fetch",
+    "fix",
+    "fmt",
+    "generate-lockfile",
+    "help",
+    "info",
+    "init",
+    "locate-project",
+    "login",
+    "logout",
+    "metadata",
+    "new",
+    "owner",
+    "package",
+    "pkgid",
+    "remove",
+    "report",
+    "rustc",
+    "rustdoc",
+    "tree",
+    "update",
+    "verify-project",
+    "version",
+    "yank",
+];
+
+/// `rtk: unknown command 'buidl', did you mean 'build'?` once cargo has rejected `name`.
+///
+/// Candidates are the built-in subcommands and rtk's filters, plus whatever `cargo --list`
+/// reports (installed plugins, `[alias]` entries); that extra cargo call only happens here,
+/// on the error path.
+fn unknown_subcommand(name: &str, opts: &FilterOptions) -> String {
+    let mut known: Vec<String> = CARGO_BUILTINS
+        .iter()
+        .copied()
+        .chain(FILTERS.iter().map(|f| f.name()))
+        .map(str::to_string)
+        .collect();
+    if let Ok(output) = opts.cargo_command().arg("--list").output() {
+        known.extend(listed_subcommands(&String::from_utf8_lossy(&output.stdout)));
+    }
+    match closest_subcommand(name, &known) {
+        Some(guess) => format!("rtk: unknown command '{}', did you mean '{}'?", name, guess),
+        None => format!("rtk: unknown command '{}' (see `cargo --list`)", name),
     }
+}
 
-    let mut result = String::new();
-    result.push_str(&format!(
-        "cargo clippy: {} errors, {} warnings\n",
-        error_count, warning_count
-    ));
-    result.push_str("═══════════════════════════════════════\n");
+/// Command names from `cargo --list` (the first word of each indented row)
+fn listed_subcommands(list: &str) -> Vec<String> {
+    list.lines()
+        .filter(|line| line.starts_with(char::is_whitespace))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
 
-    // Sort rules by frequency
-    let mut rule_counts: Vec<_> = by_rule.iter().collect();
-    rule_counts.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+/// Nearest known name within a third of the typo's length in edits (2 to 3, so a swapped
+/// pair of letters is always caught)
+fn closest_subcommand<'a>(name: &str, known: &'a [String]) -> Option<&'a str> {
+    let limit = (name.chars().count() / 3).clamp(2, 3);
+    known
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
 
-    for (rule, locations) in rule_counts.iter().take(15) {
-        result.push_str(&format!("  {} ({}x)\n", rule, locations.len()));
-        for loc in locations.iter().take(3) {
-            result.push_str(&format!("    {}\n", loc));
+/// Levenshtein distance over chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(ca != *cb);
+            current.push(substitute.min(previous[j + 1] + 1).min(current[j] + 1));
         }
-        if locations.len() > 3 {
-            result.push_str(&format!("    ... +{} more\n", locations.len() - 3));
-        }
-    }
-
-    if by_rule.len() > 15 {
-        result.push_str(&format!("\n... +{} more rules\n", by_rule.len() - 15));
+        previous = current;
     }
-
-    result.trim().to_string()
+    previous[b.len()]
 }
 
-/// Runs an unsupported cargo subcommand by passing it through directly
-pub fn run_passthrough(args: &[OsString], verbose: u8) -> Result<()> {
+/// `cargo expand`: the expansion is the point of the command, so it is never filtered.
+///
+/// Without `summary` this is a tracked passthrough (cargo-expand keeps the terminal and its
+/// highlighting). With it, stdout is captured so a size header can go above the full expansion;
+/// stderr (compile progress and errors) always goes straight to the terminal.
+pub fn run_expand(args: &[String], summary: bool, opts: &FilterOptions, verbose: u8) -> Result<()> {
+    opts.reject_json("cargo expand")?;
     let timer = tracking::TimedExecution::start();
 
     if verbose > 0 {
-        eprintln!("cargo passthrough: {:?}", args);
-    }
-    let status = Command::new("cargo")
-        .args(args)
-        .status()
-        .context("Failed to run cargo")?;
+        eprintln!("Running: cargo expand {}", args.join(" "));
+    }
+    let mut cmd = opts.cargo_command();
+    cmd.arg("expand").args(args);
+
+    let status = if summary {
+        let output = cmd
+            .stderr(std::process::Stdio::inherit())
+            .output()
+            .context("Failed to run cargo expand")?;
+        let expansion = String::from_utf8_lossy(&output.stdout);
+        if output.status.success() {
+            println!("{}", expand_summary(&expansion, &expand_item(args)));
+        }
+        print!("{}", expansion);
+        output.status
+    } else {
+        cmd.status().context("Failed to run cargo expand")?
+    };
 
-    let args_str = tracking::args_display(args);
     timer.track_passthrough(
-        &format!("cargo {}", args_str),
-        &format!("rtk cargo {} (passthrough)", args_str),
+        &format!("cargo expand {}", args.join(" ")),
+        &format!("rtk cargo expand {}", args.join(" ")),
     );
 
     if !status.success() {
-        std::process::exit(status.code().unwrap_or(1));
+        std::process::exit(exit_code(&status));
     }
     Ok(())
 }
 
+/// The module path given to `cargo expand` (its one positional), or the crate root
+fn expand_item(args: &[String]) -> String {
+    // Options that consume the next argument as their value
+    const WITH_VALUE: &[&str] = &[
+        "-p",
+        "--package",
+        "--bin",
+        "--example",
+        "--test",
+        "--bench",
+        "--features",
+        "-F",
+        "--target",
+        "--target-dir",
+        "--manifest-path",
+        "--profile",
+        "--color",
+        "--theme",
+        "-j",
+        "--jobs",
+        "--config",
+        "-Z",
+    ];
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if WITH_VALUE.contains(&arg.as_str()) {
+            iter.next();
+        } else if !arg.starts_with('-') {
+            return arg.clone();
+        }
+    }
+    "crate root".to_string()
+}
+
+/// `cargo expand: 1234 lines, 56.7KiB (module: foo::bar)`
+fn expand_summary(expansion: &str, item: &str) -> String {
+    let bytes = expansion.len();
+    let size = if bytes >= 1024 * 1024 {
+        format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1}KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{}B", bytes)
+    };
+    format!(
+        "cargo expand: {} lines, {} (module: {})",
+        expansion.lines().count(),
+        size,
+        item
+    )
+}
+
+/// `CARGO_TERM_COLOR` for a passthrough, following rtk's own resolved `--rtk-color`.
+///
+/// cargo would otherwise decide for itself; an explicit `--color` in the arguments or a
+/// `CARGO_TERM_COLOR` already in the environment is left alone.
+fn passthrough_color(color: bool, args: &[OsString], env_set: bool) -> Option<&'static str> {
+    let explicit = args.iter().any(|arg| {
+        let arg = arg.to_string_lossy();
+        arg == "--color" || arg.starts_with("--color=")
+    });
+    if explicit || env_set {
+        return None;
+    }
+    Some(if color { "always" } else { "never" })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_filter_cargo_build_success() {
-        let output = r#"   Compiling libc v0.2.153
-   Compiling cfg-if v1.0.0
-   Compiling rtk v0.5.0
-    Finished dev [unoptimized + debuginfo] target(s) in 15.23s
-"#;
-        let result = filter_cargo_build(output);
-        assert!(result.contains("✓ cargo build"));
-        assert!(result.contains("3 crates compiled"));
+    fn test_find_filter() {
+        assert_eq!(find_filter("build").map(|f| f.name()), Some("build"));
+        assert_eq!(find_filter("t").map(|f| f.name()), Some("test"));
+        assert!(find_filter("run").is_none());
+        assert!(find_filter("fmt").is_none());
+
+        // Every dispatched command except the special-cased ones has a filter
+        for cmd in [
+            CargoCommand::Build,
+            CargoCommand::Test,
+            CargoCommand::Clippy,
+            CargoCommand::Check,
+            CargoCommand::Install,
+            CargoCommand::Nextest,
+            CargoCommand::Publish,
+            CargoCommand::Clean,
+            CargoCommand::Uninstall,
+            CargoCommand::Vendor,
+            CargoCommand::Machete,
+        ] {
+            assert!(find_filter(cmd.name()).is_some(), "{}", cmd.name());
+        }
+
+        let output = "error: package ID specification `foo` did not match any packages\n";
+        assert_eq!(
+            find_filter("uninstall")
+                .unwrap()
+                .filter(output, &FilterOptions::default()),
+            filter_cargo_uninstall(output, &FilterOptions::default())
+        );
     }
 
     #[test]
-    fn test_filter_cargo_build_errors() {
-        let output = r#"   Compiling rtk v0.5.0
-error[E0308]: mismatched types
- --> src/main.rs:10:5
-  |
-10|     "hello"
-  |     ^^^^^^^ expected `i32`, found `&str`
+    fn test_warning_gate() {
+        let output = "warning: unused variable: `x`\n --> src/main.rs:1:9\n\nwarning: unused import\n --> src/lib.rs:2:5\n\nwarning: `app` (bin \"app\") generated 2 warnings\n";
+        assert_eq!(warning_gate("build", output, None), None);
+        assert_eq!(
+            warning_gate("clippy", output, Some(0)).as_deref(),
+            Some("rtk: failing on 2 warnings (--fail-on-warnings)")
+        );
+        assert_eq!(
+            warning_gate("check", output, Some(1)).as_deref(),
+            Some("rtk: 2 warnings, over the limit of 1 (--fail-over)")
+        );
+        assert_eq!(warning_gate("build", output, Some(2)), None);
+        assert_eq!(warning_gate("test", output, Some(0)), None);
+    }
 
-error: aborting due to 1 previous error
-"#;
-        let result = filter_cargo_build(output);
-        assert!(result.contains("1 errors"));
-        assert!(result.contains("E0308"));
-        assert!(result.contains("mismatched types"));
+    #[test]
+    fn test_command_line() {
+        let args = vec![
+            "--release".to_string(),
+            "-p".to_string(),
+            "my app".to_string(),
+        ];
+        assert_eq!(
+            command_line(None, "build", &args),
+            "$ cargo build --release -p 'my app'"
+        );
+        assert_eq!(command_line(None, "test", &[]), "$ cargo test");
+        assert_eq!(
+            command_line(Some("nightly"), "test", &[]),
+            "$ cargo +nightly test"
+        );
+    }
+
+    #[test]
+    fn test_toolchain_reaches_cargo_command() {
+        let opts = FilterOptions {
+            toolchain: Some("nightly".to_string()),
+            ..FilterOptions::default()
+        };
+        let cmd = capture_command("build", &["--release".to_string()], None, &opts);
+        // `cargo test` sets $CARGO to a toolchain binary; the rustup proxy is used instead
+        assert_eq!(cmd.get_program(), "cargo");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["+nightly", "build", "--release"]);
+        assert_eq!(opts.cargo_display(), "cargo +nightly");
     }
 
     #[test]
-    fn test_filter_cargo_test_all_pass() {
-        let output = r#"   Compiling rtk v0.5.0
-    Finished test [unoptimized + debuginfo] target(s) in 2.53s
-     Running target/debug/deps/rtk-abc123
+    fn test_run_file_missing_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("cargo.log");
+        let err = run_file(FilterKind::Build, &missing, &FilterOptions::default(), 0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Failed to read {}", missing.display())
+        );
+    }
 
-running 15 tests
-test utils::tests::test_truncate_short_string ... ok
-test utils::tests::test_truncate_long_string ... ok
-test utils::tests::test_strip_ansi_simple ... ok
+    #[test]
+    fn test_expand_summary() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(
+            expand_item(&args("--lib -p app parser::lexer")),
+            "parser::lexer"
+        );
+        assert_eq!(expand_item(&args("--bin app --ugly")), "crate root");
 
-test result: ok. 15 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
-"#;
-        let result = filter_cargo_test(output);
-        assert!(
-            result.contains("✓ cargo test: 15 passed (1 suite, 0.01s)"),
-            "Expected compact format, got: {}",
-            result
+        let expansion = "fn main() {}\n".repeat(200);
+        assert_eq!(
+            expand_summary(&expansion, "crate root"),
+            "cargo expand: 200 lines, 2.5KiB (module: crate root)"
+        );
+        assert_eq!(
+            expand_summary("mod a {}\n", "a"),
+            "cargo expand: 1 lines, 9B (module: a)"
         );
-        assert!(!result.contains("Compiling"));
-        assert!(!result.contains("test utils"));
     }
 
     #[test]
-    fn test_filter_cargo_test_failures() {
-        let output = r#"running 5 tests
-test foo::test_a ... ok
-test foo::test_b ... FAILED
-test foo::test_c ... ok
+    fn test_closest_subcommand() {
+        let list = "Installed Commands:\n    b                    alias: build\n    build                Compile a local package\n    check                Check a local package\n    clippy               Checks a package\n    install              Install a Rust binary\n";
+        let known = listed_subcommands(list);
+        assert_eq!(known, vec!["b", "build", "check", "clippy", "install"]);
+        assert_eq!(closest_subcommand("buidl", &known), Some("build"));
+        assert_eq!(closest_subcommand("clipy", &known), Some("clippy"));
+        assert_eq!(closest_subcommand("instal", &known), Some("install"));
+        assert_eq!(closest_subcommand("frobnicate", &known), None);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "run"), 3);
+    }
 
-failures:
+    #[test]
+    fn test_no_such_command() {
+        assert_eq!(
+            no_such_command("error: no such command: `buidl`\n").as_deref(),
+            Some("buidl")
+        );
+        assert_eq!(
+            no_such_command("\thelp: a command with a similar name exists: `build`"),
+            None
+        );
+        assert_eq!(no_such_command("error: could not compile `app`"), None);
+    }
 
----- foo::test_b stdout ----
-thread 'foo::test_b' panicked at 'assert_eq!(1, 2)'
+    #[test]
+    fn test_failure_width_follows_terminal() {
+        assert_eq!(failure_width_for(true, Some(320)), 320);
+        assert_eq!(failure_width_for(true, Some(20)), 40);
+        assert_eq!(failure_width_for(false, Some(320)), 200);
+        assert_eq!(failure_width_for(true, None), 200);
+    }
 
-failures:
-    foo::test_b
+    #[test]
+    fn test_write_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ci").join("build.txt");
+        write_output(&path, "✓ cargo build (3 crates compiled)\n\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "✓ cargo build (3 crates compiled)\n"
+        );
+        assert!(write_output(dir.path(), "x").is_err());
+    }
 
-test result: FAILED. 4 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
-"#;
-        let result = filter_cargo_test(output);
-        assert!(result.contains("FAILURES"));
-        assert!(result.contains("test_b"));
-        assert!(result.contains("test result:"));
+    #[test]
+    fn test_append_savings() {
+        assert_eq!(
+            append_savings("✓ cargo build (3 crates compiled)", 4300, 340),
+            "✓ cargo build (3 crates compiled) [rtk: 4.2 KB -> 340 B, 92%]"
+        );
+        assert_eq!(
+            append_savings("cargo build: 1 errors\n═══\nerror", 2_097_152, 2_097_152),
+            "cargo build: 1 errors [rtk: 2.0 MB -> 2.0 MB, 0%]\n═══\nerror"
+        );
+        assert_eq!(append_savings("ok", 0, 0), "ok [rtk: 0 B -> 0 B, 0%]");
     }
 
     #[test]
-    fn test_filter_cargo_test_multi_suite_all_pass() {
-        let output = r#"   Compiling rtk v0.5.0
-    Finished test [unoptimized + debuginfo] target(s) in 2.53s
-     Running unittests src/lib.rs (target/debug/deps/rtk-abc123)
+    fn test_passthrough_color() {
+        let args = |s: &str| s.split_whitespace().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(
+            passthrough_color(true, &args("tree -d"), false),
+            Some("always")
+        );
+        assert_eq!(
+            passthrough_color(false, &args("tree"), false),
+            Some("never")
+        );
+        assert_eq!(
+            passthrough_color(true, &args("tree --color=never"), false),
+            None
+        );
+        assert_eq!(passthrough_color(true, &args("tree"), true), None);
+    }
 
-running 50 tests
-test result: ok. 50 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.45s
+    #[test]
+    fn test_normalize_paths() {
+        let root = Path::new("/home/user/ws");
+        let filtered = "error[E0308]: mismatched types\n --> /home/user/ws/crates/core/src/lib.rs:4:18\n  |\nwarning: unused\n   --> /home/user/.cargo/registry/src/dep-1.0.0/src/lib.rs:9:1\n --> src/main.rs:2:9";
+        assert_eq!(
+            normalize_paths(filtered, root, false),
+            "error[E0308]: mismatched types\n --> crates/core/src/lib.rs:4:18\n  |\nwarning: unused\n   --> /home/user/.cargo/registry/src/dep-1.0.0/src/lib.rs:9:1\n --> src/main.rs:2:9"
+        );
+        assert_eq!(
+            normalize_paths(" --> src/main.rs:2:9", root, true),
+            " --> /home/user/ws/src/main.rs:2:9"
+        );
+    }
 
-     Running unittests src/main.rs (target/debug/deps/rtk-def456)
+    #[test]
+    fn test_workspace_root_prefers_workspace_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let member = dir.path().join("crates").join("core");
+        std::fs::create_dir_all(member.join("src")).unwrap();
+        std::fs::write(member.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+        assert_eq!(workspace_root(&member.join("src")), Some(member.clone()));
+
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            workspace_root(&member.join("src")),
+            Some(dir.path().to_path_buf())
+        );
+    }
 
-running 30 tests
-test result: ok. 30 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.30s
+    #[test]
+    fn test_cargo_program_resolution() {
+        assert_eq!(cargo_program(None, None), OsString::from("cargo"));
+        assert_eq!(
+            cargo_program(None, Some(OsString::from("/opt/rust/bin/cargo"))),
+            OsString::from("/opt/rust/bin/cargo")
+        );
+        assert_eq!(
+            cargo_program(None, Some(OsString::new())),
+            OsString::from("cargo")
+        );
+        // --cargo-bin wins over $CARGO
+        assert_eq!(
+            cargo_program(
+                Some(Path::new("/usr/local/bin/cross-cargo")),
+                Some(OsString::from("/opt/rust/bin/cargo"))
+            ),
+            OsString::from("/usr/local/bin/cross-cargo")
+        );
+    }
 
-     Running tests/integration.rs (target/debug/deps/integration-ghi789)
+    const LIBTEST_JSON: &str = r#"   Compiling app v0.1.0 (/home/user/app)
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.52s
+     Running unittests src/lib.rs (target/debug/deps/app-1a2b3c)
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "tests::adds" }
+{ "type": "test", "event": "started", "name": "tests::parses" }
+{ "type": "test", "event": "started", "name": "tests::skipped" }
+{ "type": "test", "name": "tests::adds", "event": "ok" }
+{ "type": "test", "name": "tests::skipped", "event": "ignored" }
+{ "type": "test", "name": "tests::parses", "event": "failed", "stdout": "debug: parsing \"x\"\nthread 'tests::parses' panicked at src/lib.rs:12:9:\nassertion `left == right` failed\n  left: 1\n right: 2\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1, "measured": 0, "filtered_out": 0, "exec_time": 0.0012 }
+"#;
 
-running 25 tests
-test result: ok. 25 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.25s
+    #[test]
+    fn test_filter_cargo_test_libtest_json() {
+        let result = filter_cargo_test(
+            LIBTEST_JSON,
+            &FilterOptions {
+                failure_width: 1000,
+                ..FilterOptions::default()
+            },
+        );
+        assert!(result.starts_with("FAILURES (1):"), "got: {}", result);
+        assert!(result.contains("1. ---- tests::parses stdout ----\ndebug: parsing \"x\""));
+        assert!(result.contains("right: 2"));
+        assert!(!result.contains("tests::adds"));
+        assert!(result.ends_with(
+            "test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s"
+        ));
+        assert_eq!(parse_failed_tests(LIBTEST_JSON), vec!["tests::parses"]);
+        assert_eq!(FilterKind::Auto.resolve(LIBTEST_JSON), FilterKind::Test);
+    }
 
-   Doc-tests rtk
+    #[test]
+    fn test_selected_packages() {
+        let args: Vec<String> = [
+            "-p",
+            "foo",
+            "--package=bar@1.2.0",
+            "-pbaz",
+            "--package",
+            "foo",
+            "--release",
+            "--",
+            "-p",
+            "qux",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(selected_packages(&args), vec!["foo", "bar", "baz"]);
+        assert!(selected_packages(&["--release".to_string()]).is_empty());
+    }
 
-running 32 tests
-test result: ok. 32 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.45s
+    #[test]
+    fn test_package_summary_per_package() {
+        let output = r#"   Compiling foo v0.1.0
+   Compiling bar v0.1.0
+   Compiling baz v0.1.0
+warning: `bar` (lib) generated 2 warnings
+error[E0308]: mismatched types
+ --> foo/src/lib.rs:4:5
+error: could not compile `foo` (lib) due to 3 previous errors; 1 warning emitted
 "#;
-        let result = filter_cargo_test(output);
-        assert!(
-            result.contains("✓ cargo test: 137 passed (4 suites, 1.45s)"),
-            "Expected aggregated format, got: {}",
-            result
+        let args: Vec<String> = ["-p", "foo", "-p", "bar", "-p", "baz"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let opts = FilterOptions::default();
+        assert_eq!(
+            package_summary("build", &args, output, &opts).unwrap(),
+            "Packages:\n  ✗ foo: 3 errors, 1 warnings\n  ⚠ bar: 2 warnings\n  ✓ baz: ok"
         );
-        assert!(!result.contains("running"));
+        assert_eq!(package_summary("build", &args[..2], output, &opts), None);
+        assert_eq!(package_summary("test", &args, output, &opts), None);
     }
 
     #[test]
-    fn test_filter_cargo_test_multi_suite_with_failures() {
-        let output = r#"     Running unittests src/lib.rs
+    fn test_format_explanations() {
+        let output = "error[E0308]: mismatched types\n --> src/a.rs:1:1\nerror[E0599]: no method\nerror[E0308]: mismatched types\nerror: linking with `cc` failed\n";
+        let codes = error_codes(output);
+        assert_eq!(codes, vec!["E0308", "E0599"]);
+
+        let fetched = std::cell::RefCell::new(Vec::new());
+        let result = format_explanations(&codes, |code| {
+            fetched.borrow_mut().push(code.to_string());
+            (code == "E0308").then(|| "Expected type did not match the received type.".to_string())
+        });
+        assert_eq!(
+            result,
+            "\n\nEXPLANATIONS (1):\nE0308: Expected type did not match the received type."
+        );
+        assert_eq!(*fetched.borrow(), vec!["E0308", "E0599"]);
+        assert_eq!(format_explanations(&[], |_| None), "");
+    }
 
-running 20 tests
-test result: ok. 20 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.10s
+    #[test]
+    fn test_first_paragraph() {
+        let text = "\nA value was moved whose size was not known at\ncompile time.\n\nErroneous code example:\n";
+        assert_eq!(
+            first_paragraph(text),
+            "A value was moved whose size was not known at compile time."
+        );
+    }
 
-     Running unittests src/main.rs
+    #[test]
+    fn test_timeout_parse_and_display() {
+        assert_eq!(
+            "10m".parse::<Timeout>(),
+            Ok(Timeout(Duration::from_secs(600)))
+        );
+        assert_eq!(
+            "90".parse::<Timeout>(),
+            Ok(Timeout(Duration::from_secs(90)))
+        );
+        assert_eq!("250ms".parse::<Timeout>().unwrap().to_string(), "250ms");
+        assert_eq!("7200s".parse::<Timeout>().unwrap().to_string(), "2h");
+        assert_eq!("90s".parse::<Timeout>().unwrap().to_string(), "90s");
+        assert!("10d".parse::<Timeout>().is_err());
+        assert!("0s".parse::<Timeout>().is_err());
+        assert!("m".parse::<Timeout>().is_err());
+    }
 
-running 15 tests
-test foo::test_bad ... FAILED
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_with_timeout_kills_children() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("survived");
+        // The grandchild would create the marker if it outlived the kill
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(format!(
+            "echo started; (sleep 1; touch {}) & sleep 5",
+            marker.display()
+        ));
 
-failures:
+        let start = std::time::Instant::now();
+        let output =
+            capture_child(cmd, "test", Some(Duration::from_millis(200)), &mut |_| {}).unwrap();
+        assert!(output.timed_out);
+        assert_eq!(output.exit_code, TIMEOUT_EXIT_CODE);
+        assert_eq!(output.stdout, "started\n");
+        assert!(start.elapsed() < Duration::from_secs(3));
 
----- foo::test_bad stdout ----
-thread panicked at 'assertion failed'
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
 
-test result: FAILED. 14 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.05s
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_child_streams_stderr_lines() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg("echo out; echo 'error: one' >&2; echo 'warning: two' >&2");
+        let mut lines: Vec<String> = Vec::new();
+        let output = capture_child(cmd, "test", None, &mut |l| lines.push(l.to_string())).unwrap();
+        assert_eq!(lines, vec!["error: one", "warning: two"]);
+        assert_eq!(output.stderr, "error: one\nwarning: two\n");
+        assert_eq!(output.stdout, "out\n");
+        assert_eq!(output.exit_code, 0);
+        assert!(!output.timed_out);
+    }
 
-     Running tests/integration.rs
+    #[test]
+    fn test_capture_command_disables_color() {
+        let env = |opts: &FilterOptions| {
+            capture_command("build", &[], None, opts)
+                .get_envs()
+                .find(|(k, _)| *k == "CARGO_TERM_COLOR")
+                .and_then(|(_, v)| v.map(|v| v.to_os_string()))
+        };
+        assert_eq!(env(&FilterOptions::default()), Some("never".into()));
+        let raw = FilterOptions {
+            raw: true,
+            ..FilterOptions::default()
+        };
+        assert_eq!(env(&raw), None);
+    }
 
-running 10 tests
-test result: ok. 10 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.02s
+    #[test]
+    fn test_run_target() {
+        let stream = r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"dep"},"executable":null,"manifest_path":"/reg/dep/Cargo.toml"}
+{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"app"},"executable":"/ws/target/debug/app","manifest_path":"/ws/app/Cargo.toml","fresh":true}
+{"reason":"build-finished","success":true}
 "#;
-        let result = filter_cargo_test(output);
-        // Should NOT aggregate when there are failures
-        assert!(result.contains("FAILURES"), "got: {}", result);
-        assert!(result.contains("test_bad"), "got: {}", result);
-        assert!(result.contains("test result:"), "got: {}", result);
-        // Should show individual summaries
-        assert!(result.contains("20 passed"), "got: {}", result);
-        assert!(result.contains("14 passed"), "got: {}", result);
-        assert!(result.contains("10 passed"), "got: {}", result);
+        assert_eq!(
+            run_target(stream, false),
+            Some(RunTarget {
+                executable: PathBuf::from("/ws/target/debug/app"),
+                manifest_dir: Some(PathBuf::from("/ws/app")),
+            })
+        );
+        assert_eq!(run_target(stream, true), None);
+
+        let two_bins = format!(
+            "{}{}",
+            stream,
+            r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"tool"},"executable":"/ws/target/debug/tool","manifest_path":"/ws/app/Cargo.toml"}"#
+        );
+        assert_eq!(run_target(&two_bins, false), None);
     }
 
     #[test]
-    fn test_filter_cargo_test_all_suites_zero_tests() {
-        let output = r#"     Running unittests src/empty1.rs
+    fn test_split_program_args() {
+        let args: Vec<String> = ["--release", "--bin", "app", "--", "--port", "80"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (cargo, program) = split_program_args(&args);
+        assert_eq!(cargo, &args[..3]);
+        assert_eq!(program, &args[4..]);
 
-running 0 tests
+        let (cargo, program) = split_program_args(&args[..3]);
+        assert_eq!(cargo.len(), 3);
+        assert!(program.is_empty());
+    }
 
-test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+    #[test]
+    fn test_apply_env_overrides() {
+        let env = |name: &str| match name {
+            "RTK_MAX_ERRORS" => Some("5".to_string()),
+            "RTK_FAILURE_WIDTH" => Some(" 400 ".to_string()),
+            "RTK_RAW" => Some("0".to_string()),
+            _ => None,
+        };
+        let mut opts = FilterOptions::default();
+        opts.apply_env(env);
+        assert_eq!(opts.max_errors, 5);
+        assert_eq!(opts.failure_width, 400);
+        assert!(!opts.raw);
 
-     Running unittests src/empty2.rs
+        let mut opts = FilterOptions::default();
+        opts.apply_env(|name| (name == "RTK_MAX_ERRORS").then(|| "lots".to_string()));
+        assert_eq!(opts.max_errors, 15);
+    }
 
-running 0 tests
+    #[test]
+    fn test_keep_patterns() {
+        let keep = keep_patterns(&[
+            "re:^warning: .*deprecated".to_string(),
+            "a.b".to_string(),
+            "re:(".to_string(),
+        ]);
+        assert_eq!(keep.len(), 2);
+        assert!(keep[0].is_match("warning: use of deprecated item"));
+        assert!(keep[1].is_match("x a.b y"));
+        assert!(!keep[1].is_match("axb"));
+    }
 
-test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+    #[test]
+    fn test_apply_filter_strips_ansi_before_parsing() {
+        let output = "\x1b[0m\x1b[1m\x1b[32m   Compiling\x1b[0m rtk v0.5.0\n\
+\x1b[0m\x1b[1m\x1b[38;5;9merror[E0308]\x1b[0m\x1b[0m\x1b[1m: mismatched types\x1b[0m\n\
+\x1b[0m \x1b[0m\x1b[0m\x1b[1m\x1b[38;5;12m--> \x1b[0m\x1b[0msrc/main.rs:10:5\x1b[0m\n\
+\n\
+\x1b[0m\x1b[1m\x1b[38;5;9merror\x1b[0m\x1b[0m\x1b[1m: aborting due to 1 previous error\x1b[0m\n";
+        let result = apply_filter(
+            output,
+            &FilterOptions::default(),
+            "cargo",
+            filter_cargo_build,
+        );
+        assert!(result.contains("1 errors"), "got: {}", result);
+        assert!(result.contains("error[E0308]: mismatched types"));
+        assert!(result.contains("(1 crates)"));
+        assert!(!result.contains('\x1b'));
+    }
 
-     Running tests/empty3.rs
+    #[test]
+    fn test_apply_filter_crlf_matches_lf() {
+        let test_output = "running 3 tests\n\
+test foo::test_a ... ok\n\
+test foo::test_b ... FAILED\n\
+test foo::test_c ... ok\n\
+\n\
+failures:\n\
+\n\
+---- foo::test_b stdout ----\n\
+thread 'foo::test_b' panicked at 'assert_eq!(1, 2)'\n\
+\n\
+failures:\n\
+    foo::test_b\n\
+\n\
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        let build_output = "   Compiling rtk v0.5.0\n\
+error[E0308]: mismatched types\n\
+ --> src/main.rs:10:5\n\
+\n\
+error: aborting due to 1 previous error\n";
+
+        let opts = FilterOptions::default();
+        for (output, filter) in [
+            (
+                test_output,
+                filter_cargo_test as fn(&str, &FilterOptions) -> String,
+            ),
+            (build_output, filter_cargo_build),
+        ] {
+            let crlf = output.replace('\n', "\r\n");
+            let expected = apply_filter(output, &opts, "cargo", filter);
+            let actual = apply_filter(&crlf, &opts, "cargo", filter);
+            assert_eq!(actual, expected);
+            assert!(!actual.contains('\r'));
+        }
+    }
 
-running 0 tests
+    #[test]
+    fn test_summary_only_prints_only_summary_line() {
+        let opts = FilterOptions {
+            summary_only: true,
+            ..FilterOptions::default()
+        };
+        let build = "error[E0308]: mismatched types\n --> src/main.rs:1:1\n\nerror[E0425]: cannot find value\n --> src/main.rs:2:1\n";
+        let result = apply_filter(build, &opts, "cargo", filter_cargo_build);
+        assert!(
+            result.starts_with("cargo build: 2 errors"),
+            "got: {}",
+            result
+        );
+        assert!(!result.contains('\n'), "got: {}", result);
 
-test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
-"#;
-        let result = filter_cargo_test(output);
+        let test = "running 1 test\ntest a ... FAILED\n\nfailures:\n\n---- a stdout ----\npanicked\n\nfailures:\n    a\n\ntest result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        let result = apply_filter(test, &opts, "cargo", filter_cargo_test);
         assert!(
-            result.contains("✓ cargo test: 0 passed (3 suites, 0.00s)"),
-            "Expected compact format for zero tests, got: {}",
+            result.starts_with("test result: FAILED."),
+            "got: {}",
             result
         );
+        assert!(!result.contains('\n'));
+
+        let ok = "   Compiling rtk v0.5.0\n    Finished dev [unoptimized] target(s) in 1.0s\n";
+        assert_eq!(
+            apply_filter(ok, &opts, "cargo", filter_cargo_build),
+            "✓ cargo build (1 crates compiled)"
+        );
     }
 
     #[test]
-    fn test_filter_cargo_test_with_ignored_and_filtered() {
-        let output = r#"     Running unittests src/lib.rs
-
-running 50 tests
-test result: ok. 45 passed; 0 failed; 3 ignored; 0 measured; 2 filtered out; finished in 0.50s
-
-     Running tests/integration.rs
+    fn test_file_lock_stripped_by_every_filter() {
+        let lock = "    Blocking waiting for file lock on package cache\n    Blocking waiting for file lock on build directory\n";
+        type Filter = fn(&str, &FilterOptions) -> String;
+        let outputs: [(&str, Filter, &str); 5] = [
+            ("build", filter_cargo_build, "   Compiling app v0.1.0\n    Finished `dev` profile target(s) in 1.0s\n"),
+            ("test", filter_cargo_test, "running 1 test\ntest a ... ok\n\ntest result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n"),
+            ("clippy", filter_cargo_clippy, "    Checking app v0.1.0\n    Finished `dev` profile target(s) in 1.0s\n"),
+            ("install", filter_cargo_install, "  Installing app v0.1.0\n   Installed package `app v0.1.0` (executable `app`)\n"),
+            ("nextest", filter_cargo_nextest, "    Starting 1 test across 1 binary\n        PASS [   0.010s] app tests::a\n     Summary [   0.010s] 1 test run: 1 passed, 0 skipped\n"),
+        ];
+        for (name, filter, output) in outputs {
+            let raw = format!("{}{}", lock, output);
+            let filtered = filter(&raw, &FilterOptions::default());
+            assert!(
+                !filtered.contains("Blocking"),
+                "{} leaked: {}",
+                name,
+                filtered
+            );
+
+            let result = apply_filter(&raw, &FilterOptions::default(), "cargo", filter);
+            assert!(
+                result.ends_with("\n⚠ waited on file lock"),
+                "{}: {}",
+                name,
+                result
+            );
+            assert_eq!(result.matches("file lock").count(), 1);
+        }
 
-running 20 tests
-test result: ok. 18 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.20s
-"#;
-        let result = filter_cargo_test(output);
-        assert!(
-            result.contains("✓ cargo test: 63 passed, 5 ignored, 2 filtered out (2 suites, 0.70s)"),
-            "Expected compact format with ignored and filtered, got: {}",
-            result
+        let result = apply_filter(
+            outputs[0].2,
+            &FilterOptions::default(),
+            "cargo",
+            filter_cargo_build,
         );
+        assert!(!result.contains("file lock"));
     }
 
     #[test]
-    fn test_filter_cargo_test_single_suite_compact() {
-        let output = r#"     Running unittests src/main.rs
+    fn test_filter_cargo_clean_summary() {
+        let output = "     Removed 1234 files, 1.2GiB total\n";
+        assert_eq!(
+            filter_cargo_clean(output, &FilterOptions::default()),
+            "✓ cargo clean: removed 1234 files (1.2GiB freed)"
+        );
+    }
 
-running 15 tests
-test result: ok. 15 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
-"#;
-        let result = filter_cargo_test(output);
-        assert!(
-            result.contains("✓ cargo test: 15 passed (1 suite, 0.01s)"),
-            "Expected singular 'suite', got: {}",
-            result
+    #[test]
+    fn test_filter_cargo_clean_verbose() {
+        let output = "    Removing /home/user/app/target/debug/app\n    Removing /home/user/app/target/debug/deps\n    Removing /home/user/app/target/debug/build\n";
+        assert_eq!(
+            filter_cargo_clean(output, &FilterOptions::default()),
+            "✓ cargo clean: removed 3 files"
         );
+        assert_eq!(FilterKind::Auto.resolve(output), FilterKind::Clean);
     }
 
     #[test]
-    fn test_filter_cargo_test_regex_fallback() {
-        let output = r#"     Running unittests src/main.rs
+    fn test_filter_cargo_clean_dry_run_and_empty() {
+        let output =
+            "     Summary 56 files, 12.3MiB total\nwarning: no files deleted due to --dry-run\n";
+        assert_eq!(
+            filter_cargo_clean(output, &FilterOptions::default()),
+            "✓ cargo clean (dry-run): would remove 56 files (12.3MiB total)"
+        );
+        assert_eq!(
+            filter_cargo_clean("", &FilterOptions::default()),
+            "✓ cargo clean"
+        );
+    }
 
-running 15 tests
-test result: MALFORMED LINE WITHOUT PROPER FORMAT
+    #[test]
+    fn test_filter_cargo_search() {
+        let output = r#"serde = "1.0.210"                         # A generic serialization/deserialization framework
+serde_json = "1.0.128"                    # A JSON serialization file format
+serde_derive_internals_extra = "0.1.0"    # Internals of serde_derive, which are
+                                          # re-exported for derive helpers
+... and 4821 crates more (use --limit N to see more)
+note: to learn more about a package, run `cargo info <name>`
 "#;
-        let result = filter_cargo_test(output);
-        // Should fallback to original behavior (show line with checkmark)
-        assert!(
-            result.contains("✓ test result: MALFORMED"),
-            "Expected fallback format, got: {}",
-            result
+        assert_eq!(
+            filter_cargo_search(output, &FilterOptions::default(), 10),
+            r#"serde = "1.0.210"                       # A generic serialization/deserialization framework
+serde_json = "1.0.128"                  # A JSON serialization file format
+serde_derive_internals_extra = "0.1.0"  # Internals of serde_derive, which are re-exported for derive helpers
+... and 4821 more"#
+        );
+
+        let capped = filter_cargo_search(output, &FilterOptions::default(), 1);
+        assert_eq!(
+            capped,
+            "serde = \"1.0.210\"  # A generic serialization/deserialization framework\n... and 4823 more"
         );
     }
 
+    const LLVM_COV_TABLE: &str = "\
+running 2 tests
+test a ... ok
+test b ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out
+
+Filename                      Regions    Missed Regions     Cover   Functions  Missed Functions  Executed       Lines      Missed Lines     Cover    Branches   Missed Branches     Cover
+-----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------
+src/lib.rs                         40                 4    90.00%           8                 0   100.00%         100                 5    95.00%           0                 0         -
+src/parser.rs                      60                17    71.67%          12                 3    75.00%         120                36    70.00%           0                 0         -
+-----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------
+TOTAL                             100                21    79.00%          20                 3    85.00%         220                41    81.36%           0                 0         -
+";
+
     #[test]
-    fn test_filter_cargo_clippy_clean() {
-        let output = r#"    Checking rtk v0.5.0
-    Finished dev [unoptimized + debuginfo] target(s) in 1.53s
-"#;
-        let result = filter_cargo_clippy(output);
-        assert!(result.contains("✓ cargo clippy: No issues found"));
+    fn test_filter_cargo_llvm_cov() {
+        let (filtered, below) =
+            filter_cargo_llvm_cov(LLVM_COV_TABLE, &FilterOptions::default(), None);
+        assert_eq!(
+            filtered,
+            "✓ coverage: 81.4% lines, 79.0% regions, 85.0% functions"
+        );
+        assert!(!below);
+
+        let (filtered, below) =
+            filter_cargo_llvm_cov(LLVM_COV_TABLE, &FilterOptions::default(), Some(80.0));
+        assert_eq!(
+            filtered,
+            "✓ coverage: 81.4% lines, 79.0% regions, 85.0% functions\n1 files below 80%:\n  src/parser.rs 70.0% lines"
+        );
+        assert!(!below);
+
+        let (filtered, below) =
+            filter_cargo_llvm_cov(LLVM_COV_TABLE, &FilterOptions::default(), Some(90.0));
+        assert!(filtered.starts_with(
+            "✗ coverage: 81.4% lines, 79.0% regions, 85.0% functions (below 90% threshold)"
+        ));
+        assert!(below);
     }
 
     #[test]
-    fn test_filter_cargo_clippy_warnings() {
-        let output = r#"    Checking rtk v0.5.0
-warning: unused variable: `x` [unused_variables]
- --> src/main.rs:10:9
-  |
-10|     let x = 5;
-  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
-
-warning: this function has too many arguments [clippy::too_many_arguments]
- --> src/git.rs:16:1
-  |
-16| pub fn run(a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32, h: i32) {}
-  |
-
-warning: `rtk` (bin) generated 2 warnings
-    Finished dev [unoptimized + debuginfo] target(s) in 1.53s
-"#;
-        let result = filter_cargo_clippy(output);
-        assert!(result.contains("0 errors, 2 warnings"));
-        assert!(result.contains("unused_variables"));
-        assert!(result.contains("clippy::too_many_arguments"));
+    fn test_filter_cargo_llvm_cov_without_table() {
+        let output = "running 1 test\ntest a ... FAILED\n\nfailures:\n\n---- a stdout ----\npanicked\n\nfailures:\n    a\n\ntest result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        let (filtered, below) =
+            filter_cargo_llvm_cov(output, &FilterOptions::default(), Some(80.0));
+        assert_eq!(
+            filtered,
+            filter_cargo_test(output, &FilterOptions::default())
+        );
+        assert!(!below);
     }
 
     #[test]
-    fn test_filter_cargo_install_success() {
-        let output = r#"  Installing rtk v0.11.0
-  Downloading crates ...
-  Downloaded anyhow v1.0.80
-  Downloaded clap v4.5.0
-   Compiling libc v0.2.153
-   Compiling cfg-if v1.0.0
-   Compiling anyhow v1.0.80
-   Compiling clap v4.5.0
-   Compiling rtk v0.11.0
-    Finished `release` profile [optimized] target(s) in 45.23s
-  Replacing /Users/user/.cargo/bin/rtk
-   Replaced package `rtk v0.9.4` with `rtk v0.11.0` (/Users/user/.cargo/bin/rtk)
-"#;
-        let result = filter_cargo_install(output);
-        assert!(result.contains("✓ cargo install"), "got: {}", result);
-        assert!(result.contains("rtk v0.11.0"), "got: {}", result);
-        assert!(result.contains("5 deps compiled"), "got: {}", result);
-        assert!(result.contains("Replaced"), "got: {}", result);
-        assert!(!result.contains("Compiling"), "got: {}", result);
-        assert!(!result.contains("Downloading"), "got: {}", result);
+    fn test_filter_cargo_machete() {
+        let output = "Analyzing dependencies of crates in this directory...
+cargo-machete found the following unused dependencies in this directory:
+foo -- ./foo/Cargo.toml:
+\tserde
+\tregex
+bar -- ./bar/Cargo.toml:
+\tlog
+
+If you believe cargo-machete has detected an unused dependency incorrectly,
+you can add the dependency to the list of dependencies to ignore in the
+`[package.metadata.cargo-machete]` section of the appropriate Cargo.toml.
+Done!
+";
+        assert_eq!(
+            filter_cargo_machete(output, &FilterOptions::default()),
+            "cargo machete: 3 unused dependencies in 2 crates\nfoo: unused serde, regex\nbar: unused log"
+        );
+
+        let clean = "Analyzing dependencies of crates in this directory...
+cargo-machete didn't find any unused dependencies in this directory. Good job!
+Done!
+";
+        assert_eq!(
+            filter_cargo_machete(clean, &FilterOptions::default()),
+            "✓ cargo machete: no unused dependencies"
+        );
     }
 
     #[test]
-    fn test_filter_cargo_install_replace() {
-        let output = r#"  Installing rtk v0.11.0
-   Compiling rtk v0.11.0
-    Finished `release` profile [optimized] target(s) in 10.0s
-  Replacing /Users/user/.cargo/bin/rtk
-   Replaced package `rtk v0.9.4` with `rtk v0.11.0` (/Users/user/.cargo/bin/rtk)
+    fn test_filter_cargo_vendor() {
+        let output = r#"   Vendoring anyhow v1.0.80 (/home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/anyhow-1.0.80) to vendor/anyhow
+   Vendoring clap v4.5.0 (/home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/clap-4.5.0) to vendor/clap
+   Vendoring regex v1.10.2 (/home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/regex-1.10.2) to vendor/regex
+To use vendored sources, add this to your .cargo/config.toml for this project:
+
+[source.crates-io]
+replace-with = "vendored-sources"
+
+[source.vendored-sources]
+directory = "vendor"
 "#;
-        let result = filter_cargo_install(output);
-        assert!(result.contains("✓ cargo install"), "got: {}", result);
-        assert!(result.contains("Replacing"), "got: {}", result);
-        assert!(result.contains("Replaced"), "got: {}", result);
+        assert_eq!(
+            filter_cargo_vendor(output, &FilterOptions::default()),
+            "✓ cargo vendor: 3 crates vendored to vendor/\nAdd to .cargo/config.toml:\n[source.crates-io]\nreplace-with = \"vendored-sources\"\n\n[source.vendored-sources]\ndirectory = \"vendor\""
+        );
     }
 
     #[test]
-    fn test_filter_cargo_install_error() {
-        let output = r#"  Installing rtk v0.11.0
-   Compiling rtk v0.11.0
-error[E0308]: mismatched types
- --> src/main.rs:10:5
-  |
-10|     "hello"
-  |     ^^^^^^^ expected `i32`, found `&str`
+    fn test_filter_cargo_uninstall() {
+        let output =
+            "    Removing /home/user/.cargo/bin/ripgrep\n    Removing /home/user/.cargo/bin/rg\n";
+        assert_eq!(
+            filter_cargo_uninstall(output, &FilterOptions::default()),
+            "✓ cargo uninstall: ripgrep (removed /home/user/.cargo/bin/ripgrep)\n✓ cargo uninstall: rg (removed /home/user/.cargo/bin/rg)"
+        );
 
-error: aborting due to 1 previous error
-"#;
-        let result = filter_cargo_install(output);
-        assert!(result.contains("cargo install: 1 error"), "got: {}", result);
-        assert!(result.contains("E0308"), "got: {}", result);
-        assert!(result.contains("mismatched types"), "got: {}", result);
-        assert!(!result.contains("aborting"), "got: {}", result);
+        let output = "error: package ID specification `foo` did not match any packages\n";
+        assert_eq!(
+            filter_cargo_uninstall(output, &FilterOptions::default()),
+            "✗ cargo uninstall: foo is not installed"
+        );
     }
 
     #[test]
-    fn test_filter_cargo_install_already_installed() {
-        let output = r#"  Ignored package `rtk v0.11.0`, is already installed
-"#;
-        let result = filter_cargo_install(output);
-        assert!(result.contains("already installed"), "got: {}", result);
-        assert!(result.contains("rtk v0.11.0"), "got: {}", result);
+    fn test_filter_cargo_search_empty_and_limit() {
+        assert_eq!(
+            filter_cargo_search("", &FilterOptions::default(), 10),
+            "✓ cargo search: no matching crates"
+        );
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(search_limit(&args("serde --limit 3")), 3);
+        assert_eq!(search_limit(&args("--limit=25 tokio")), 25);
+        assert_eq!(search_limit(&args("tokio")), 10);
     }
 
     #[test]
-    fn test_filter_cargo_install_up_to_date() {
-        let output = r#"  Ignored package `cargo-deb v2.1.0 (/Users/user/cargo-deb)`, is already installed
+    fn test_filter_cargo_publish_dry_run() {
+        let output = r#"    Updating crates.io index
+   Packaging foo v1.2.3 (/home/user/foo)
+    Packaged 12 files, 30.1KiB (8.2KiB compressed)
+   Verifying foo v1.2.3 (/home/user/foo)
+   Compiling serde v1.0.190
+   Compiling foo v1.2.3 (/home/user/foo/target/package/foo-1.2.3)
+    Finished `dev` profile [unoptimized + debuginfo] target(s) in 4.12s
+   Uploading foo v1.2.3 (/home/user/foo)
+warning: aborting upload due to dry run
 "#;
-        let result = filter_cargo_install(output);
-        assert!(result.contains("already installed"), "got: {}", result);
-        assert!(result.contains("cargo-deb v2.1.0"), "got: {}", result);
+        assert_eq!(
+            filter_cargo_publish(output, &FilterOptions::default()),
+            "✓ cargo publish (dry-run): foo v1.2.3 ready (12 files, 30.1KiB)"
+        );
     }
 
     #[test]
-    fn test_filter_cargo_install_empty_output() {
-        let result = filter_cargo_install("");
-        assert!(result.contains("✓ cargo install"), "got: {}", result);
-        assert!(result.contains("0 deps compiled"), "got: {}", result);
+    fn test_filter_cargo_publish_uploaded() {
+        let output = r#"   Packaging foo v1.2.3 (/home/user/foo)
+   Verifying foo v1.2.3 (/home/user/foo)
+   Uploading foo v1.2.3 (/home/user/foo)
+    Uploaded foo v1.2.3 to registry `crates-io`
+note: waiting for `foo v1.2.3` to be available at registry `crates-io`.
+You may press ctrl-c to skip waiting; the crate should be available shortly.
+   Published foo v1.2.3 at registry `crates-io`
+"#;
+        let result = filter_cargo_publish(output, &FilterOptions::default());
+        assert!(
+            result.starts_with("✓ cargo publish: foo v1.2.3"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("Uploaded foo v1.2.3 to registry `crates-io`"));
+        assert!(result.contains("Published foo v1.2.3"));
+        assert!(!result.contains("ctrl-c"));
     }
 
     #[test]
-    fn test_filter_cargo_install_path_warning() {
-        let output = r#"  Installing rtk v0.11.0
-   Compiling rtk v0.11.0
-    Finished `release` profile [optimized] target(s) in 10.0s
-  Replacing /Users/user/.cargo/bin/rtk
-   Replaced package `rtk v0.9.4` with `rtk v0.11.0` (/Users/user/.cargo/bin/rtk)
-warning: be sure to add `/Users/user/.cargo/bin` to your PATH
+    fn test_filter_cargo_publish_dirty_tree() {
+        let output = r#"    Updating crates.io index
+error: 2 files in the working directory contain changes that were not yet committed into git:
+
+src/main.rs
+Cargo.toml
+
+to proceed despite this and include the uncommitted changes, pass the `--allow-dirty` flag
 "#;
-        let result = filter_cargo_install(output);
-        assert!(result.contains("✓ cargo install"), "got: {}", result);
+        let result = filter_cargo_publish(output, &FilterOptions::default());
         assert!(
-            result.contains("be sure to add"),
-            "PATH warning should be kept: {}",
+            result.starts_with("cargo publish: 1 error (package)"),
+            "got: {}",
             result
         );
-        assert!(result.contains("Replaced"), "got: {}", result);
+        assert!(result.contains("src/main.rs"));
+        assert!(result.contains("--allow-dirty"));
+        assert!(!result.contains("Updating"));
     }
 
     #[test]
-    fn test_filter_cargo_install_multiple_errors() {
-        let output = r#"  Installing rtk v0.11.0
-   Compiling rtk v0.11.0
-error[E0308]: mismatched types
- --> src/main.rs:10:5
-  |
-10|     "hello"
-  |     ^^^^^^^ expected `i32`, found `&str`
-
-error[E0425]: cannot find value `foo`
- --> src/lib.rs:20:9
-  |
-20|     foo
-  |     ^^^ not found in this scope
-
-error: aborting due to 2 previous errors
+    fn test_filter_cargo_publish_missing_metadata() {
+        let output = r#"   Packaging foo v0.1.0 (/home/user/foo)
+warning: manifest has no description, license, license-file, documentation, homepage or repository.
+See https://doc.rust-lang.org/cargo/reference/manifest.html#package-metadata for more info.
+error: failed to publish to registry at https://crates.io
+
+Caused by:
+  the remote server responded with an error: missing or empty metadata fields: description, license.
 "#;
-        let result = filter_cargo_install(output);
+        let result = filter_cargo_publish(output, &FilterOptions::default());
         assert!(
-            result.contains("2 errors"),
-            "should show 2 errors: {}",
+            result.starts_with("cargo publish: 1 error (foo v0.1.0)"),
+            "got: {}",
             result
         );
-        assert!(result.contains("E0308"), "got: {}", result);
-        assert!(result.contains("E0425"), "got: {}", result);
-        assert!(!result.contains("aborting"), "got: {}", result);
+        assert!(result.contains("missing or empty metadata fields"));
     }
 
     #[test]
-    fn test_filter_cargo_install_locking_and_blocking() {
-        let output = r#"  Locking 45 packages to latest compatible versions
-  Blocking waiting for file lock on package cache
-  Downloading crates ...
-  Downloaded serde v1.0.200
-   Compiling serde v1.0.200
-   Compiling rtk v0.11.0
-    Finished `release` profile [optimized] target(s) in 30.0s
-  Installing rtk v0.11.0
-"#;
-        let result = filter_cargo_install(output);
-        assert!(result.contains("✓ cargo install"), "got: {}", result);
-        assert!(!result.contains("Locking"), "got: {}", result);
-        assert!(!result.contains("Blocking"), "got: {}", result);
-        assert!(!result.contains("Downloading"), "got: {}", result);
+    fn test_with_test_filters() {
+        let names = vec!["foo::test_b".to_string()];
+        assert_eq!(
+            with_test_filters(&["--lib".to_string()], &names),
+            vec!["--lib", "--", "--exact", "foo::test_b"]
+        );
+        assert_eq!(
+            with_test_filters(&["--".to_string(), "--nocapture".to_string()], &names),
+            vec!["--", "--nocapture", "--exact", "foo::test_b"]
+        );
     }
 
     #[test]
-    fn test_filter_cargo_install_from_path() {
-        let output = r#"  Installing /Users/user/projects/rtk
-   Compiling rtk v0.11.0
-    Finished `release` profile [optimized] target(s) in 10.0s
-"#;
-        let result = filter_cargo_install(output);
-        // Path-based install: crate info not extracted from path
-        assert!(result.contains("✓ cargo install"), "got: {}", result);
-        assert!(result.contains("1 deps compiled"), "got: {}", result);
+    fn test_filter_kind_from_str() {
+        assert_eq!("build".parse::<FilterKind>(), Ok(FilterKind::Build));
+        assert_eq!("Clippy".parse::<FilterKind>(), Ok(FilterKind::Clippy));
+        assert_eq!("auto".parse::<FilterKind>(), Ok(FilterKind::Auto));
+        assert!("deploy".parse::<FilterKind>().is_err());
+    }
+
+    #[test]
+    fn test_filter_kind_auto_sniffing() {
+        let test_log = "running 2 tests\ntest a ... ok\n\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(FilterKind::Auto.resolve(test_log), FilterKind::Test);
+
+        let nextest_log = "    Starting 3 tests across 1 binary\n        PASS [   0.01s] a\n     Summary [   0.02s] 3 tests run: 3 passed, 0 skipped\n";
+        assert_eq!(FilterKind::Auto.resolve(nextest_log), FilterKind::Nextest);
+
+        let clippy_log = "warning: redundant clone [clippy::redundant_clone]\n --> src/a.rs:1:1\n";
+        assert_eq!(FilterKind::Auto.resolve(clippy_log), FilterKind::Clippy);
+
+        let install_log = "  Installing ripgrep v14.1.0\n   Installed package `ripgrep v14.1.0`\n";
+        assert_eq!(FilterKind::Auto.resolve(install_log), FilterKind::Install);
+
+        let build_log = "   Compiling rtk v0.5.0\n    Finished dev target(s) in 1.0s\n";
+        assert_eq!(FilterKind::Auto.resolve(build_log), FilterKind::Build);
+
+        // Explicit kinds are never overridden
+        assert_eq!(FilterKind::Build.resolve(test_log), FilterKind::Build);
     }
 
     #[test]
-    fn test_format_crate_info() {
-        assert_eq!(format_crate_info("rtk", "v0.11.0", ""), "rtk v0.11.0");
-        assert_eq!(format_crate_info("rtk", "", ""), "rtk");
-        assert_eq!(format_crate_info("", "", "package"), "package");
-        assert_eq!(format_crate_info("", "v0.1.0", "fallback"), "fallback");
+    fn test_color_choice_from_str() {
+        assert_eq!("auto".parse::<ColorChoice>(), Ok(ColorChoice::Auto));
+        assert_eq!("ALWAYS".parse::<ColorChoice>(), Ok(ColorChoice::Always));
+        assert_eq!("never".parse::<ColorChoice>(), Ok(ColorChoice::Never));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+    }
+
+    #[test]
+    fn test_render_plain_when_color_disabled() {
+        let opts = FilterOptions::default();
+        let summary = "cargo build: 2 errors, 1 warnings (3 crates)";
+        assert_eq!(opts.render(summary), summary);
+    }
+
+    #[test]
+    fn test_colorize_summary() {
+        let theme = Theme::unicode();
+        let success = colorize("✓ cargo build (3 crates compiled)", &theme);
+        assert!(
+            success.contains("\x1b[32m"),
+            "expected green: {:?}",
+            success
+        );
+
+        let header = colorize("cargo build: 2 errors, 0 warnings (3 crates)", &theme);
+        assert!(
+            header.contains("\x1b[1;31m2 errors"),
+            "expected red: {:?}",
+            header
+        );
+        assert!(header.contains(", 0 warnings"), "zero count stays plain");
+
+        let warn = colorize("cargo build: 0 errors, 4 warnings (3 crates)", &theme);
+        assert!(
+            warn.contains("\x1b[33m4 warnings"),
+            "expected yellow: {:?}",
+            warn
+        );
+        assert_eq!(
+            crate::utils::strip_ansi(&warn),
+            "cargo build: 0 errors, 4 warnings (3 crates)"
+        );
     }
 
     #[test]
@@ -1385,7 +3775,7 @@ error: aborting due to 2 previous errors
 ────────────────────────────
      Summary [   0.192s] 301 tests run: 301 passed, 0 skipped
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert_eq!(
             result, "✓ cargo nextest: 301 passed (1 binary, 0.192s)",
             "got: {}",
@@ -1421,7 +3811,7 @@ error: aborting due to 2 previous errors
         FAIL [   0.006s] (4/4) test-proj tests::another_failing
 error: test run failed
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert!(
             result.contains("tests::failing_test"),
             "should contain first failure: {}",
@@ -1470,7 +3860,7 @@ error: test run failed
 ────────────────────────────
      Summary [   0.500s] 50 tests run: 50 passed, 3 skipped
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert_eq!(
             result, "✓ cargo nextest: 50 passed, 3 skipped (2 binaries, 0.500s)",
             "got: {}",
@@ -1494,7 +3884,7 @@ error: test run failed
         FAIL [   0.005s] (2/2) proj tests::bad
 error: test run failed
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert!(
             result.contains("assertion failed: false"),
             "should show panic message: {}",
@@ -1521,7 +3911,7 @@ error: test run failed
 ────────────────────────────
      Summary [   1.234s] 100 tests run: 100 passed, 0 skipped
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert_eq!(
             result, "✓ cargo nextest: 100 passed (5 binaries, 1.234s)",
             "got: {}",
@@ -1541,7 +3931,7 @@ error: test run failed
 ────────────────────────────
      Summary [   0.050s] 10 tests run: 10 passed, 0 skipped
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert!(
             !result.contains("Compiling"),
             "should strip Compiling: {}",
@@ -1566,7 +3956,7 @@ error: test run failed
 
     #[test]
     fn test_filter_cargo_nextest_empty() {
-        let result = filter_cargo_nextest("");
+        let result = filter_cargo_nextest("", &FilterOptions::default());
         assert!(result.is_empty(), "got: {}", result);
     }
 
@@ -1585,7 +3975,7 @@ error: test run failed
         FAIL [   0.005s] (1/3) proj tests::bad
 error: test run failed
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert!(
             result.contains("Cancelling due to test failure"),
             "should include cancel notice: {}",
@@ -1612,7 +4002,7 @@ error: test run failed
 ────────────────────────────
      Summary MALFORMED LINE
 "#;
-        let result = filter_cargo_nextest(output);
+        let result = filter_cargo_nextest(output, &FilterOptions::default());
         assert!(
             result.contains("Summary MALFORMED"),
             "should fall back to raw summary: {}",
@@ -0,0 +1,436 @@
+//! `rtk cargo deny`: cargo-deny findings grouped by check and severity.
+//!
+//! Parses both the human report (`error[rejected]: ...` diagnostics with code snippets)
+//! and `--format json` (one diagnostic object per line), then prints one summary line
+//! plus one line per finding with the offending crate.
+
+use crate::cargo_cmd::FilterOptions;
+use crate::tracking;
+use crate::utils::{exit_code, normalize_newlines, strip_ansi};
+use anyhow::{Context, Result};
+use std::sync::OnceLock;
+
+/// The four cargo-deny checks, in the order it reports them
+const CHECKS: &[&str] = &["advisories", "bans", "licenses", "sources"];
+
+/// One diagnostic reported by cargo deny
+#[derive(Debug, Clone, PartialEq)]
+struct Finding {
+    /// `error`, `warning`, `note` or `help`
+    severity: String,
+    /// Diagnostic code, e.g. `vulnerability`, `rejected`, `banned`
+    code: String,
+    message: String,
+    /// `name version` of the offending crate, when the diagnostic names one
+    krate: Option<String>,
+}
+
+impl Finding {
+    fn is_error(&self) -> bool {
+        self.severity == "error"
+    }
+
+    /// Which check produced this code (`other` for codes this filter does not know)
+    fn check(&self) -> &'static str {
+        check_for_code(&self.code)
+    }
+}
+
+pub fn run(args: &[String], opts: &FilterOptions, verbose: u8) -> Result<()> {
+    opts.reject_json("cargo deny")?;
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = opts.cargo_command();
+    cmd.arg("deny");
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    if verbose > 0 {
+        eprintln!("Running: cargo deny {}", args.join(" "));
+    }
+
+    let output = cmd.output().context("Failed to run cargo deny")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let status = exit_code(&output.status);
+    let clean = normalize_newlines(&strip_ansi(&raw));
+
+    let findings = parse_json(&clean).or_else(|| parse_text(&clean));
+    let filtered = match &findings {
+        Some(findings) => format_findings(findings, opts),
+        // Not a cargo-deny report (e.g. not installed, bad deny.toml): keep cargo's own words
+        None => clean.trim().to_string(),
+    };
+    println!("{}", opts.render(&filtered));
+
+    timer.track(
+        &format!("cargo deny {}", args.join(" ")),
+        &format!("rtk cargo deny {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    // Gate CI on error-level findings; otherwise keep cargo-deny's own status
+    let code = match &findings {
+        Some(findings) if findings.iter().any(Finding::is_error) => 1,
+        _ => status,
+    };
+    if code != 0 {
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
+
+/// Check a diagnostic code belongs to
+fn check_for_code(code: &str) -> &'static str {
+    match code {
+        "vulnerability"
+        | "notice"
+        | "unmaintained"
+        | "unsound"
+        | "yanked"
+        | "index-failure"
+        | "advisory-not-detected"
+        | "unknown-advisory" => "advisories",
+        "banned"
+        | "allowed"
+        | "not-allowed"
+        | "duplicate"
+        | "skipped"
+        | "wildcard"
+        | "unmatched-skip"
+        | "unmatched-skip-root"
+        | "build-script-not-allowed"
+        | "exact-features-mismatch"
+        | "feature-banned"
+        | "unknown-feature"
+        | "default-feature-enabled"
+        | "path-bypassed"
+        | "checksum-match"
+        | "checksum-mismatch"
+        | "denied-by-extension"
+        | "unmatched-bypass"
+        | "unmatched-path-bypass"
+        | "unmatched-glob"
+        | "workspace-duplicate"
+        | "unresolved-workspace-dependency"
+        | "unused-workspace-dependency"
+        | "nonexistent-bypass" => "bans",
+        "rejected"
+        | "accepted"
+        | "unlicensed"
+        | "skipped-private-workspace-crate"
+        | "license-not-encountered"
+        | "license-exception-not-encountered"
+        | "missing-clarification-file"
+        | "parse-error"
+        | "empty-license-field"
+        | "no-license-field"
+        | "gather-failure" => "licenses",
+        "git-source-underspecified"
+        | "allowed-source"
+        | "allowed-by-organization"
+        | "source-not-allowed"
+        | "unmatched-source"
+        | "unmatched-organization" => "sources",
+        _ => "other",
+    }
+}
+
+/// Parse `cargo deny --format json`: one `{"type":"diagnostic",...}` object per line
+fn parse_json(output: &str) -> Option<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let mut seen = false;
+    for line in output.lines() {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            continue;
+        };
+        match json.get("type").and_then(|t| t.as_str()) {
+            Some("diagnostic") => {}
+            Some("summary") => {
+                seen = true;
+                continue;
+            }
+            _ => continue,
+        }
+        seen = true;
+        let Some(fields) = json.get("fields") else {
+            continue;
+        };
+        let text = |key: &str| {
+            fields
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string()
+        };
+        // The inclusion graph names the crate; a label span (`openssl 0.10.0 registry+...`)
+        // is the fallback
+        let krate = fields
+            .pointer("/graphs/0/Krate")
+            .and_then(|k| {
+                let name = k.get("name")?.as_str()?;
+                let version = k.get("version").and_then(|v| v.as_str()).unwrap_or("");
+                Some(format!("{} {}", name, version).trim().to_string())
+            })
+            .or_else(|| {
+                fields
+                    .pointer("/labels/0/span")
+                    .and_then(|s| s.as_str())
+                    .and_then(crate_from_span)
+            });
+        findings.push(Finding {
+            severity: text("severity"),
+            code: text("code"),
+            message: text("message"),
+            krate,
+        });
+    }
+    seen.then_some(findings)
+}
+
+/// Parse the human report: `severity[code]: message` headers, each followed by a snippet
+/// that names the crate (`#ring@0.16.20` paths, `│ openssl 0.10.0 registry+...` lines)
+fn parse_text(output: &str) -> Option<Vec<Finding>> {
+    static HEADER_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static SOURCE_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static SNIPPET_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let header_re = HEADER_RE.get_or_init(|| {
+        regex::Regex::new(r"^(error|warning|note|help)\[([\w-]+)\]: (.+)$")
+            .expect("invalid deny header regex")
+    });
+    let source_re = SOURCE_RE.get_or_init(|| {
+        regex::Regex::new(r"#([\w-]+)@([\w.+-]+)").expect("invalid deny source regex")
+    });
+    let snippet_re = SNIPPET_RE.get_or_init(|| {
+        regex::Regex::new(r"^\s*\d+\s*│\s*(?:[╭│]\s*)?([\w-]+) (\d+\.\d+\.\d+\S*)")
+            .expect("invalid deny snippet regex")
+    });
+
+    let mut findings: Vec<Finding> = Vec::new();
+    let mut seen_status = false;
+    for line in output.lines() {
+        if let Some(caps) = header_re.captures(line) {
+            findings.push(Finding {
+                severity: caps[1].to_string(),
+                code: caps[2].to_string(),
+                message: caps[3].trim().to_string(),
+                krate: None,
+            });
+            continue;
+        }
+        // "advisories ok, bans FAILED, licenses ok, sources ok"
+        if CHECKS.iter().any(|check| {
+            line.trim_start().starts_with(&format!("{} ok", check))
+                || line.trim_start().starts_with(&format!("{} FAILED", check))
+        }) {
+            seen_status = true;
+            continue;
+        }
+        let Some(finding) = findings.last_mut() else {
+            continue;
+        };
+        if finding.krate.is_some() {
+            continue;
+        }
+        if let Some(caps) = source_re
+            .captures(line)
+            .or_else(|| snippet_re.captures(line))
+        {
+            finding.krate = Some(format!("{} {}", &caps[1], &caps[2]));
+        }
+    }
+
+    (seen_status || !findings.is_empty()).then_some(findings)
+}
+
+/// `openssl 0.10.0` from a label span like `openssl 0.10.0 registry+https://...`
+fn crate_from_span(span: &str) -> Option<String> {
+    let mut words = span.split_whitespace();
+    let name = words.next()?;
+    let version = words.next()?;
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then(|| format!("{} {}", name, version))
+}
+
+/// `1 advisory`, `2 license violations`, `3 bans`
+fn count_label(check: &str, n: usize) -> String {
+    let (one, many) = match check {
+        "advisories" => ("advisory", "advisories"),
+        "licenses" => ("license violation", "license violations"),
+        "bans" => ("ban", "bans"),
+        "sources" => ("source violation", "source violations"),
+        _ => ("other finding", "other findings"),
+    };
+    format!("{} {}", n, if n == 1 { one } else { many })
+}
+
+fn format_findings(findings: &[Finding], opts: &FilterOptions) -> String {
+    // Notes and helps (e.g. `accepted` licenses) are informational only
+    let reported: Vec<&Finding> = findings
+        .iter()
+        .filter(|f| f.severity == "error" || f.severity == "warning")
+        .collect();
+    let warnings = reported.iter().filter(|f| !f.is_error()).count();
+    let warning_note = if warnings > 0 {
+        format!(
+            ", {} warning{}",
+            warnings,
+            if warnings == 1 { "" } else { "s" }
+        )
+    } else {
+        String::new()
+    };
+
+    let errors: Vec<String> = CHECKS
+        .iter()
+        .chain(&["other"])
+        .filter_map(|check| {
+            let n = reported
+                .iter()
+                .filter(|f| f.is_error() && f.check() == *check)
+                .count();
+            (n > 0).then(|| count_label(check, n))
+        })
+        .collect();
+    if errors.is_empty() && warnings == 0 {
+        return format!("{} cargo deny: all checks passed", opts.theme.ok);
+    }
+
+    let mut result = if errors.is_empty() {
+        format!("{} cargo deny: no errors{}\n", opts.theme.ok, warning_note)
+    } else {
+        format!("cargo deny: {}{}\n", errors.join(", "), warning_note)
+    };
+
+    // Grouped by check, errors before warnings within each
+    let mut ordered: Vec<&Finding> = Vec::new();
+    for check in CHECKS.iter().chain(&["other"]) {
+        for errors_first in [true, false] {
+            ordered.extend(
+                reported
+                    .iter()
+                    .filter(|f| f.check() == *check && f.is_error() == errors_first),
+            );
+        }
+    }
+    let mut current_check = "";
+    for finding in ordered.iter().take(opts.max_errors) {
+        if finding.check() != current_check {
+            current_check = finding.check();
+            result.push_str(&format!("{}:\n", current_check));
+        }
+        let krate = finding
+            .krate
+            .as_deref()
+            .map(|k| format!("{}: ", k))
+            .unwrap_or_default();
+        result.push_str(&format!(
+            "  {}[{}] {}{}\n",
+            finding.severity, finding.code, krate, finding.message
+        ));
+    }
+    if ordered.len() > opts.max_errors {
+        result.push_str(&format!(
+            "... +{} more findings\n",
+            ordered.len() - opts.max_errors
+        ));
+    }
+
+    result.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT_REPORT: &str = r#"error[vulnerability]: Use after free in X509 verification
+    ┌─ /work/Cargo.lock:112:1
+    │
+112 │ openssl 0.10.0 registry+https://github.com/rust-lang/crates.io-index
+    │ -------------------------------------------------------------------- security vulnerability detected
+    │
+    = ID: RUSTSEC-2024-0001
+
+warning[duplicate]: found 2 duplicate entries for crate 'windows-sys'
+    ┌─ /work/Cargo.lock:300:1
+    │
+300 │ ╭ windows-sys 0.48.0 registry+https://github.com/rust-lang/crates.io-index
+301 │ │ windows-sys 0.52.0 registry+https://github.com/rust-lang/crates.io-index
+    │ ╰────────────────────────────────────────────────────────────────────────┘ lock entries
+
+error[rejected]: failed to satisfy license requirements
+  ┌─ registry+https://github.com/rust-lang/crates.io-index#ring@0.16.20:2:36
+  │
+2 │ license = "MIT AND ISC AND OpenSSL"
+  │            ----------------------- rejected: license is not explicitly allowed
+
+error[rejected]: failed to satisfy license requirements
+  ┌─ registry+https://github.com/rust-lang/crates.io-index#webpki@0.22.0:4:12
+  │
+4 │ license = "ISC"
+
+advisories FAILED, bans ok, licenses FAILED, sources ok
+"#;
+
+    #[test]
+    fn test_parse_text_report() {
+        let findings = parse_text(TEXT_REPORT).unwrap();
+        assert_eq!(findings.len(), 4);
+        assert_eq!(findings[0].code, "vulnerability");
+        assert_eq!(findings[0].krate.as_deref(), Some("openssl 0.10.0"));
+        assert_eq!(findings[1].krate.as_deref(), Some("windows-sys 0.48.0"));
+        assert_eq!(findings[2].krate.as_deref(), Some("ring 0.16.20"));
+        assert_eq!(findings[3].check(), "licenses");
+    }
+
+    #[test]
+    fn test_format_findings_groups_by_check() {
+        let findings = parse_text(TEXT_REPORT).unwrap();
+        assert_eq!(
+            format_findings(&findings, &FilterOptions::default()),
+            "cargo deny: 1 advisory, 2 license violations, 1 warning\n\
+             advisories:\n  \
+             error[vulnerability] openssl 0.10.0: Use after free in X509 verification\n\
+             bans:\n  \
+             warning[duplicate] windows-sys 0.48.0: found 2 duplicate entries for crate 'windows-sys'\n\
+             licenses:\n  \
+             error[rejected] ring 0.16.20: failed to satisfy license requirements\n  \
+             error[rejected] webpki 0.22.0: failed to satisfy license requirements"
+        );
+    }
+
+    #[test]
+    fn test_clean_run() {
+        let findings = parse_text("advisories ok, bans ok, licenses ok, sources ok\n").unwrap();
+        assert!(findings.is_empty());
+        assert_eq!(
+            format_findings(&findings, &FilterOptions::default()),
+            "✓ cargo deny: all checks passed"
+        );
+        assert!(parse_text("error: no such command: `deny`\n").is_none());
+    }
+
+    #[test]
+    fn test_parse_json_report() {
+        let json = r#"{"type":"diagnostic","fields":{"severity":"error","code":"banned","message":"crate 'openssl = 0.10.0' is explicitly banned","labels":[{"span":"openssl 0.10.0 registry+https://github.com/rust-lang/crates.io-index","message":"banned here","line":12,"column":1}],"graphs":[{"Krate":{"name":"openssl","version":"0.10.0"}}],"notes":[]}}
+{"type":"diagnostic","fields":{"severity":"note","code":"accepted","message":"license requirements satisfied","labels":[],"graphs":[],"notes":[]}}
+{"type":"diagnostic","fields":{"severity":"warning","code":"git-source-underspecified","message":"git source is underspecified","labels":[{"span":"foo 0.1.0 git+https://example.com/foo","message":"","line":3,"column":1}],"graphs":[],"notes":[]}}
+{"type":"summary","fields":{"advisories":{"errors":0,"warnings":0,"notes":0,"helps":0}}}"#;
+        let findings = parse_json(json).unwrap();
+        assert_eq!(findings.len(), 3);
+        assert_eq!(findings[0].krate.as_deref(), Some("openssl 0.10.0"));
+        assert_eq!(findings[0].check(), "bans");
+        assert_eq!(findings[2].krate.as_deref(), Some("foo 0.1.0"));
+        assert_eq!(findings[2].check(), "sources");
+        assert!(parse_json(TEXT_REPORT).is_none());
+
+        let result = format_findings(&findings, &FilterOptions::default());
+        assert!(result.starts_with("cargo deny: 1 ban, 1 warning\n"));
+        assert!(!result.contains("accepted"));
+    }
+}
@@ -0,0 +1,1343 @@
+use super::{
+    diagnostic_rule, failed_crate, format_failed_crates, in_only, is_noise_line, limit_context,
+    only_note, render_blocks, span_file, split_sccache_stats,
+};
+use crate::cargo_cmd::FilterOptions;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Lines naming a file cargo wrote, as (prefix, summary label); kept instead of dropped as noise
+const ARTIFACT_PREFIXES: &[(&str, &str)] = &[("Timing report saved to ", "build timings")];
+
+/// `(label, path)` when `line` reports an artifact cargo produced
+fn artifact_line(line: &str) -> Option<(&'static str, &str)> {
+    let trimmed = line.trim();
+    ARTIFACT_PREFIXES.iter().find_map(|(prefix, label)| {
+        trimmed
+            .strip_prefix(prefix)
+            .map(|path| (*label, path.trim_end_matches('.')))
+    })
+}
+
+/// Cache hit rate from an sccache stats table (see [`split_sccache_stats`]): the
+/// `Cache hits rate` row when present, otherwise computed from the `Cache hits` /
+/// `Cache misses` counts
+fn sccache_hit_rate(stats: &str) -> Option<f64> {
+    let row = |name: &str| {
+        stats.lines().find_map(|line| {
+            let value = line.strip_prefix(name)?.trim();
+            value.split_whitespace().next()?.parse::<f64>().ok()
+        })
+    };
+    if let Some(rate) = row("Cache hits rate ") {
+        return Some(rate);
+    }
+    let hits = row("Cache hits ")?;
+    let misses = row("Cache misses ")?;
+    if hits + misses == 0.0 {
+        return None;
+    }
+    Some(hits * 100.0 / (hits + misses))
+}
+
+/// Filter cargo build/check output - strip "Compiling"/"Checking" lines, keep errors + summary
+pub(crate) fn filter_cargo_build(output: &str, opts: &FilterOptions) -> String {
+    let (output, sccache_stats) = split_sccache_stats(output);
+    let output = output.as_str();
+    let mut errors: Vec<String> = Vec::new();
+    let mut failed_crates: Vec<String> = Vec::new();
+    let mut warnings = 0;
+    let mut error_count = 0;
+    let mut compiled = 0;
+    let mut in_error = false;
+    let mut current_error = Vec::new();
+    // Lines outside any block that would be dropped but match a `keep` pattern
+    let mut kept_lines: Vec<String> = Vec::new();
+    let mut warnings_by_crate: Vec<(String, usize)> = Vec::new();
+    // `✓ build timings: <path>` and the like, shown under the summary line
+    let mut artifacts: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        if let Some((label, path)) = artifact_line(line) {
+            artifacts.push(format!("{} {}: {}", opts.check(), label, path));
+            continue;
+        }
+        if line.trim_start().starts_with("Compiling") || line.trim_start().starts_with("Checking") {
+            compiled += 1;
+            if opts.keeps(line) {
+                kept_lines.push(line.trim().to_string());
+            }
+            continue;
+        }
+        if is_noise_line(line) {
+            if opts.keeps(line) {
+                kept_lines.push(line.trim().to_string());
+            }
+            continue;
+        }
+
+        // `--message-format short`: one diagnostic per line, no block to collect
+        if let Some((location, diagnostic)) = short_diagnostic(line) {
+            if in_error && !current_error.is_empty() {
+                errors.push(current_error.join("\n"));
+                current_error.clear();
+            }
+            in_error = false;
+            if diagnostic.starts_with("error") {
+                error_count += 1;
+            } else {
+                warnings += 1;
+            }
+            errors.push(format!("{}\n  --> {}", diagnostic, location));
+            continue;
+        }
+
+        // Detect error/warning blocks
+        if line.starts_with("error[") || line.starts_with("error:") {
+            // "could not compile `foo`" only names the failing crate; keep that for the header
+            if let Some(name) = failed_crate(line) {
+                if !failed_crates.contains(&name) {
+                    failed_crates.push(name);
+                }
+                continue;
+            }
+            // Skip "error: aborting due to" summary lines
+            if line.contains("aborting due to") {
+                continue;
+            }
+            if in_error && !current_error.is_empty() {
+                errors.push(current_error.join("\n"));
+                current_error.clear();
+            }
+            error_count += 1;
+            in_error = true;
+            current_error.push(line.to_string());
+        } else if line.starts_with("warning:")
+            && line.contains("generated")
+            && line.contains("warning")
+        {
+            // "warning: `crate` generated N warnings" summary line
+            if let Some((name, count)) = crate_warning_summary(line) {
+                match warnings_by_crate.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, total)) => *total += count,
+                    None => warnings_by_crate.push((name, count)),
+                }
+            }
+            if opts.keeps(line) {
+                kept_lines.push(line.to_string());
+            }
+            continue;
+        } else if line.starts_with("warning:") || line.starts_with("warning[") {
+            if in_error && !current_error.is_empty() {
+                errors.push(current_error.join("\n"));
+                current_error.clear();
+            }
+            warnings += 1;
+            in_error = true;
+            current_error.push(line.to_string());
+        } else if in_error {
+            if line.trim().is_empty() && current_error.len() > 3 {
+                errors.push(current_error.join("\n"));
+                current_error.clear();
+                in_error = false;
+            } else {
+                current_error.push(line.to_string());
+            }
+        }
+    }
+
+    if !current_error.is_empty() {
+        errors.push(current_error.join("\n"));
+    }
+
+    // Linker failures dump the whole `cc` command line and symbol list; collapse them
+    for err in errors.iter_mut() {
+        if err.starts_with("error: linking with") {
+            *err = summarize_linker_error(err);
+        } else if let Some(panic) = label_proc_macro_panic(err) {
+            *err = panic;
+        } else if let Some(hint) = missing_crate_hint(err) {
+            err.push('\n');
+            err.push_str(&hint);
+        }
+        if let Some(hint) = feature_hint(err) {
+            // Right under the header: the gating note is usually buried at the bottom
+            let header_end = err.find('\n').unwrap_or(err.len());
+            err.insert_str(header_end, &format!("\n{}", hint));
+        }
+    }
+
+    annotate_targets(&mut errors);
+
+    let across = format_error_files(&errors);
+    let categories = warning_categories(&errors);
+
+    // --errors-only / --warnings-only: drop the other kind before the max_errors cap
+    // `keep` matches survive all of these
+    let hidden = if opts.errors_only && warnings > 0 {
+        errors.retain(|block| !block.starts_with("warning") || opts.keeps(block));
+        format!(" ({} warnings hidden)", warnings)
+    } else if opts.warnings_only && error_count > 0 {
+        errors.retain(|block| !block.starts_with("error") || opts.keeps(block));
+        format!(" ({} errors hidden)", error_count)
+    } else {
+        // Errors first: warning details only once the build compiles (count stays in the summary)
+        if error_count > 0 && !opts.with_warnings {
+            errors.retain(|block| !block.starts_with("warning") || opts.keeps(block));
+        }
+        String::new()
+    };
+
+    let only = opts.only.as_deref().map(|glob| {
+        let total = errors.len();
+        errors.retain(|block| in_only(glob, block_file(block)) || opts.keeps(block));
+        only_note(glob, errors.len(), total)
+    });
+    let only = only.unwrap_or_default();
+
+    if error_count == 0 && (warnings == 0 || opts.errors_only) {
+        let mut result = format!(
+            "{} cargo build ({} crates compiled){}{}",
+            opts.check(),
+            compiled,
+            hidden,
+            only
+        );
+        if let Some(rate) = sccache_hit_rate(&sccache_stats) {
+            result.push_str(&format!("\nsccache: {:.0}% cache hit", rate));
+        }
+        for artifact in &artifacts {
+            result.push('\n');
+            result.push_str(artifact);
+        }
+        for kept in errors.iter().chain(&kept_lines) {
+            result.push_str("\n\n");
+            result.push_str(kept);
+        }
+        return result;
+    }
+
+    let failed_in = format_failed_crates(&failed_crates);
+
+    // --first: the earliest error only; later ones are often cascades of it
+    if opts.first && error_count > 0 {
+        if let Some(first) = errors.iter().find(|block| block.starts_with("error")) {
+            return format!(
+                "cargo build: {} errors{}{} (showing first){}\n{}\n{}",
+                error_count,
+                across,
+                failed_in,
+                only,
+                opts.separator(),
+                limit_context(first, opts.context)
+            );
+        }
+    }
+
+    let mut result = String::new();
+    if opts.errors_only {
+        result.push_str(&format!(
+            "cargo build: {} errors{}{} ({} crates){}{}\n",
+            error_count, across, failed_in, compiled, hidden, only
+        ));
+    } else if opts.warnings_only {
+        result.push_str(&format!(
+            "cargo build: {} warnings ({} crates){}{}\n",
+            warnings, compiled, hidden, only
+        ));
+    } else {
+        result.push_str(&format!(
+            "cargo build: {} errors{}, {} warnings{} ({} crates){}\n",
+            error_count, across, warnings, failed_in, compiled, only
+        ));
+    }
+    if !opts.errors_only {
+        if let Some(by_crate) = format_warnings_by_crate(&mut warnings_by_crate) {
+            result.push_str(&by_crate);
+            result.push('\n');
+        }
+        if !categories.is_empty() {
+            result.push_str(&format!(
+                "Warning kinds: {}\n",
+                format_rule_counts(&categories)
+            ));
+        }
+    }
+    for artifact in &artifacts {
+        result.push_str(artifact);
+        result.push('\n');
+    }
+    result.push_str(opts.separator());
+    result.push('\n');
+    result.push_str(&render_blocks(&errors, opts));
+    if !kept_lines.is_empty() {
+        result.push_str("\n\n");
+        result.push_str(&kept_lines.join("\n"));
+    }
+
+    result.trim().to_string()
+}
+
+/// ` across N files`: distinct files in the first `-->` span of each error block.
+/// Errors without a span (linker, manifest) don't count; empty when none have one.
+fn format_error_files(blocks: &[String]) -> String {
+    let mut files: Vec<&str> = Vec::new();
+    for block in blocks.iter().filter(|b| b.starts_with("error")) {
+        if let Some(file) = block_file(block) {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    match files.len() {
+        0 => String::new(),
+        1 => " across 1 file".to_string(),
+        n => format!(" across {} files", n),
+    }
+}
+
+/// Target kind a source file belongs to, by cargo's conventional layout
+fn target_kind(file: &str) -> &'static str {
+    let parts: Vec<&str> = file.split(['/', '\\']).collect();
+    // `tests/` etc. count above `src/` only: `src/tests/` is a module of the lib
+    let top = parts
+        .iter()
+        .position(|p| *p == "src")
+        .unwrap_or(parts.len() - 1);
+    let dir = |name: &str| parts[..top].contains(&name);
+    if dir("tests") {
+        "test"
+    } else if dir("examples") {
+        "example"
+    } else if dir("benches") {
+        "bench"
+    } else if parts.last() == Some(&"build.rs") {
+        "build script"
+    } else if parts.ends_with(&["src", "main.rs"]) || parts.windows(2).any(|w| w == ["src", "bin"])
+    {
+        "bin"
+    } else {
+        "lib"
+    }
+}
+
+/// With diagnostics in more than one kind of target (`--all-targets`), add
+/// `= target: example` under each block's location so it's clear which one to fix
+fn annotate_targets(blocks: &mut [String]) {
+    let kinds: Vec<Option<&'static str>> = blocks
+        .iter()
+        .map(|block| block_file(block).map(target_kind))
+        .collect();
+    let mut distinct: Vec<&str> = kinds.iter().flatten().copied().collect();
+    distinct.sort();
+    distinct.dedup();
+    if distinct.len() < 2 {
+        return;
+    }
+
+    for (block, kind) in blocks.iter_mut().zip(kinds) {
+        let Some(kind) = kind else { continue };
+        let mut lines: Vec<String> = block.lines().map(str::to_string).collect();
+        if let Some(at) = lines
+            .iter()
+            .position(|line| line.trim_start().starts_with("--> "))
+        {
+            lines.insert(at + 1, format!("  = target: {}", kind));
+            *block = lines.join("\n");
+        }
+    }
+}
+
+/// File of a diagnostic block's first `--> file:line:col` span
+fn block_file(block: &str) -> Option<&str> {
+    block
+        .lines()
+        .find_map(|line| line.trim_start().strip_prefix("--> "))
+        .and_then(span_file)
+}
+
+/// (`foo`, 12) from `warning: `foo` (lib) generated 12 warnings (run `cargo fix` ...)`
+pub(crate) fn crate_warning_summary(line: &str) -> Option<(String, usize)> {
+    static SUMMARY_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let summary_re = SUMMARY_RE.get_or_init(|| {
+        regex::Regex::new(r"^warning: `([^`]+)`(?: \([^)]*\))? generated (\d+) warnings?")
+            .expect("invalid warning summary regex")
+    });
+    let caps = summary_re.captures(line)?;
+    Some((caps[1].to_string(), caps[2].parse().ok()?))
+}
+
+/// `Warnings by crate: foo (12), bar (4)`, worst first; only worth a line with 2+ crates
+fn format_warnings_by_crate(tally: &mut [(String, usize)]) -> Option<String> {
+    if tally.len() < 2 {
+        return None;
+    }
+    tally.sort_by_key(|t| std::cmp::Reverse(t.1));
+    let parts: Vec<String> = tally
+        .iter()
+        .map(|(name, count)| format!("{} ({})", name, count))
+        .collect();
+    Some(format!("Warnings by crate: {}", parts.join(", ")))
+}
+
+/// (`src/foo.rs:10:5`, `error[E0308]: mismatched types`) from a `--message-format short` line
+fn short_diagnostic(line: &str) -> Option<(&str, &str)> {
+    static SHORT_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let short_re = SHORT_RE.get_or_init(|| {
+        regex::Regex::new(r"^(\S[^:]*:\d+:\d+): ((?:error|warning)(?:\[[A-Za-z0-9_:]+\])?: .*)$")
+            .expect("invalid short diagnostic regex")
+    });
+    let caps = short_re.captures(line)?;
+    Some((caps.get(1)?.as_str(), caps.get(2)?.as_str()))
+}
+
+/// `hint: try `cargo add foo`` for unresolved-import / missing-crate errors (E0432, E0433, E0463)
+fn missing_crate_hint(block: &str) -> Option<String> {
+    static CRATE_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let crate_re = CRATE_RE.get_or_init(|| {
+        regex::Regex::new(
+            r"(?:undeclared crate or module|unlinked crate|can't find crate for|you might be missing crate) `([A-Za-z0-9_-]+)`",
+        )
+        .expect("invalid missing crate regex")
+    });
+
+    let header = block.lines().next()?;
+    if !["error[E0432]", "error[E0433]", "error[E0463]"]
+        .iter()
+        .any(|code| header.starts_with(code))
+    {
+        return None;
+    }
+    // Newer rustc already suggests `cargo add` itself
+    if block.contains("cargo add") {
+        return None;
+    }
+
+    let name = crate_re.captures(block)?.get(1)?.as_str();
+    if matches!(name, "std" | "core" | "alloc" | "crate" | "self" | "super") {
+        return None;
+    }
+    Some(format!("hint: try `cargo add {}`", name))
+}
+
+/// `hint: enable feature `X`` when rustc says the item is gated behind a cargo feature
+fn feature_hint(block: &str) -> Option<String> {
+    static FEATURE_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let feature_re = FEATURE_RE.get_or_init(|| {
+        regex::Regex::new(r"(?:only available with|gated behind) the `([^`]+)` feature")
+            .expect("invalid feature regex")
+    });
+
+    let feature = feature_re.captures(block)?.get(1)?.as_str();
+    Some(format!("hint: enable feature `{}`", feature))
+}
+
+/// `error: proc-macro derive panicked` + `= help: message: boom` becomes
+/// `error: PROC-MACRO PANIC (derive): boom`, keeping the invocation's `-->` and snippet
+fn label_proc_macro_panic(block: &str) -> Option<String> {
+    let header = block.lines().next()?;
+    let kind = header
+        .strip_prefix("error: ")?
+        .strip_suffix(" panicked")?
+        .trim_start_matches("proc-macro")
+        .trim_start_matches("proc macro")
+        .trim();
+    // "proc-macro derive", "proc macro", "custom attribute"
+    if !(header.contains("proc-macro")
+        || header.contains("proc macro")
+        || kind == "custom attribute")
+    {
+        return None;
+    }
+
+    let mut message = None;
+    let mut rest = Vec::new();
+    for line in block.lines().skip(1) {
+        match line.trim_start().strip_prefix("= help: message: ") {
+            Some(msg) => message = Some(msg.trim()),
+            None => rest.push(line),
+        }
+    }
+    // The trailing bare `|` left behind by the removed help line
+    while rest.last().is_some_and(|l| l.trim() == "|") {
+        rest.pop();
+    }
+
+    let mut label = "error: PROC-MACRO PANIC".to_string();
+    if !kind.is_empty() {
+        label.push_str(&format!(" ({})", kind));
+    }
+    if let Some(message) = message {
+        label.push_str(&format!(": {}", message));
+    }
+    rest.insert(0, &label);
+    Some(rest.join("\n"))
+}
+
+/// Collapse a `error: linking with ... failed` block into symbol/library counts plus a hint
+fn summarize_linker_error(block: &str) -> String {
+    static SYMBOL_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static LIB_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let symbol_re = SYMBOL_RE.get_or_init(|| {
+        // GNU ld: undefined reference to `foo'; lld: undefined symbol: foo; ld64: "_foo", referenced from:
+        regex::Regex::new(
+            r#"undefined reference to [`']([^'`]+)'|undefined symbol: (\S+)|"([^"]+)", referenced from"#,
+        )
+        .expect("invalid linker symbol regex")
+    });
+    let lib_re = LIB_RE.get_or_init(|| {
+        regex::Regex::new(
+            r"cannot find -l([\w.+-]+)|library not found for -l([\w.+-]+)|unable to find library -l([\w.+-]+)|could not find native static library `([^`]+)`",
+        )
+        .expect("invalid linker library regex")
+    });
+
+    let first_match = |caps: &regex::Captures| {
+        caps.iter()
+            .skip(1)
+            .flatten()
+            .next()
+            .map(|m| m.as_str().to_string())
+    };
+
+    let mut symbols: Vec<String> = Vec::new();
+    for caps in symbol_re.captures_iter(block) {
+        if let Some(symbol) = first_match(&caps) {
+            if !symbols.contains(&symbol) {
+                symbols.push(symbol);
+            }
+        }
+    }
+    let mut libs: Vec<String> = Vec::new();
+    for caps in lib_re.captures_iter(block) {
+        if let Some(lib) = first_match(&caps) {
+            if !libs.contains(&lib) {
+                libs.push(lib);
+            }
+        }
+    }
+
+    let header = block.lines().next().unwrap_or("error: linking failed");
+    let mut result = header.to_string();
+    if !symbols.is_empty() {
+        let shown: Vec<&str> = symbols.iter().take(5).map(String::as_str).collect();
+        let more = if symbols.len() > 5 {
+            format!(" (+{} more)", symbols.len() - 5)
+        } else {
+            String::new()
+        };
+        result.push_str(&format!(
+            "\n  {} undefined symbol{}: {}{}",
+            symbols.len(),
+            if symbols.len() == 1 { "" } else { "s" },
+            shown.join(", "),
+            more
+        ));
+    }
+    if !libs.is_empty() {
+        result.push_str(&format!("\n  missing libraries: {}", libs.join(", ")));
+    }
+    result.push_str(
+        "\n  hint: a system library is missing or not on the linker path \
+(install its -dev package), or a crate feature that bundles/vendors it is off",
+    );
+    result
+}
+
+/// Warning blocks grouped by lint, most frequent first.
+///
+/// rustc names the lint only in the `#[warn(lint)]` note of its first occurrence, so
+/// messages (with backticked names blanked) seen next to that note inherit the lint name.
+fn warning_categories(blocks: &[String]) -> Vec<(String, usize)> {
+    static LINT_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static NAME_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let lint_re = LINT_RE.get_or_init(|| {
+        regex::Regex::new(r"#\[(?:warn|deny|forbid)\(([\w:]+)\)\]").expect("invalid lint regex")
+    });
+    let name_re =
+        NAME_RE.get_or_init(|| regex::Regex::new(r"`[^`]*`").expect("invalid name regex"));
+
+    let warnings: Vec<(String, Option<String>)> = blocks
+        .iter()
+        .filter(|block| block.starts_with("warning"))
+        .map(|block| {
+            let header = block.lines().next().unwrap_or("");
+            let rule = name_re
+                .replace_all(&diagnostic_rule(header), "`_`")
+                .to_string();
+            let lint = lint_re.captures(block).map(|caps| caps[1].to_string());
+            (rule, lint)
+        })
+        .collect();
+    let lint_for_rule: HashMap<&str, &str> = warnings
+        .iter()
+        .filter_map(|(rule, lint)| Some((rule.as_str(), lint.as_deref()?)))
+        .collect();
+
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for (rule, _) in &warnings {
+        let name = lint_for_rule.get(rule.as_str()).copied().unwrap_or(rule);
+        match counts.iter_mut().find(|(n, _)| n == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name.to_string(), 1)),
+        }
+    }
+    counts.sort_by_key(|c| std::cmp::Reverse(c.1));
+    counts
+}
+
+/// `unused_variables (6x), dead_code (3x)`: the five most frequent, `+N more` after
+fn format_rule_counts(counts: &[(String, usize)]) -> String {
+    let mut parts: Vec<String> = counts
+        .iter()
+        .take(5)
+        .map(|(rule, n)| format!("{} ({}x)", rule, n))
+        .collect();
+    if counts.len() > 5 {
+        parts.push(format!("+{} more", counts.len() - 5));
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cargo_cmd::keep_patterns;
+    use crate::utils::Theme;
+
+    #[test]
+    fn test_filter_cargo_build_success() {
+        let output = r#"   Compiling libc v0.2.153
+   Compiling cfg-if v1.0.0
+   Compiling rtk v0.5.0
+    Finished dev [unoptimized + debuginfo] target(s) in 15.23s
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(result.contains("✓ cargo build"));
+        assert!(result.contains("3 crates compiled"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_errors() {
+        let output = r#"   Compiling rtk v0.5.0
+error[E0308]: mismatched types
+ --> src/main.rs:10:5
+  |
+10|     "hello"
+  |     ^^^^^^^ expected `i32`, found `&str`
+
+error: aborting due to 1 previous error
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(result.contains("1 errors"));
+        assert!(result.contains("E0308"));
+        assert!(result.contains("mismatched types"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_keeps_timings_report() {
+        let output = r#"   Compiling rtk v0.5.0
+    Finished `dev` profile [unoptimized + debuginfo] target(s) in 15.23s
+      Timing report saved to target/cargo-timings/cargo-timing.html
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert_eq!(
+            result,
+            "✓ cargo build (1 crates compiled)\n✓ build timings: target/cargo-timings/cargo-timing.html"
+        );
+
+        let failing = format!(
+            "error: expected item, found `}}`\n --> src/lib.rs:7:1\n{}",
+            output
+        );
+        let result = filter_cargo_build(&failing, &FilterOptions::default());
+        assert!(result
+            .contains("(1 crates)\n✓ build timings: target/cargo-timings/cargo-timing.html\n═"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_short_message_format() {
+        let output = r#"   Compiling rtk v0.5.0
+src/main.rs:3:9: warning: unused variable: `x`
+src/main.rs:10:5: error[E0308]: mismatched types
+src/lib.rs:7:1: error: expected item, found `}`
+error: could not compile `rtk` (bin "rtk") due to 2 previous errors; 1 warning emitted
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(result.contains("2 errors"), "{}", result);
+        assert!(result.contains("across 2 files"));
+        assert!(result.contains("error[E0308]: mismatched types\n  --> src/main.rs:10:5"));
+        assert!(result.contains("error: expected item, found `}`\n  --> src/lib.rs:7:1"));
+        assert!(result.contains("Warning kinds: unused variable: `_` (1x)"));
+        assert!(!result.contains("src/main.rs:3:9"));
+        assert_eq!(
+            short_diagnostic("src/main.rs:3:9: warning: unused variable: `x`"),
+            Some(("src/main.rs:3:9", "warning: unused variable: `x`"))
+        );
+        assert_eq!(short_diagnostic("error[E0308]: mismatched types"), None);
+    }
+
+    #[test]
+    fn test_filter_cargo_build_respects_max_errors() {
+        let mut output = String::from("   Compiling rtk v0.5.0\n");
+        for i in 0..5 {
+            output.push_str(&format!(
+                "error[E0308]: mismatched types {}\n --> src/main.rs:{}:5\n  |\n  = note: x\n\n",
+                i, i
+            ));
+        }
+        let opts = FilterOptions {
+            max_errors: 2,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(&output, &opts);
+        assert!(result.contains("mismatched types 1"));
+        assert!(!result.contains("mismatched types 2"));
+        assert!(result.contains("... +3 more issues"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_keep_survives_cap_and_warning_drop() {
+        let mut output = String::from("   Compiling rtk v0.5.0\n");
+        for i in 0..4 {
+            output.push_str(&format!(
+                "error[E0308]: mismatched types {}\n --> src/main.rs:{}:5\n  |\n  = note: x\n\n",
+                i, i
+            ));
+        }
+        output.push_str("warning: use of deprecated function `old`\n --> src/lib.rs:3:5\n  |\n  = note: `#[warn(deprecated)]` on by default\n\n");
+        output.push_str("warning: unused variable: `x`\n --> src/lib.rs:9:9\n  |\n  = note: x\n\n");
+        let opts = FilterOptions {
+            max_errors: 1,
+            keep: keep_patterns(&[
+                "re:mismatched types 3".to_string(),
+                "deprecated".to_string(),
+            ]),
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(&output, &opts);
+        assert!(result.contains("mismatched types 0"));
+        assert!(!result.contains("mismatched types 1"));
+        assert!(result.contains("mismatched types 3"));
+        assert!(result.contains("use of deprecated function"));
+        assert!(!result.contains("unused variable: `x`"));
+        assert!(result.contains("... +2 more issues"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_warnings_by_crate() {
+        let output = r#"   Compiling core v0.1.0
+   Compiling app v0.1.0
+warning: unused variable: `x`
+ --> core/src/lib.rs:2:9
+  |
+2 |     let x = 1;
+  |         ^
+
+warning: `core` (lib) generated 1 warning
+warning: `app` (bin "app") generated 12 warnings (run `cargo fix --bin "app"` to apply 3 suggestions)
+warning: `core` (lib test) generated 3 warnings (1 duplicate)
+    Finished dev [unoptimized + debuginfo] target(s) in 1.2s
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result.contains("\nWarnings by crate: app (12), core (4)\n"),
+            "got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_build_warning_kinds() {
+        let output = r#"   Compiling app v0.1.0
+warning: unused variable: `a`
+ --> src/main.rs:2:9
+  |
+  = note: `#[warn(unused_variables)]` on by default
+
+warning: unused variable: `b`
+ --> src/main.rs:3:9
+  |
+  = help: if this is intentional, prefix it with an underscore: `_b`
+
+warning: function `helper` is never used
+ --> src/main.rs:8:4
+  |
+  = note: `#[warn(dead_code)]` on by default
+
+warning: unused variable: `c`
+ --> src/lib.rs:4:9
+  |
+  = help: if this is intentional, prefix it with an underscore: `_c`
+
+warning: `app` (bin "app") generated 4 warnings
+    Finished dev [unoptimized + debuginfo] target(s) in 0.5s
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result.contains("\nWarning kinds: unused_variables (3x), dead_code (1x)\n"),
+            "got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_build_proc_macro_panic() {
+        let output = r#"   Compiling app v0.1.0
+error: proc-macro derive panicked
+ --> src/main.rs:3:10
+  |
+3 | #[derive(Builder)]
+  |          ^^^^^^^
+  |
+  = help: message: field `id` has no type
+
+error[E0308]: mismatched types
+ --> src/main.rs:9:18
+  |
+9 |     let x: u8 = "a";
+  |                 ^^^ expected `u8`, found `&str`
+
+error: could not compile `app` (bin "app") due to 2 previous errors
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result.contains(
+                "error: PROC-MACRO PANIC (derive): field `id` has no type\n --> src/main.rs:3:10\n  |\n3 | #[derive(Builder)]\n  |          ^^^^^^^\n"
+            ),
+            "got: {}",
+            result
+        );
+        assert!(!result.contains("= help: message"));
+        assert!(result.starts_with("cargo build: 2 errors"));
+    }
+
+    #[test]
+    fn test_label_proc_macro_panic() {
+        assert_eq!(
+            label_proc_macro_panic(
+                "error: custom attribute panicked\n --> src/lib.rs:1:1\n  = help: message: boom"
+            )
+            .as_deref(),
+            Some("error: PROC-MACRO PANIC (custom attribute): boom\n --> src/lib.rs:1:1")
+        );
+        assert_eq!(
+            label_proc_macro_panic("error: proc macro panicked\n --> src/lib.rs:2:5").as_deref(),
+            Some("error: PROC-MACRO PANIC\n --> src/lib.rs:2:5")
+        );
+        assert_eq!(
+            label_proc_macro_panic("error: thread 'main' panicked"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_rule() {
+        assert_eq!(
+            diagnostic_rule("warning: redundant clone [clippy::redundant_clone]"),
+            "clippy::redundant_clone"
+        );
+        assert_eq!(
+            diagnostic_rule("warning: unused import: `std::fs`"),
+            "unused import: `std::fs`"
+        );
+        assert_eq!(
+            format_rule_counts(&[("a".to_string(), 2), ("b".to_string(), 1)]),
+            "a (2x), b (1x)"
+        );
+    }
+
+    #[test]
+    fn test_crate_warning_summary() {
+        assert_eq!(
+            crate_warning_summary("warning: `foo` (lib) generated 1 warning"),
+            Some(("foo".to_string(), 1))
+        );
+        assert_eq!(
+            crate_warning_summary("warning: `foo-bar` generated 7 warnings"),
+            Some(("foo-bar".to_string(), 7))
+        );
+        assert_eq!(crate_warning_summary("warning: unused import"), None);
+        assert_eq!(
+            format_warnings_by_crate(&mut [("foo".to_string(), 3)]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_build_context_limit() {
+        let output = r#"   Compiling app v0.1.0 (/home/user/app)
+error[E0308]: mismatched types
+ --> src/main.rs:4:18
+  |
+4 |     let x: i32 = "hello";
+  |            ---   ^^^^^^^ expected `i32`, found `&str`
+  |            |
+  |            expected due to this
+  = note: expected type `i32`
+
+error: could not compile `app` (bin "app") due to 1 previous error
+"#;
+        let full = filter_cargo_build(output, &FilterOptions::default());
+        assert!(full.contains("expected due to this"));
+
+        let opts = FilterOptions {
+            context: Some(2),
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(output, &opts);
+        assert!(
+            result.contains("4 |     let x: i32 = \"hello\";"),
+            "got: {}",
+            result
+        );
+        assert!(!result.contains("expected due to this"), "got: {}", result);
+        assert!(result.contains("= note: expected type `i32`"));
+
+        let opts = FilterOptions {
+            context: Some(0),
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(output, &opts);
+        assert!(result.ends_with(
+            "error[E0308]: mismatched types\n --> src/main.rs:4:18\n  = note: expected type `i32`"
+        ), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_check_all_targets_attribution() {
+        let output = r#"    Checking app v0.1.0
+error[E0425]: cannot find value `cfg` in this scope
+ --> examples/demo.rs:4:13
+  |
+4 |     run(cfg);
+  |         ^^^ not found in this scope
+
+error[E0308]: mismatched types
+ --> src/lib.rs:8:18
+  |
+8 |     let n: u32 = "1";
+  |            ---   ^^^ expected `u32`, found `&str`
+
+error: could not compile `app` (example "demo") due to 1 previous error
+error: could not compile `app` (lib) due to 1 previous error
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result.contains(" --> examples/demo.rs:4:13\n  = target: example\n"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains(" --> src/lib.rs:8:18\n  = target: lib\n"));
+
+        // One kind of target: nothing to disambiguate
+        let lib_only = output.replace("examples/demo.rs", "src/demo.rs");
+        assert!(!filter_cargo_build(&lib_only, &FilterOptions::default()).contains("= target:"));
+    }
+
+    #[test]
+    fn test_target_kind() {
+        assert_eq!(target_kind("tests/api.rs"), "test");
+        assert_eq!(target_kind("crates/core/examples/demo.rs"), "example");
+        assert_eq!(target_kind("benches/parse.rs"), "bench");
+        assert_eq!(target_kind("build.rs"), "build script");
+        assert_eq!(target_kind("src/main.rs"), "bin");
+        assert_eq!(target_kind("src/bin/tool.rs"), "bin");
+        assert_eq!(target_kind("src/tests/helpers.rs"), "lib");
+        assert_eq!(target_kind("src/lib.rs"), "lib");
+    }
+
+    #[test]
+    fn test_filter_cargo_build_only_glob() {
+        let output = r#"   Compiling app v0.1.0
+error[E0425]: cannot find value `x` in this scope
+ --> src/parser/lexer.rs:3:5
+  |
+3 |     x
+  |     ^ not found in this scope
+
+error[E0308]: mismatched types
+ --> src/main.rs:8:18
+  |
+8 |     let n: u32 = "1";
+  |            ---   ^^^ expected `u32`, found `&str`
+
+error[E0599]: no method named `peek` found for struct `Lexer`
+ --> src/parser/mod.rs:12:10
+  |
+12|     lexer.peek()
+  |           ^^^^ method not found in `Lexer`
+
+error: could not compile `app` (bin "app") due to 3 previous errors
+"#;
+        let opts = FilterOptions {
+            only: Some("src/parser/*".to_string()),
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(output, &opts);
+        assert!(
+            result.starts_with(
+                "cargo build: 3 errors across 3 files, 0 warnings in `app` (1 crates) (filtered to src/parser/*: showing 2 of 3)\n"
+            ),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("src/parser/lexer.rs:3:5"));
+        assert!(result.contains("src/parser/mod.rs:12:10"));
+        assert!(!result.contains("mismatched types"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_errors_only() {
+        let output = r#"   Compiling app v0.1.0 (/home/user/app)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+
+warning: unused import: `std::fs`
+ --> src/main.rs:1:5
+  |
+1 | use std::fs;
+  |     ^^^^^^^
+
+error[E0308]: mismatched types
+ --> src/main.rs:4:18
+  |
+4 |     let y: i32 = "hello";
+  |                  ^^^^^^^ expected `i32`, found `&str`
+
+warning: `app` (bin "app") generated 2 warnings
+error: could not compile `app` (bin "app") due to 1 previous error; 2 warnings emitted
+"#;
+        let opts = FilterOptions {
+            errors_only: true,
+            max_errors: 1,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(output, &opts);
+        assert!(
+            result.starts_with(
+                "cargo build: 1 errors across 1 file in `app` (1 crates) (2 warnings hidden)"
+            ),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("mismatched types"));
+        assert!(!result.contains("unused"), "got: {}", result);
+        assert!(!result.contains("more issues"));
+
+        let opts = FilterOptions {
+            warnings_only: true,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(output, &opts);
+        assert!(result.starts_with("cargo build: 2 warnings (1 crates) (1 errors hidden)"));
+        assert!(result.contains("unused import"));
+        assert!(!result.contains("mismatched types"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_errors_before_warnings() {
+        let mut output = String::from("   Compiling app v0.1.0\n");
+        for i in 0..3 {
+            output.push_str(&format!(
+                "warning: unused variable: `w{}`\n --> src/main.rs:{}:9\n  |\n  = note: x\n\n",
+                i, i
+            ));
+        }
+        output.push_str(
+            "error[E0308]: mismatched types\n --> src/main.rs:9:18\n  |\n  = note: y\n\n",
+        );
+
+        let opts = FilterOptions {
+            max_errors: 1,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(&output, &opts);
+        assert!(result.starts_with("cargo build: 1 errors across 1 file, 3 warnings (1 crates)"));
+        assert!(result.contains("mismatched types"), "got: {}", result);
+        assert!(!result.contains("unused variable: `w0`"));
+        assert!(result.contains("Warning kinds: unused variable: `_` (3x)"));
+        assert!(!result.contains("more issues"));
+
+        let opts = FilterOptions {
+            with_warnings: true,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(&output, &opts);
+        assert!(result.contains("unused variable: `w0`"));
+        assert!(result.contains("mismatched types"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_names_failing_crates() {
+        let output = r#"   Compiling core v0.1.0 (/ws/core)
+   Compiling cli v0.1.0 (/ws/cli)
+error[E0308]: mismatched types
+ --> core/src/lib.rs:4:18
+  |
+4 |     let x: i32 = "a";
+  |                  ^^^ expected `i32`, found `&str`
+
+error: could not compile `core` (lib) due to 1 previous error
+error: could not compile `core` (lib test) due to 1 previous error
+error[E0425]: cannot find value `y` in this scope
+ --> cli/src/main.rs:2:5
+  |
+2 |     y
+  |     ^ not found in this scope
+
+error: aborting due to 1 previous error
+error: could not compile `cli` (bin "cli") due to 1 previous error
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result.starts_with(
+                "cargo build: 2 errors across 2 files, 0 warnings in `core`, `cli` (2 crates)"
+            ),
+            "got: {}",
+            result
+        );
+        assert!(!result.contains("could not compile"));
+        assert!(!result.contains("aborting due to"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_first_error_only() {
+        let mut output = String::from("   Compiling app v0.1.0\n");
+        output
+            .push_str("warning: unused variable: `w`\n --> src/main.rs:1:9\n  |\n  = note: w\n\n");
+        for i in 0..3 {
+            output.push_str(&format!(
+                "error[E0308]: mismatched types {}\n --> src/main.rs:{}:5\n  |\n  = note: x\n\n",
+                i,
+                i + 2
+            ));
+        }
+        output.push_str("error: could not compile `app` (bin \"app\") due to 3 previous errors\n");
+
+        let opts = FilterOptions {
+            first: true,
+            with_warnings: true,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(&output, &opts);
+        assert_eq!(
+            result,
+            "cargo build: 3 errors across 1 file in `app` (showing first)\n═══════════════════════════════════════\nerror[E0308]: mismatched types 0\n --> src/main.rs:2:5\n  |\n  = note: x"
+        );
+    }
+
+    #[test]
+    fn test_format_error_files_counts_distinct_spans() {
+        let blocks = vec![
+            "error[E0308]: mismatched types\n --> src/a.rs:1:5".to_string(),
+            "error[E0425]: cannot find value\n --> src/a.rs:9:1\n  ::: src/c.rs:3:1".to_string(),
+            "error[E0599]: no method\n   --> src/b.rs:4:2".to_string(),
+            "warning: unused\n --> src/d.rs:1:1".to_string(),
+            "error: linking with `cc` failed: exit status: 1".to_string(),
+        ];
+        assert_eq!(format_error_files(&blocks), " across 2 files");
+        assert_eq!(format_error_files(&blocks[4..]), "");
+    }
+
+    #[test]
+    fn test_filter_cargo_build_errors_only_clean() {
+        let output = "   Compiling app v0.1.0\nwarning: unused variable: `x`\n --> src/main.rs:2:9\n  |\n2 |     let x = 5;\n  |         ^\n\n    Finished dev [unoptimized] target(s) in 1.0s\n";
+        let opts = FilterOptions {
+            errors_only: true,
+            ..FilterOptions::default()
+        };
+        assert_eq!(
+            filter_cargo_build(output, &opts),
+            "✓ cargo build (1 crates compiled) (1 warnings hidden)"
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_build_strips_sccache() {
+        let output = r#"sccache: Starting the server...
+   Compiling app v0.1.0 (/home/user/app)
+    Finished `dev` profile [unoptimized + debuginfo] target(s) in 2.10s
+Compile requests                     20
+Compile requests executed            20
+Cache hits                           18
+Cache hits (Rust)                    18
+Cache misses                          2
+Cache misses (Rust)                   2
+Cache timeouts                        0
+Non-cacheable compilations            0
+Average cache read hit            0.010 s
+Cache location                  Local disk: "/home/user/.cache/sccache"
+Version (client)                0.8.1
+"#;
+        assert_eq!(
+            filter_cargo_build(output, &FilterOptions::default()),
+            "✓ cargo build (1 crates compiled)\nsccache: 90% cache hit"
+        );
+        assert!(!is_noise_line("sccache: error: Server startup failed"));
+    }
+
+    #[test]
+    fn test_sccache_hit_rate_row() {
+        let output = "Cache hits                           3\nCache misses                         1\nCache hits rate                  75.00 %\n";
+        assert_eq!(sccache_hit_rate(output), Some(75.0));
+        assert_eq!(sccache_hit_rate("    Finished dev"), None);
+    }
+
+    #[test]
+    fn test_filter_cargo_build_ascii_theme() {
+        let output = "   Compiling rtk v0.5.0\n    Finished dev [unoptimized] target(s) in 1.0s\n";
+        let opts = FilterOptions {
+            theme: Theme::ascii(),
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_build(output, &opts);
+        assert_eq!(result, "OK cargo build (1 crates compiled)");
+    }
+
+    #[test]
+    fn test_filter_cargo_build_skips_locking() {
+        let output =
+            "     Locking 3 packages to latest compatible versions\n   Compiling rtk v0.5.0\n";
+        assert_eq!(
+            filter_cargo_build(output, &FilterOptions::default()),
+            "✓ cargo build (1 crates compiled)"
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_build_linker_error() {
+        let output = r#"   Compiling app v0.1.0 (/home/user/app)
+error: linking with `cc` failed: exit status: 1
+  |
+  = note: LC_ALL="C" PATH="/usr/bin" "cc" "-m64" "/tmp/rustcXYZ/symbols.o" "app.0.rcgu.o" "-lssl" "-lcrypto" "-nodefaultlibs"
+  = note: /usr/bin/ld: app.0.rcgu.o: in function `app::main':
+          app.0.rcgu.o:(.text+0x12): undefined reference to `SSL_new'
+          /usr/bin/ld: app.0.rcgu.o:(.text+0x40): undefined reference to `SSL_free'
+          /usr/bin/ld: app.0.rcgu.o:(.text+0x52): undefined reference to `SSL_new'
+          /usr/bin/ld: cannot find -lssl: No such file or directory
+          collect2: error: ld returned 1 exit status
+  = note: some `extern` functions couldn't be found; some native libraries may need to be installed or have their path specified
+
+error: could not compile `app` (bin "app") due to 1 previous error
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result.starts_with("cargo build: 1 errors"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("error: linking with `cc` failed: exit status: 1"));
+        assert!(
+            result.contains("2 undefined symbols: SSL_new, SSL_free"),
+            "got: {}",
+            result
+        );
+        assert!(
+            result.contains("missing libraries: ssl\n"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("hint:"));
+        assert!(!result.contains("LC_ALL"));
+    }
+
+    #[test]
+    fn test_filter_cargo_build_missing_crate_hint() {
+        let output = r#"   Compiling app v0.1.0 (/home/user/app)
+error[E0432]: unresolved import `serde`
+ --> src/main.rs:1:5
+  |
+1 | use serde::Serialize;
+  |     ^^^^^ use of undeclared crate or module `serde`
+
+error[E0463]: can't find crate for `rand`
+ --> src/main.rs:2:1
+  |
+2 | extern crate rand;
+  | ^^^^^^^^^^^^^^^^^^ can't find crate
+
+error[E0432]: unresolved import `crate::missing`
+ --> src/main.rs:3:5
+  |
+3 | use crate::missing;
+  |     ^^^^^^^^^^^^^^ no `missing` in the root
+
+error: could not compile `app` (bin "app") due to 3 previous errors
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result
+                .contains("use of undeclared crate or module `serde`\nhint: try `cargo add serde`"),
+            "got: {}",
+            result
+        );
+        assert!(
+            result.contains("hint: try `cargo add rand`"),
+            "got: {}",
+            result
+        );
+        assert_eq!(result.matches("hint:").count(), 2, "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_build_feature_hint() {
+        let output = r#"   Compiling app v0.1.0 (/home/user/app)
+error[E0432]: unresolved import `tokio::fs`
+ --> src/main.rs:1:5
+  |
+1 | use tokio::fs;
+  |     ^^^^^^^^^ no `fs` in the root
+  |
+note: found an item that was configured out
+ --> /home/user/.cargo/registry/src/tokio-1.38.0/src/lib.rs:464:13
+  = note: the item is gated behind the `fs` feature
+
+error: could not compile `app` (bin "app") due to 1 previous error
+"#;
+        let result = filter_cargo_build(output, &FilterOptions::default());
+        assert!(
+            result
+                .contains("error[E0432]: unresolved import `tokio::fs`\nhint: enable feature `fs`"),
+            "got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_feature_hint_only_available_with() {
+        let block = "error[E0599]: no method named `par_iter` found\n  = help: `par_iter` is only available with the `rayon` feature";
+        assert_eq!(
+            feature_hint(block),
+            Some("hint: enable feature `rayon`".to_string())
+        );
+        assert_eq!(feature_hint("error[E0308]: mismatched types"), None);
+    }
+
+    #[test]
+    fn test_missing_crate_hint_skips_when_rustc_suggests() {
+        let block = "error[E0433]: failed to resolve: use of unresolved module or unlinked crate `tokio`\n  = help: if you wanted to use a crate named `tokio`, use `cargo add tokio` to add it to your `Cargo.toml`";
+        assert_eq!(missing_crate_hint(block), None);
+        let std_block = "error[E0463]: can't find crate for `std`";
+        assert_eq!(missing_crate_hint(std_block), None);
+    }
+
+    #[test]
+    fn test_summarize_linker_error_macos() {
+        let block = "error: linking with `cc` failed: exit status: 1\n  = note: Undefined symbols for architecture arm64:\n            \"_git_libgit2_init\", referenced from:\n          ld: library not found for -lgit2";
+        let result = summarize_linker_error(block);
+        assert!(
+            result.contains("1 undefined symbol: _git_libgit2_init"),
+            "got: {}",
+            result
+        );
+        assert!(
+            result.contains("missing libraries: git2"),
+            "got: {}",
+            result
+        );
+    }
+}
@@ -0,0 +1,531 @@
+use super::{diagnostic_rule, in_only, is_noise_line, only_note, span_file, split_sccache_stats};
+use crate::cargo_cmd::FilterOptions;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Why an `error:` lint is an error rather than a warning
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DenySource {
+    /// `-D warnings` (cargo flag or `RUSTFLAGS`) promoted a warn-level lint
+    Warnings,
+    /// `-D <lint>` on the command line, or a `#[deny]`/`#![deny]` attribute
+    Config,
+    /// The lint is deny-by-default (e.g. `clippy::correctness`)
+    Default,
+}
+
+/// Where a diagnostic's level came from, from the notes under it
+fn deny_source(line: &str) -> Option<DenySource> {
+    let line = line.trim_start();
+    if line.contains("implied by `-D warnings`") {
+        Some(DenySource::Warnings)
+    } else if line.contains("requested on the command line with `-D")
+        || line.contains("the lint level is defined here")
+    {
+        Some(DenySource::Config)
+    } else if line.contains("#[deny(") && line.contains("on by default") {
+        Some(DenySource::Default)
+    } else {
+        None
+    }
+}
+
+/// Counts of diagnostics by their effective level and, for denied lints, why
+#[derive(Debug, Default, PartialEq)]
+struct LevelCounts {
+    warn: usize,
+    /// Compiler errors that are not lints at all
+    error: usize,
+    deny_warnings: usize,
+    deny_config: usize,
+    deny_default: usize,
+}
+
+impl LevelCounts {
+    /// Count one finished diagnostic (`error` is its `error`/`warning` keyword)
+    fn add(&mut self, error: bool, source: Option<DenySource>) {
+        match (error, source) {
+            (false, _) => self.warn += 1,
+            (true, None) => self.error += 1,
+            (true, Some(DenySource::Warnings)) => self.deny_warnings += 1,
+            (true, Some(DenySource::Config)) => self.deny_config += 1,
+            (true, Some(DenySource::Default)) => self.deny_default += 1,
+        }
+    }
+
+    /// `by level: 3 denied by -D warnings, 1 error, 4 warn`; `None` without denied lints,
+    /// where the errors/warnings counts already say everything
+    fn format(&self) -> Option<String> {
+        if self.deny_warnings + self.deny_config + self.deny_default == 0 {
+            return None;
+        }
+        let parts: Vec<String> = [
+            (self.deny_warnings, "denied by -D warnings"),
+            (self.deny_config, "denied by config"),
+            (self.deny_default, "deny by default"),
+            (self.error, "error"),
+            (self.warn, "warn"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, label)| format!("{} {}", n, label))
+        .collect();
+        Some(format!("by level: {}", parts.join(", ")))
+    }
+}
+
+/// Filter cargo clippy output - group warnings by lint rule
+pub(crate) fn filter_cargo_clippy(output: &str, opts: &FilterOptions) -> String {
+    let (output, _) = split_sccache_stats(output);
+    let output = output.as_str();
+    let mut by_rule: HashMap<String, Vec<String>> = HashMap::new();
+    let mut error_count = 0;
+    let mut warning_count = 0;
+    let mut levels = LevelCounts::default();
+    // Keyword of the open diagnostic (true for `error`) and its deny source so far
+    let mut current: Option<(bool, Option<DenySource>)> = None;
+    // The note saying why a rule is denied, kept from its first denied occurrence
+    let mut deny_notes: HashMap<String, String> = HashMap::new();
+    // Diagnostics with a `-->` location, before `--only` drops any
+    let mut located = 0;
+    // `--suggest`: lines of the open diagnostic, and each rule's first suggested fix
+    let mut block: Vec<&str> = Vec::new();
+    let mut suggestions: HashMap<String, Suggestion> = HashMap::new();
+
+    // Parse clippy output lines
+    // Format: "warning: description\n  --> file:line:col\n  |\n  | code\n"
+    let mut current_rule = String::new();
+
+    for line in output.lines() {
+        // Skip compilation lines
+        if is_noise_line(line) {
+            continue;
+        }
+
+        // "warning: unused variable [unused_variables]" or "warning: description [clippy::rule_name]"
+        if (line.starts_with("warning:") || line.starts_with("warning["))
+            || (line.starts_with("error:") || line.starts_with("error["))
+        {
+            if let Some((error, source)) = current.take() {
+                levels.add(error, source);
+            }
+            if opts.suggest && !suggestions.contains_key(&current_rule) {
+                if let Some(suggestion) = clippy_suggestion(&block) {
+                    suggestions.insert(current_rule.clone(), suggestion);
+                }
+            }
+            block.clear();
+            // Skip summary lines: "warning: `rtk` (bin) generated 5 warnings"
+            if line.contains("generated") && line.contains("warning") {
+                continue;
+            }
+            // Skip "error: aborting" / "error: could not compile"
+            if line.contains("aborting due to") || line.contains("could not compile") {
+                continue;
+            }
+
+            let is_error = line.starts_with("error");
+            current = Some((is_error, None));
+            if is_error {
+                error_count += 1;
+            } else {
+                warning_count += 1;
+            }
+
+            current_rule = diagnostic_rule(line);
+        } else if let Some((error, source @ None)) = current.as_mut() {
+            *source = deny_source(line);
+            if *error && source.is_some() && !current_rule.is_empty() {
+                deny_notes
+                    .entry(current_rule.clone())
+                    .or_insert_with(|| line.trim_start().trim_start_matches("= ").to_string());
+            }
+        }
+
+        block.push(line);
+
+        if line.trim_start().starts_with("--> ") {
+            let location = line.trim_start().trim_start_matches("--> ").to_string();
+            if !current_rule.is_empty() {
+                located += 1;
+                if let Some(glob) = opts.only.as_deref() {
+                    if !in_only(glob, span_file(&location)) && !opts.keeps(&current_rule) {
+                        continue;
+                    }
+                }
+                by_rule
+                    .entry(current_rule.clone())
+                    .or_default()
+                    .push(location);
+            }
+        }
+    }
+
+    if let Some((error, source)) = current {
+        levels.add(error, source);
+    }
+    if opts.suggest && !suggestions.contains_key(&current_rule) {
+        if let Some(suggestion) = clippy_suggestion(&block) {
+            suggestions.insert(current_rule.clone(), suggestion);
+        }
+    }
+
+    if error_count == 0 && warning_count == 0 {
+        return format!("{} cargo clippy: No issues found", opts.check());
+    }
+
+    let only = opts.only.as_deref().map_or(String::new(), |glob| {
+        only_note(glob, by_rule.values().map(Vec::len).sum(), located)
+    });
+    let mut result = String::new();
+    result.push_str(&format!(
+        "cargo clippy: {} errors, {} warnings{}\n",
+        error_count, warning_count, only
+    ));
+    if let Some(levels) = levels.format() {
+        result.push_str(&levels);
+        result.push('\n');
+    }
+    result.push_str(opts.separator());
+    result.push('\n');
+
+    // Sort rules by frequency
+    let mut rule_counts: Vec<_> = by_rule.iter().collect();
+    rule_counts.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
+
+    // Rules matching a `keep` pattern are listed in full, past either cap
+    let mut omitted = 0;
+    for (i, (rule, locations)) in rule_counts.iter().enumerate() {
+        let kept = opts.keeps(rule);
+        if i >= 15 && !kept {
+            omitted += 1;
+            continue;
+        }
+        let max_locations = if kept { locations.len() } else { 3 };
+        result.push_str(&format!("  {} ({}x)\n", rule, locations.len()));
+        if let Some(note) = deny_notes.get(*rule) {
+            result.push_str(&format!("    {}\n", note));
+        }
+        for loc in locations.iter().take(max_locations) {
+            result.push_str(&format!("    {}\n", loc));
+        }
+        if locations.len() > max_locations {
+            result.push_str(&format!(
+                "    ... +{} more\n",
+                locations.len() - max_locations
+            ));
+        }
+        if let Some(suggestion) = suggestions.get(*rule) {
+            for line in &suggestion.before {
+                result.push_str(&format!("    - {}\n", line));
+            }
+            for line in &suggestion.after {
+                result.push_str(&format!("    + {}\n", line));
+            }
+        }
+    }
+
+    if omitted > 0 {
+        result.push_str(&format!("\n... +{} more rules\n", omitted));
+    }
+
+    result.trim().to_string()
+}
+
+/// A suggested fix: the flagged source lines and their replacement, trimmed
+#[derive(Debug, PartialEq)]
+struct Suggestion {
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+/// The suggested fix in one clippy diagnostic block, from either rendering rustc uses:
+/// a label on the span (`^^^^ help: try: `x.is_empty()``), spliced into the span line,
+/// or a `help:` section with `3 - old` / `3 + new` lines (or `3 | new` over `~~~`).
+fn clippy_suggestion(block: &[&str]) -> Option<Suggestion> {
+    static CODE_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static DIFF_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static LABEL_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let code_re = CODE_RE
+        .get_or_init(|| regex::Regex::new(r"^\s*\d+\s*\| ?(.*)$").expect("invalid code regex"));
+    let diff_re = DIFF_RE
+        .get_or_init(|| regex::Regex::new(r"^\s*\d+\s+([-+]) ?(.*)$").expect("invalid diff regex"));
+    let label_re = LABEL_RE.get_or_init(|| {
+        regex::Regex::new(r"^(\s*\|\s*)(\^+) help: .*`([^`]*)`$").expect("invalid label regex")
+    });
+    // `|` followed only by markers: `~~~` under replaced code, `+++` under inserted code
+    let is_marker = |line: &str| {
+        line.trim_start()
+            .strip_prefix('|')
+            .map(str::trim)
+            .is_some_and(|rest| {
+                !rest.is_empty() && rest.chars().all(|c| matches!(c, '~' | '+' | '-'))
+            })
+    };
+
+    let help = block.iter().position(|line| line.starts_with("help:"));
+    let span_end = help.unwrap_or(block.len());
+    let span = (0..span_end).find(|&i| code_re.is_match(block[i]))?;
+    let span_code = code_re.captures(block[span])?.get(1)?.as_str();
+
+    // Inline label: splice the suggestion over the underlined columns
+    if let Some(caps) = block.get(span + 1).and_then(|line| label_re.captures(line)) {
+        let start = caps[1].len().checked_sub(block[span + 1].find('|')? + 2)?;
+        let end = start + caps[2].len();
+        let before = span_code.get(..start)?;
+        let after = span_code.get(end..)?;
+        return Some(Suggestion {
+            before: vec![span_code.trim().to_string()],
+            after: vec![format!("{}{}{}", before, &caps[3], after)
+                .trim()
+                .to_string()],
+        });
+    }
+
+    let help = help?;
+    let mut suggestion = Suggestion {
+        before: Vec::new(),
+        after: Vec::new(),
+    };
+    let section = &block[help + 1..];
+    for (i, line) in section.iter().enumerate() {
+        if line.trim().is_empty()
+            || line.starts_with("help:")
+            || line.trim_start().starts_with("= ")
+        {
+            break;
+        }
+        if let Some(caps) = diff_re.captures(line) {
+            let code = caps[2].trim().to_string();
+            if &caps[1] == "-" {
+                suggestion.before.push(code);
+            } else {
+                suggestion.after.push(code);
+            }
+        } else if let Some(caps) = code_re.captures(line) {
+            if section.get(i + 1).is_some_and(|next| is_marker(next)) {
+                suggestion.after.push(caps[1].trim().to_string());
+            }
+        }
+    }
+    if suggestion.after.is_empty() && suggestion.before.is_empty() {
+        return None;
+    }
+    if suggestion.before.is_empty() {
+        suggestion.before.push(span_code.trim().to_string());
+    }
+    Some(suggestion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Theme;
+
+    #[test]
+    fn test_filter_cargo_clippy_clean() {
+        let output = r#"    Checking rtk v0.5.0
+    Finished dev [unoptimized + debuginfo] target(s) in 1.53s
+"#;
+        let result = filter_cargo_clippy(output, &FilterOptions::default());
+        assert!(result.contains("✓ cargo clippy: No issues found"));
+    }
+
+    #[test]
+    fn test_filter_cargo_clippy_warnings() {
+        let output = r#"    Checking rtk v0.5.0
+warning: unused variable: `x` [unused_variables]
+ --> src/main.rs:10:9
+  |
+10|     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+
+warning: this function has too many arguments [clippy::too_many_arguments]
+ --> src/git.rs:16:1
+  |
+16| pub fn run(a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32, h: i32) {}
+  |
+
+warning: `rtk` (bin) generated 2 warnings
+    Finished dev [unoptimized + debuginfo] target(s) in 1.53s
+"#;
+        let result = filter_cargo_clippy(output, &FilterOptions::default());
+        assert!(result.contains("0 errors, 2 warnings"));
+        assert!(result.contains("unused_variables"));
+        assert!(result.contains("clippy::too_many_arguments"));
+    }
+
+    #[test]
+    fn test_filter_cargo_clippy_level_breakdown() {
+        let output = r#"    Checking app v0.1.0
+error: unneeded `return` statement
+ --> src/lib.rs:3:5
+  |
+3 |     return x;
+  |     ^^^^^^^^
+  |
+  = note: `-D clippy::needless-return` implied by `-D warnings`
+  = help: to override `-D warnings` add `#[allow(clippy::needless_return)]`
+
+error: used `unwrap()` on an `Option` value
+ --> src/lib.rs:8:5
+  |
+8 |     y.unwrap()
+  |     ^^^^^^^^^^
+  |
+  = note: requested on the command line with `-D clippy::unwrap-used`
+
+error: this comparison involving the minimum or maximum element for this type contains a case that is always true or always false
+ --> src/lib.rs:12:8
+  |
+  = note: `#[deny(clippy::absurd_extreme_comparisons)]` on by default
+
+error[E0308]: mismatched types
+ --> src/lib.rs:20:5
+  |
+
+warning: unused variable: `z`
+ --> src/lib.rs:30:9
+  |
+  = note: `#[warn(unused_variables)]` on by default
+
+error: could not compile `app` (lib) due to 4 previous errors; 1 warning emitted
+"#;
+        let result = filter_cargo_clippy(output, &FilterOptions::default());
+        assert!(
+            result.starts_with("cargo clippy: 4 errors, 1 warnings\nby level: 1 denied by -D warnings, 1 denied by config, 1 deny by default, 1 error, 1 warn\n"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains(
+            "  unneeded `return` statement (1x)\n    note: `-D clippy::needless-return` implied by `-D warnings`\n    src/lib.rs:3:5"
+        ));
+        assert!(result
+            .contains("    note: requested on the command line with `-D clippy::unwrap-used`"));
+        // Warn-level notes are not deny reasons
+        assert!(!result.contains("#[warn(unused_variables)]"));
+    }
+
+    #[test]
+    fn test_filter_cargo_clippy_suggest() {
+        let output = r#"    Checking app v0.1.0
+warning: unneeded `return` statement
+ --> src/lib.rs:3:5
+  |
+3 |     return x;
+  |     ^^^^^^^^^
+  |
+  = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#needless_return
+  = note: `#[warn(clippy::needless_return)]` on by default
+help: remove `return`
+  |
+3 -     return x;
+3 +     x
+  |
+
+warning: unneeded `return` statement
+ --> src/lib.rs:9:5
+  |
+9 |     return y;
+  |     ^^^^^^^^^
+  |
+help: remove `return`
+  |
+9 -     return y;
+9 +     y
+  |
+
+warning: length comparison to zero
+ --> src/lib.rs:14:8
+   |
+14 |     if v.len() == 0 {
+   |        ^^^^^^^^^^^^ help: using `is_empty` is clearer and more explicit: `v.is_empty()`
+   |
+   = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#len_zero
+
+warning: `app` (lib) generated 3 warnings
+"#;
+        let opts = FilterOptions {
+            suggest: true,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_clippy(output, &opts);
+        assert!(
+            result.contains(
+                "  unneeded `return` statement (2x)\n    src/lib.rs:3:5\n    src/lib.rs:9:5\n    - return x;\n    + x\n"
+            ),
+            "got: {}",
+            result
+        );
+        assert!(result.ends_with(
+            "  length comparison to zero (1x)\n    src/lib.rs:14:8\n    - if v.len() == 0 {\n    + if v.is_empty() {"
+        ));
+
+        let plain = filter_cargo_clippy(output, &FilterOptions::default());
+        assert!(!plain.contains("+ x"));
+    }
+
+    #[test]
+    fn test_clippy_suggestion_tilde_style() {
+        let block = [
+            "warning: redundant clone",
+            " --> src/main.rs:5:14",
+            "  |",
+            "5 |     let s = t.clone();",
+            "  |              ^^^^^^^^",
+            "help: remove this",
+            "  |",
+            "5 |     let s = t;",
+            "  |             ~",
+        ];
+        assert_eq!(
+            clippy_suggestion(&block),
+            Some(Suggestion {
+                before: vec!["let s = t.clone();".to_string()],
+                after: vec!["let s = t;".to_string()],
+            })
+        );
+        assert_eq!(clippy_suggestion(&block[..5]), None);
+    }
+
+    #[test]
+    fn test_filter_cargo_clippy_only_glob() {
+        let output = r#"warning: redundant clone [clippy::redundant_clone]
+ --> src/parser.rs:5:14
+
+warning: redundant clone [clippy::redundant_clone]
+ --> src/main.rs:9:14
+
+warning: unused variable: `z` [unused_variables]
+ --> src/main.rs:30:9
+
+warning: `app` (bin "app") generated 3 warnings
+"#;
+        let opts = FilterOptions {
+            only: Some("*parser.rs".to_string()),
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_clippy(output, &opts);
+        assert!(result.starts_with(
+            "cargo clippy: 0 errors, 3 warnings (filtered to *parser.rs: showing 1 of 3)\n"
+        ));
+        assert!(result.contains("  clippy::redundant_clone (1x)\n    src/parser.rs:5:14"));
+        assert!(!result.contains("unused_variables"));
+    }
+
+    #[test]
+    fn test_filter_cargo_clippy_ascii_theme() {
+        let output = "warning: unused variable: `x` [unused_variables]\n --> src/main.rs:2:9\n";
+        let opts = FilterOptions {
+            theme: Theme::ascii(),
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_clippy(output, &opts);
+        assert!(
+            result.is_ascii(),
+            "expected ASCII-only output, got: {}",
+            result
+        );
+        assert!(result.contains("---------------------------------------"));
+    }
+}
@@ -0,0 +1,483 @@
+use super::{
+    failed_crate, format_failed_crates, is_noise_line, render_blocks, split_sccache_stats,
+};
+use crate::cargo_cmd::FilterOptions;
+
+/// Format crate name + version into a display string
+fn format_crate_info(name: &str, version: &str, fallback: &str) -> String {
+    if name.is_empty() {
+        fallback.to_string()
+    } else if version.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} {}", name, version)
+    }
+}
+
+/// Format every installed crate, comma-separated, or `fallback` when none were seen
+fn format_crate_list(crates: &[(String, String)], fallback: &str) -> String {
+    if crates.is_empty() {
+        return fallback.to_string();
+    }
+    crates
+        .iter()
+        .map(|(name, version)| format_crate_info(name, version, fallback))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Record a `name version` crate once (`Installing` and `Installed` both mention it)
+fn push_crate(crates: &mut Vec<(String, String)>, info: &str) {
+    let (name, version) = info.split_once(' ').unwrap_or((info, ""));
+    if !crates.iter().any(|(n, _)| n == name) {
+        crates.push((name.to_string(), version.trim().to_string()));
+    }
+}
+
+/// Filter cargo install output - strip dep compilation, keep installed/replaced/errors
+pub(crate) fn filter_cargo_install(output: &str, opts: &FilterOptions) -> String {
+    let (output, _) = split_sccache_stats(output);
+    let output = output.as_str();
+    let mut errors: Vec<String> = Vec::new();
+    let mut error_count = 0;
+    let mut compiled = 0;
+    let mut in_error = false;
+    let mut current_error = Vec::new();
+    let mut installed: Vec<(String, String)> = Vec::new();
+    let mut replaced_lines: Vec<String> = Vec::new();
+    let mut ignored: Vec<String> = Vec::new();
+    let mut hidden_warnings = 0;
+    let mut failed_crates: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim_start();
+
+        // Strip noise: dep compilation, downloading, locking, etc.
+        if trimmed.starts_with("Compiling") {
+            compiled += 1;
+            continue;
+        }
+        if is_noise_line(line) || trimmed.starts_with("Summary ") {
+            continue;
+        }
+
+        // Keep: Installing line (extract crate name + version; binary paths are skipped)
+        if trimmed.starts_with("Installing") {
+            let rest = trimmed.strip_prefix("Installing").unwrap_or("").trim();
+            if !rest.is_empty() && !rest.starts_with('/') {
+                push_crate(&mut installed, rest);
+            }
+            continue;
+        }
+
+        // Keep: Installed line, e.g. "Installed package `rg v14.0.0` (executable `rg`)"
+        if trimmed.starts_with("Installed") {
+            if let Some(info) = trimmed.split('`').nth(1) {
+                push_crate(&mut installed, info);
+            }
+            continue;
+        }
+
+        // Keep: Replacing/Replaced lines
+        if trimmed.starts_with("Replacing") || trimmed.starts_with("Replaced") {
+            replaced_lines.push(trimmed.to_string());
+            continue;
+        }
+
+        // Keep: "Ignored package" (already up to date)
+        if trimmed.starts_with("Ignored package") {
+            let info = trimmed.split('`').nth(1).unwrap_or(trimmed);
+            ignored.push(info.to_string());
+            continue;
+        }
+
+        // Keep: actionable warnings (e.g., "be sure to add `/path` to your PATH")
+        // Skip summary lines like "warning: `crate` generated N warnings"
+        if line.starts_with("warning:") {
+            if line.contains("generated") && line.contains("warning") {
+                continue;
+            }
+            if opts.errors_only {
+                hidden_warnings += 1;
+            } else {
+                replaced_lines.push(line.to_string());
+            }
+            continue;
+        }
+
+        // Detect error blocks
+        if line.starts_with("error[") || line.starts_with("error:") {
+            if let Some(name) = failed_crate(line) {
+                if !failed_crates.contains(&name) {
+                    failed_crates.push(name);
+                }
+                continue;
+            }
+            if line.contains("aborting due to") || line.contains("some crates failed to install") {
+                continue;
+            }
+            if in_error && !current_error.is_empty() {
+                errors.push(current_error.join("\n"));
+                current_error.clear();
+            }
+            error_count += 1;
+            in_error = true;
+            current_error.push(line.to_string());
+        } else if in_error {
+            // A blank line always ends the block, however short: rustc never
+            // emits blank lines inside a diagnostic, and waiting for a longer
+            // block would glue unrelated trailing output onto a two-line error.
+            if line.trim().is_empty() {
+                errors.push(current_error.join("\n"));
+                current_error.clear();
+                in_error = false;
+            } else {
+                current_error.push(line.to_string());
+            }
+        }
+    }
+
+    if !current_error.is_empty() {
+        errors.push(current_error.join("\n"));
+    }
+
+    let hidden = if hidden_warnings > 0 {
+        format!(" ({} warnings hidden)", hidden_warnings)
+    } else {
+        String::new()
+    };
+
+    // Already installed / up to date
+    if !ignored.is_empty() && installed.is_empty() && error_count == 0 {
+        return format!(
+            "{} cargo install: {} already installed",
+            opts.check(),
+            ignored.join(", ")
+        );
+    }
+
+    // Errors
+    if error_count > 0 {
+        let crate_info = format_crate_list(&installed, "");
+        let deps_info = if compiled > 0 {
+            format!(", {} deps compiled", compiled)
+        } else {
+            String::new()
+        };
+
+        let mut result = String::new();
+        if crate_info.is_empty() {
+            result.push_str(&format!(
+                "cargo install: {} error{}{}{}{}\n",
+                error_count,
+                if error_count > 1 { "s" } else { "" },
+                format_failed_crates(&failed_crates),
+                deps_info,
+                hidden
+            ));
+        } else {
+            result.push_str(&format!(
+                "cargo install: {} error{}{} ({}{}){}\n",
+                error_count,
+                if error_count > 1 { "s" } else { "" },
+                format_failed_crates(&failed_crates),
+                crate_info,
+                deps_info,
+                hidden
+            ));
+        }
+        result.push_str(opts.separator());
+        result.push('\n');
+        result.push_str(&render_blocks(&errors, opts));
+
+        return result.trim().to_string();
+    }
+
+    // Success
+    let crate_info = format_crate_list(&installed, "package");
+
+    let mut result = if installed.len() > 1 {
+        format!(
+            "{} cargo install: {} ({} deps compiled)",
+            opts.check(),
+            crate_info,
+            compiled
+        )
+    } else {
+        format!(
+            "{} cargo install ({}, {} deps compiled)",
+            opts.check(),
+            crate_info,
+            compiled
+        )
+    };
+
+    result.push_str(&hidden);
+    if !ignored.is_empty() {
+        result.push_str(&format!("\n  already installed: {}", ignored.join(", ")));
+    }
+    for line in &replaced_lines {
+        result.push_str(&format!("\n  {}", line));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_cargo_install_success() {
+        let output = r#"  Installing rtk v0.11.0
+  Downloading crates ...
+  Downloaded anyhow v1.0.80
+  Downloaded clap v4.5.0
+   Compiling libc v0.2.153
+   Compiling cfg-if v1.0.0
+   Compiling anyhow v1.0.80
+   Compiling clap v4.5.0
+   Compiling rtk v0.11.0
+    Finished `release` profile [optimized] target(s) in 45.23s
+  Replacing /Users/user/.cargo/bin/rtk
+   Replaced package `rtk v0.9.4` with `rtk v0.11.0` (/Users/user/.cargo/bin/rtk)
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("✓ cargo install"), "got: {}", result);
+        assert!(result.contains("rtk v0.11.0"), "got: {}", result);
+        assert!(result.contains("5 deps compiled"), "got: {}", result);
+        assert!(result.contains("Replaced"), "got: {}", result);
+        assert!(!result.contains("Compiling"), "got: {}", result);
+        assert!(!result.contains("Downloading"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_replace() {
+        let output = r#"  Installing rtk v0.11.0
+   Compiling rtk v0.11.0
+    Finished `release` profile [optimized] target(s) in 10.0s
+  Replacing /Users/user/.cargo/bin/rtk
+   Replaced package `rtk v0.9.4` with `rtk v0.11.0` (/Users/user/.cargo/bin/rtk)
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("✓ cargo install"), "got: {}", result);
+        assert!(result.contains("Replacing"), "got: {}", result);
+        assert!(result.contains("Replaced"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_error() {
+        let output = r#"  Installing rtk v0.11.0
+   Compiling rtk v0.11.0
+error[E0308]: mismatched types
+ --> src/main.rs:10:5
+  |
+10|     "hello"
+  |     ^^^^^^^ expected `i32`, found `&str`
+
+error: aborting due to 1 previous error
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("cargo install: 1 error"), "got: {}", result);
+        assert!(result.contains("E0308"), "got: {}", result);
+        assert!(result.contains("mismatched types"), "got: {}", result);
+        assert!(!result.contains("aborting"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_already_installed() {
+        let output = r#"  Ignored package `rtk v0.11.0`, is already installed
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("already installed"), "got: {}", result);
+        assert!(result.contains("rtk v0.11.0"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_up_to_date() {
+        let output = r#"  Ignored package `cargo-deb v2.1.0 (/Users/user/cargo-deb)`, is already installed
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("already installed"), "got: {}", result);
+        assert!(result.contains("cargo-deb v2.1.0"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_empty_output() {
+        let result = filter_cargo_install("", &FilterOptions::default());
+        assert!(result.contains("✓ cargo install"), "got: {}", result);
+        assert!(result.contains("0 deps compiled"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_path_warning() {
+        let output = r#"  Installing rtk v0.11.0
+   Compiling rtk v0.11.0
+    Finished `release` profile [optimized] target(s) in 10.0s
+  Replacing /Users/user/.cargo/bin/rtk
+   Replaced package `rtk v0.9.4` with `rtk v0.11.0` (/Users/user/.cargo/bin/rtk)
+warning: be sure to add `/Users/user/.cargo/bin` to your PATH
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("✓ cargo install"), "got: {}", result);
+        assert!(
+            result.contains("be sure to add"),
+            "PATH warning should be kept: {}",
+            result
+        );
+        assert!(result.contains("Replaced"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_multiple_errors() {
+        let output = r#"  Installing rtk v0.11.0
+   Compiling rtk v0.11.0
+error[E0308]: mismatched types
+ --> src/main.rs:10:5
+  |
+10|     "hello"
+  |     ^^^^^^^ expected `i32`, found `&str`
+
+error[E0425]: cannot find value `foo`
+ --> src/lib.rs:20:9
+  |
+20|     foo
+  |     ^^^ not found in this scope
+
+error: aborting due to 2 previous errors
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(
+            result.contains("2 errors"),
+            "should show 2 errors: {}",
+            result
+        );
+        assert!(result.contains("E0308"), "got: {}", result);
+        assert!(result.contains("E0425"), "got: {}", result);
+        assert!(!result.contains("aborting"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_locking_and_blocking() {
+        let output = r#"  Locking 45 packages to latest compatible versions
+  Blocking waiting for file lock on package cache
+  Downloading crates ...
+  Downloaded serde v1.0.200
+   Compiling serde v1.0.200
+   Compiling rtk v0.11.0
+    Finished `release` profile [optimized] target(s) in 30.0s
+  Installing rtk v0.11.0
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("✓ cargo install"), "got: {}", result);
+        assert!(!result.contains("Locking"), "got: {}", result);
+        assert!(!result.contains("Blocking"), "got: {}", result);
+        assert!(!result.contains("Downloading"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_from_path() {
+        let output = r#"  Installing /Users/user/projects/rtk
+   Compiling rtk v0.11.0
+    Finished `release` profile [optimized] target(s) in 10.0s
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        // Path-based install: crate info not extracted from path
+        assert!(result.contains("✓ cargo install"), "got: {}", result);
+        assert!(result.contains("1 deps compiled"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_install_multiple_crates() {
+        let output = r#"    Updating crates.io index
+  Installing ripgrep v14.0.0
+   Compiling memchr v2.7.1
+   Compiling regex v1.10.2
+  Installing /home/user/.cargo/bin/rg
+   Installed package `ripgrep v14.0.0` (executable `rg`)
+  Installing fd-find v9.0.0
+   Compiling libc v0.2.150
+  Installing /home/user/.cargo/bin/fd
+   Installed package `fd-find v9.0.0` (executable `fd`)
+     Summary Successfully installed ripgrep, fd-find!
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert_eq!(
+            result,
+            "✓ cargo install: ripgrep v14.0.0, fd-find v9.0.0 (3 deps compiled)"
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_install_multiple_partially_installed() {
+        let output = r#"  Ignored package `ripgrep v14.0.0`, is already installed
+  Installing fd-find v9.0.0
+   Compiling libc v0.2.150
+   Installed package `fd-find v9.0.0` (executable `fd`)
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(
+            result.starts_with("✓ cargo install (fd-find v9.0.0, 1 deps compiled)"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("already installed: ripgrep v14.0.0"));
+
+        let all_ignored = "  Ignored package `ripgrep v14.0.0`, is already installed\n  Ignored package `fd-find v9.0.0`, is already installed\n";
+        assert_eq!(
+            filter_cargo_install(all_ignored, &FilterOptions::default()),
+            "✓ cargo install: ripgrep v14.0.0, fd-find v9.0.0 already installed"
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_install_multiple_with_failure() {
+        let output = r#"  Installing ripgrep v14.0.0
+   Installed package `ripgrep v14.0.0` (executable `rg`)
+  Installing broken v0.1.0
+   Compiling broken v0.1.0
+error[E0425]: cannot find value `x` in this scope
+ --> src/main.rs:2:5
+  |
+2 |     x
+  |     ^ not found in this scope
+
+error: could not compile `broken` (bin "broken") due to 1 previous error
+     Summary Successfully installed ripgrep! Failed to install broken (see error(s) above).
+error: some crates failed to install
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(
+            result
+                .starts_with("cargo install: 1 error in `broken` (ripgrep v14.0.0, broken v0.1.0"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("E0425"));
+        assert!(!result.contains("some crates failed"));
+    }
+
+    #[test]
+    fn test_filter_cargo_install_short_error_block() {
+        let output = r#"  Installing rtk v0.11.0
+
+error: failed to compile `rtk v0.11.0`, intermediate artifacts can be found at `/tmp/cargo-install`
+Caused by: linker `cc` not found
+
+  Installing /home/user/.cargo/bin/rtk
+note: unrelated trailing output
+"#;
+        let result = filter_cargo_install(output, &FilterOptions::default());
+        assert!(result.contains("cargo install: 1 error"), "got: {}", result);
+        assert!(result.contains("linker `cc` not found"), "got: {}", result);
+        assert!(!result.contains("unrelated trailing"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_format_crate_info() {
+        assert_eq!(format_crate_info("rtk", "v0.11.0", ""), "rtk v0.11.0");
+        assert_eq!(format_crate_info("rtk", "", ""), "rtk");
+        assert_eq!(format_crate_info("", "", "package"), "package");
+        assert_eq!(format_crate_info("", "v0.1.0", "fallback"), "fallback");
+    }
+}
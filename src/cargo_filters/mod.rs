@@ -0,0 +1,217 @@
+//! Filters for the captured output of `cargo build`/`check`, `test`, `clippy` and
+//! `install`, registered in `cargo_cmd`'s filter table, plus the line helpers they share.
+
+mod build;
+mod clippy;
+mod install;
+mod test;
+
+pub(crate) use build::{crate_warning_summary, filter_cargo_build};
+pub(crate) use clippy::filter_cargo_clippy;
+pub(crate) use install::filter_cargo_install;
+pub(crate) use test::{filter_cargo_test, parse_failed_tests};
+
+use crate::cargo_cmd::FilterOptions;
+use std::sync::OnceLock;
+
+/// Cargo status words that only report progress, never diagnostics
+const NOISE_PREFIXES: &[&str] = &[
+    "Compiling",
+    "Checking",
+    "Downloading",
+    "Downloaded",
+    "Finished",
+    "Locking",
+    "Updating",
+    "Adding",
+    "Blocking waiting for file lock",
+];
+
+/// Rows of an `sccache --show-stats` table that leaks into build logs. Generic names like
+/// `Cache ` are only matched inside a table opened by a `Compile requests` row, see
+/// [`split_sccache_stats`].
+const SCCACHE_STATS_ROWS: &[&str] = &[
+    "Compile requests",
+    "Compilation failures",
+    "Cache ",
+    "Forced recaches",
+    "Non-cacheable ",
+    "Non-compilation calls",
+    "Unsupported compiler calls",
+    "Average cache ",
+    "Average compiler",
+    "Failed distributed compilations",
+    "Successful distributed compilations",
+    "Use direct/preprocessor mode?",
+    "Version (client)",
+    "Max cache size",
+];
+
+/// Whether a line is cargo progress noise; shared by every cargo filter so they strip the same set
+pub(crate) fn is_noise_line(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    NOISE_PREFIXES.iter().any(|prefix| {
+        trimmed
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+    }) || is_sccache_line(line)
+}
+
+/// Split an `sccache --show-stats` table out of cargo's output: returns the output without
+/// it and the table itself. The table starts at a column-0 `Compile requests` row and runs
+/// while rows match [`SCCACHE_STATS_ROWS`], so a program's own `Cache ...` line elsewhere
+/// is kept.
+pub(crate) fn split_sccache_stats(output: &str) -> (String, String) {
+    let mut rest = Vec::new();
+    let mut stats = Vec::new();
+    let mut in_stats = false;
+    for line in output.lines() {
+        in_stats = if in_stats {
+            SCCACHE_STATS_ROWS.iter().any(|row| line.starts_with(row))
+        } else {
+            line.starts_with("Compile requests")
+        };
+        if in_stats {
+            stats.push(line);
+        } else {
+            rest.push(line);
+        }
+    }
+    (rest.join("\n"), stats.join("\n"))
+}
+
+/// sccache status chatter (`sccache: Starting the server...`); its own errors are kept
+fn is_sccache_line(line: &str) -> bool {
+    line.starts_with("sccache: ") && !line.contains("error")
+}
+
+/// The first `max_errors` blocks plus any later ones matching a `keep` pattern,
+/// then a count of the rest
+fn render_blocks(blocks: &[String], opts: &FilterOptions) -> String {
+    let mut shown = Vec::new();
+    let mut omitted = 0;
+    for (i, block) in blocks.iter().enumerate() {
+        if i < opts.max_errors || opts.keeps(block) {
+            shown.push(limit_context(block, opts.context));
+        } else {
+            omitted += 1;
+        }
+    }
+
+    let mut result = shown.join("\n\n");
+    if omitted > 0 {
+        result.push_str(&format!("\n\n... +{} more issues", omitted));
+    }
+    result
+}
+
+/// Keep at most `max` of the `|`-prefixed snippet lines in an error block;
+/// the header, `-->` location, notes and hints are always kept.
+fn limit_context(block: &str, max: Option<usize>) -> String {
+    static GUTTER_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let Some(max) = max else {
+        return block.to_string();
+    };
+    let gutter_re =
+        GUTTER_RE.get_or_init(|| regex::Regex::new(r"^\s*\d*\s*\|").expect("invalid gutter regex"));
+
+    let mut kept = 0;
+    block
+        .lines()
+        .filter(|line| {
+            if !gutter_re.is_match(line) {
+                return true;
+            }
+            kept += 1;
+            kept <= max
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `src/lib.rs` from `src/lib.rs:3:5`
+fn span_file(span: &str) -> Option<&str> {
+    span.trim_end().rsplitn(3, ':').nth(2)
+}
+
+/// `--only`: whether a diagnostic's file matches the glob (no file never does)
+fn in_only(glob: &str, file: Option<&str>) -> bool {
+    file.is_some_and(|file| crate::find_cmd::glob_match(glob, file))
+}
+
+/// ` (filtered to src/parser/*: showing 3 of 20)` for the summary line
+fn only_note(glob: &str, shown: usize, total: usize) -> String {
+    format!(" (filtered to {}: showing {} of {})", glob, shown, total)
+}
+
+/// Crate named by `error: could not compile `foo` (lib) due to N previous errors`
+fn failed_crate(line: &str) -> Option<String> {
+    let rest = line.split_once("could not compile `")?.1;
+    rest.split_once('`').map(|(name, _)| name.to_string())
+}
+
+/// ` in `foo`, `bar`` for the header, empty when cargo named no failing crate
+fn format_failed_crates(crates: &[String]) -> String {
+    if crates.is_empty() {
+        return String::new();
+    }
+    let names: Vec<String> = crates.iter().map(|c| format!("`{}`", c)).collect();
+    format!(" in {}", names.join(", "))
+}
+
+/// Rule a diagnostic header belongs to: the `[rule_name]` bracket, or the message itself
+fn diagnostic_rule(header: &str) -> String {
+    if let (Some(start), Some(end)) = (header.rfind('['), header.rfind(']')) {
+        if start < end {
+            return header[start + 1..end].to_string();
+        }
+    }
+    ["error: ", "warning: "]
+        .iter()
+        .find_map(|prefix| header.strip_prefix(prefix))
+        .unwrap_or(header)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sccache_stats_keeps_unrelated_cache_lines() {
+        let output = "Cache warmed: 120 entries\n   Compiling app v0.1.0\nCompile requests                     2\nCache hits                           1\nCache misses                         1\n    Finished `dev` profile\nCache size is 4 MiB\n";
+        let (rest, stats) = split_sccache_stats(output);
+        assert_eq!(
+            rest,
+            "Cache warmed: 120 entries\n   Compiling app v0.1.0\n    Finished `dev` profile\nCache size is 4 MiB"
+        );
+        assert_eq!(stats.lines().count(), 3);
+        assert!(!is_noise_line("Cache warmed: 120 entries"));
+    }
+
+    #[test]
+    fn test_is_noise_line() {
+        for line in [
+            "   Compiling rtk v0.5.0",
+            "    Checking serde v1.0.0",
+            " Downloading crates ...",
+            "  Downloaded regex v1.10.2",
+            "    Finished `dev` profile [unoptimized + debuginfo] target(s) in 1.2s",
+            "     Locking 42 packages to latest compatible versions",
+            "    Updating crates.io index",
+            "      Adding serde v1.0.0 (latest: v1.0.1)",
+            "    Blocking waiting for file lock on package cache",
+        ] {
+            assert!(is_noise_line(line), "should be noise: {}", line);
+        }
+        for line in [
+            "error[E0308]: mismatched types",
+            "warning: unused variable",
+            "test result: ok. 1 passed",
+            "Checkingsomething",
+            "  Installing rtk v0.5.0",
+        ] {
+            assert!(!is_noise_line(line), "should keep: {}", line);
+        }
+    }
+}
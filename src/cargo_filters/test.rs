@@ -0,0 +1,1038 @@
+use crate::cargo_cmd::{filter_cargo_nextest, is_nextest_output, FilterOptions};
+use crate::utils::truncate;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Aggregated test results for compact display
+#[derive(Debug, Default, Clone)]
+struct AggregatedTestResult {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    measured: usize,
+    filtered_out: usize,
+    suites: usize,
+    duration_secs: f64,
+    has_duration: bool,
+}
+
+impl AggregatedTestResult {
+    /// Parse a test result summary line
+    /// Format: "test result: ok. 15 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s"
+    fn parse_line(line: &str) -> Option<Self> {
+        static RE: OnceLock<regex::Regex> = OnceLock::new();
+        let re = RE.get_or_init(|| {
+            regex::Regex::new(
+                r"test result: (\w+)\.\s+(\d+) passed;\s+(\d+) failed;\s+(\d+) ignored;\s+(\d+) measured;\s+(\d+) filtered out(?:;\s+finished in ([\d.]+)s)?"
+            ).unwrap()
+        });
+
+        let caps = re.captures(line)?;
+        let status = caps.get(1)?.as_str();
+
+        // Only aggregate if status is "ok" (all tests passed)
+        if status != "ok" {
+            return None;
+        }
+
+        let passed = caps.get(2)?.as_str().parse().ok()?;
+        let failed = caps.get(3)?.as_str().parse().ok()?;
+        let ignored = caps.get(4)?.as_str().parse().ok()?;
+        let measured = caps.get(5)?.as_str().parse().ok()?;
+        let filtered_out = caps.get(6)?.as_str().parse().ok()?;
+
+        let (duration_secs, has_duration) = if let Some(duration_match) = caps.get(7) {
+            (duration_match.as_str().parse().unwrap_or(0.0), true)
+        } else {
+            (0.0, false)
+        };
+
+        Some(Self {
+            passed,
+            failed,
+            ignored,
+            measured,
+            filtered_out,
+            suites: 1,
+            duration_secs,
+            has_duration,
+        })
+    }
+
+    /// Merge another test result into this one
+    fn merge(&mut self, other: &Self) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.ignored += other.ignored;
+        self.measured += other.measured;
+        self.filtered_out += other.filtered_out;
+        self.suites += other.suites;
+        self.duration_secs += other.duration_secs;
+        self.has_duration = self.has_duration && other.has_duration;
+    }
+
+    /// Format as compact single line
+    fn format_compact(&self, check: &str) -> String {
+        let mut parts = vec![format!("{} passed", self.passed)];
+
+        if self.ignored > 0 {
+            parts.push(format!("{} ignored", self.ignored));
+        }
+        if self.filtered_out > 0 {
+            parts.push(format!("{} filtered out", self.filtered_out));
+        }
+
+        let counts = parts.join(", ");
+
+        let suite_text = if self.suites == 1 {
+            "1 suite".to_string()
+        } else {
+            format!("{} suites", self.suites)
+        };
+
+        if self.has_duration {
+            format!(
+                "{} cargo test: {} ({}, {:.2}s)",
+                check, counts, suite_text, self.duration_secs
+            )
+        } else {
+            format!("{} cargo test: {} ({})", check, counts, suite_text)
+        }
+    }
+}
+
+/// Filter cargo test output - show failures + summary only
+pub(crate) fn filter_cargo_test(output: &str, opts: &FilterOptions) -> String {
+    // `cargo test` aliased to nextest (e.g. via .cargo/config.toml) prints nextest's format
+    if is_nextest_output(output) {
+        return filter_cargo_nextest(output, opts);
+    }
+
+    let (failures, summary_lines) =
+        parse_libtest_json(output).unwrap_or_else(|| parse_libtest_text(output));
+    render_test_results(output, &failures, &summary_lines, opts)
+}
+
+/// One libtest `--format json` event (`-Z unstable-options --format json`)
+#[derive(Debug, Deserialize)]
+struct LibtestEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    event: String,
+    name: Option<String>,
+    stdout: Option<String>,
+    passed: Option<usize>,
+    failed: Option<usize>,
+    ignored: Option<usize>,
+    measured: Option<usize>,
+    filtered_out: Option<usize>,
+    exec_time: Option<f64>,
+}
+
+/// Names of failed tests: libtest `test foo::bar ... FAILED` and nextest `FAIL [ 0.01s] crate foo::bar`
+pub(crate) fn parse_failed_tests(output: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        let event = trimmed
+            .starts_with('{')
+            .then(|| serde_json::from_str::<LibtestEvent>(trimmed).ok())
+            .flatten()
+            .filter(|e| e.kind == "test" && e.event == "failed");
+        let name = if let Some(event) = &event {
+            event.name.as_deref()
+        } else if let Some(rest) = trimmed.strip_prefix("test ") {
+            rest.strip_suffix(" ... FAILED").map(str::trim)
+        } else if trimmed.starts_with("FAIL [") {
+            trimmed.split_whitespace().last()
+        } else {
+            None
+        };
+        if let Some(name) = name {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Failure blocks and `test result:` lines from libtest JSON events, or `None`
+/// when the output has no JSON events (plain text format)
+fn parse_libtest_json(output: &str) -> Option<(Vec<String>, Vec<String>)> {
+    let mut failures: Vec<String> = Vec::new();
+    let mut summary_lines: Vec<String> = Vec::new();
+    let mut seen = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if !trimmed.starts_with('{') {
+            continue;
+        }
+        let Ok(event) = serde_json::from_str::<LibtestEvent>(trimmed) else {
+            continue;
+        };
+        seen = true;
+
+        match (event.kind.as_str(), event.event.as_str()) {
+            ("test", "failed") => {
+                let name = event.name.unwrap_or_default();
+                let mut block = format!("---- {} stdout ----", name);
+                let stdout = event
+                    .stdout
+                    .unwrap_or_default()
+                    .lines()
+                    .filter(|line| !is_backtrace_note(line))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                if !stdout.trim().is_empty() {
+                    block.push('\n');
+                    block.push_str(stdout.trim_end());
+                }
+                failures.push(block);
+            }
+            ("suite", status @ ("ok" | "failed")) => {
+                let mut summary = format!(
+                    "test result: {}. {} passed; {} failed; {} ignored; {} measured; {} filtered out",
+                    if status == "ok" { "ok" } else { "FAILED" },
+                    event.passed.unwrap_or(0),
+                    event.failed.unwrap_or(0),
+                    event.ignored.unwrap_or(0),
+                    event.measured.unwrap_or(0),
+                    event.filtered_out.unwrap_or(0)
+                );
+                if let Some(secs) = event.exec_time {
+                    summary.push_str(&format!("; finished in {:.2}s", secs));
+                }
+                summary_lines.push(summary);
+            }
+            _ => {}
+        }
+    }
+
+    seen.then_some((failures, summary_lines))
+}
+
+/// Failure blocks and `test result:` lines from libtest's human-readable output.
+///
+/// Each failure is keyed on the test name in its `---- <name> stdout ----` header and
+/// owns every line up to the next header, blank lines and unindented output included.
+/// The trailing `failures:` name list only adds tests that printed no stdout section.
+fn parse_libtest_text(output: &str) -> (Vec<String>, Vec<String>) {
+    #[derive(PartialEq)]
+    enum Section {
+        Outside,
+        Stdout,
+        NameList,
+    }
+
+    let mut failures: Vec<(String, Vec<String>)> = Vec::new();
+    let mut summary_lines: Vec<String> = Vec::new();
+    let mut section = Section::Outside;
+
+    for line in output.lines() {
+        if line.starts_with("test result:") {
+            summary_lines.push(line.to_string());
+            section = Section::Outside;
+            continue;
+        }
+
+        match section {
+            Section::Outside => {
+                if line == "failures:" {
+                    section = Section::Stdout;
+                }
+            }
+            Section::Stdout => {
+                if line == "failures:" {
+                    section = Section::NameList;
+                } else if let Some((name, stream)) = failure_header(line) {
+                    match failures.iter_mut().find(|(n, _)| n == name) {
+                        // `---- name stderr ----` after its stdout section: same failure
+                        Some((_, body)) => body.push(format!("---- {} ----", stream)),
+                        None => failures.push((name.to_string(), Vec::new())),
+                    }
+                } else if !is_backtrace_note(line) {
+                    if let Some((_, body)) = failures.last_mut() {
+                        body.push(line.to_string());
+                    }
+                }
+            }
+            Section::NameList => {
+                let name = line.trim();
+                if !name.is_empty() && !failures.iter().any(|(n, _)| n == name) {
+                    failures.push((name.to_string(), Vec::new()));
+                }
+            }
+        }
+    }
+
+    let failures = failures
+        .into_iter()
+        .map(|(name, body)| {
+            let body = body.join("\n");
+            let body = body.trim_matches('\n');
+            if body.is_empty() {
+                format!("---- {} stdout ----", name)
+            } else {
+                format!("---- {} stdout ----\n{}", name, body)
+            }
+        })
+        .collect();
+
+    (failures, summary_lines)
+}
+
+/// Cap a failure at `width` characters without cutting into its panic message.
+///
+/// Output the test printed before `thread '...' panicked at` is shortened first; the panic
+/// message itself (e.g. a multi-line `assert_eq!` `left:`/`right:` diff) is kept whole,
+/// with only lines longer than `width` cut.
+fn truncate_failure(failure: &str, width: usize) -> String {
+    if failure.chars().count() <= width {
+        return failure.to_string();
+    }
+    let lines: Vec<&str> = failure.lines().collect();
+    let Some(panic) = lines
+        .iter()
+        .position(|line| line.starts_with("thread '") && line.contains("' panicked at"))
+    else {
+        return truncate(failure, width);
+    };
+
+    let (header, captured) = match lines[..panic].split_first() {
+        Some((header, captured)) if failure_header(header).is_some() => (Some(*header), captured),
+        _ => (None, &lines[..panic]),
+    };
+    let message: Vec<String> = lines[panic..]
+        .iter()
+        .map(|line| truncate(line, width))
+        .collect();
+
+    let used = header.map_or(0, |h| h.chars().count() + 1)
+        + message.iter().map(|l| l.chars().count() + 1).sum::<usize>();
+    let captured = captured.join("\n");
+    let captured = captured.trim();
+
+    let mut result: Vec<String> = header.map(str::to_string).into_iter().collect();
+    if !captured.is_empty() {
+        // At least the `...` marker, so it's clear output was dropped
+        result.push(truncate(captured, width.saturating_sub(used).max(3)));
+    }
+    result.extend(message);
+    result.join("\n")
+}
+
+/// User-code frames kept from a `RUST_BACKTRACE=1` backtrace
+const MAX_USER_FRAMES: usize = 3;
+
+/// Split a failure into its message and the `stack backtrace:` section, if any
+fn split_backtrace(failure: &str) -> (&str, Option<&str>) {
+    match failure.find("\nstack backtrace:") {
+        Some(pos) => (&failure[..pos], Some(&failure[pos + 1..])),
+        None => (failure, None),
+    }
+}
+
+/// Keep the first few frames from the crate under test and collapse std/runtime
+/// (and dependency) frames into `... N std frames` lines.
+fn condense_backtrace(backtrace: &str) -> String {
+    static FRAME_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let frame_re =
+        FRAME_RE.get_or_init(|| regex::Regex::new(r"^\s*\d+: ").expect("invalid frame regex"));
+
+    // (frame line, optional `at path:line:col` line)
+    let mut frames: Vec<(&str, Option<&str>)> = Vec::new();
+    for line in backtrace.lines().skip(1) {
+        if frame_re.is_match(line) {
+            frames.push((line, None));
+        } else if let Some(at) = line.trim_start().strip_prefix("at ") {
+            if let Some(frame) = frames.last_mut() {
+                frame.1 = Some(at);
+            }
+        }
+    }
+
+    let mut lines = vec!["stack backtrace:".to_string()];
+    let mut kept = 0;
+    let mut collapsed = 0;
+    for (frame, at) in &frames {
+        if kept < MAX_USER_FRAMES && at.is_some_and(is_user_frame_path) {
+            if collapsed > 0 {
+                lines.push(format!("  ... {} std frames", collapsed));
+                collapsed = 0;
+            }
+            lines.push(frame.to_string());
+            lines.push(format!("             at {}", at.unwrap_or_default()));
+            kept += 1;
+        } else {
+            collapsed += 1;
+        }
+    }
+    if collapsed > 0 {
+        lines.push(format!("  ... {} std frames", collapsed));
+    }
+    lines.join("\n")
+}
+
+/// Whether a backtrace `at` path is inside the current crate (not std, a registry or git dependency)
+fn is_user_frame_path(path: &str) -> bool {
+    if path.starts_with("/rustc/")
+        || path.contains("/.cargo/registry/")
+        || path.contains("/.cargo/git/")
+        || path.contains("/.rustup/toolchains/")
+    {
+        return false;
+    }
+    if !path.starts_with('/') {
+        return true;
+    }
+    std::env::current_dir().is_ok_and(|cwd| Path::new(path).starts_with(cwd))
+}
+
+/// `(file, item, line)` from a doctest failure header: `---- src/lib.rs - foo (line 10) stdout ----`
+fn doctest_location(failure: &str) -> Option<(&str, &str, &str)> {
+    static DOCTEST_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let doctest_re = DOCTEST_RE.get_or_init(|| {
+        regex::Regex::new(r"^---- (.+?) - (.+) \(line (\d+)\) stdout ----")
+            .expect("invalid doctest regex")
+    });
+    let caps = doctest_re.captures(failure)?;
+    Some((
+        caps.get(1)?.as_str(),
+        caps.get(2)?.as_str(),
+        caps.get(3)?.as_str(),
+    ))
+}
+
+/// `src/lib.rs:10 foo` followed by the compile error or panic, minus rustdoc's wrapping
+fn format_doctest_failure(failure: &str) -> String {
+    let Some((file, item, line)) = doctest_location(failure) else {
+        return failure.to_string();
+    };
+
+    let mut lines = vec![format!("{}:{} {}", file, line, item)];
+    for body in failure.lines().skip(1) {
+        let trimmed = body.trim();
+        if trimmed.is_empty()
+            || is_backtrace_note(body)
+            || trimmed.starts_with("error: aborting due to")
+            || trimmed.starts_with("For more information about")
+            || trimmed.starts_with("Test executable failed")
+            || trimmed == "stdout:"
+            || trimmed == "stderr:"
+            // Code snippet gutter (`3 | x`, `  |`); the `-->` location is kept
+            || trimmed.starts_with('|')
+            || trimmed.split_once(" |").is_some_and(|(n, _)| n.parse::<u32>().is_ok())
+        {
+            continue;
+        }
+        if trimmed == "Couldn't compile the test." {
+            lines.push("   (example does not compile)".to_string());
+            continue;
+        }
+        lines.push(format!("   {}", trimmed));
+    }
+    lines.join("\n")
+}
+
+/// Test name and stream from a `---- <name> stdout ----` section header
+fn failure_header(line: &str) -> Option<(&str, &str)> {
+    let inner = line.strip_prefix("---- ")?.strip_suffix(" ----")?;
+    let (name, stream) = inner.rsplit_once(' ')?;
+    matches!(stream, "stdout" | "stderr").then_some((name, stream))
+}
+
+/// libtest's `note: run with `RUST_BACKTRACE=1` ...` line, repeated for every panic
+fn is_backtrace_note(line: &str) -> bool {
+    line.trim_start()
+        .starts_with("note: run with `RUST_BACKTRACE=")
+}
+
+/// Failures list + summary, or the aggregated one-liner when everything passed
+fn render_test_results(
+    output: &str,
+    failures: &[String],
+    summary_lines: &[String],
+    opts: &FilterOptions,
+) -> String {
+    let mut result = String::new();
+
+    if failures.is_empty() && !summary_lines.is_empty() {
+        // All passed - try to aggregate
+        let mut aggregated: Option<AggregatedTestResult> = None;
+        let mut all_parsed = true;
+
+        for line in summary_lines {
+            if let Some(parsed) = AggregatedTestResult::parse_line(line) {
+                if let Some(ref mut agg) = aggregated {
+                    agg.merge(&parsed);
+                } else {
+                    aggregated = Some(parsed);
+                }
+            } else {
+                all_parsed = false;
+                break;
+            }
+        }
+
+        // If all lines parsed successfully and we have at least one suite, return compact format
+        if all_parsed {
+            if let Some(agg) = aggregated {
+                if agg.suites > 0 {
+                    return agg.format_compact(opts.check());
+                }
+            }
+        }
+
+        // Fallback: use original behavior if regex failed
+        for line in summary_lines {
+            result.push_str(&format!("{} {}\n", opts.check(), line));
+        }
+        return result.trim().to_string();
+    }
+
+    // rustdoc failures get their own section: the fix is in the doc example, not the code
+    let (doctests, failures): (Vec<&String>, Vec<&String>) =
+        failures.iter().partition(|f| doctest_location(f).is_some());
+
+    if !failures.is_empty() {
+        result.push_str(&format!("FAILURES ({}):\n", failures.len()));
+        result.push_str(opts.separator());
+        result.push('\n');
+        for (i, failure) in failures.iter().enumerate().take(10) {
+            // Truncate the panic message only; the condensed backtrace is already bounded
+            let (message, backtrace) = split_backtrace(failure);
+            result.push_str(&format!(
+                "{}. {}\n",
+                i + 1,
+                truncate_failure(message, opts.failure_width)
+            ));
+            if let Some(backtrace) = backtrace {
+                result.push_str(&condense_backtrace(backtrace));
+                result.push('\n');
+            }
+        }
+        if failures.len() > 10 {
+            result.push_str(&format!("\n... +{} more failures\n", failures.len() - 10));
+        }
+        result.push('\n');
+    }
+
+    if !doctests.is_empty() {
+        result.push_str(&format!("DOCTEST FAILURES ({}):\n", doctests.len()));
+        result.push_str(opts.separator());
+        result.push('\n');
+        for (i, failure) in doctests.iter().enumerate().take(10) {
+            result.push_str(&format!(
+                "{}. {}\n",
+                i + 1,
+                truncate(&format_doctest_failure(failure), opts.failure_width)
+            ));
+        }
+        if doctests.len() > 10 {
+            result.push_str(&format!("\n... +{} more failures\n", doctests.len() - 10));
+        }
+        result.push('\n');
+    }
+
+    for line in summary_lines {
+        result.push_str(&format!("{}\n", line));
+    }
+
+    if result.trim().is_empty() {
+        // Fallback: show last meaningful lines
+        let meaningful: Vec<&str> = output
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with("Compiling"))
+            .collect();
+        for line in meaningful.iter().rev().take(5).rev() {
+            result.push_str(&format!("{}\n", line));
+        }
+    }
+
+    result.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_cargo_test_libtest_json_all_passed() {
+        let output = r#"{ "type": "suite", "event": "started", "test_count": 2 }
+{ "type": "test", "name": "a", "event": "ok" }
+{ "type": "test", "name": "b", "event": "ok" }
+{ "type": "suite", "event": "ok", "passed": 2, "failed": 0, "ignored": 0, "measured": 0, "filtered_out": 3, "exec_time": 0.25 }
+"#;
+        assert_eq!(
+            filter_cargo_test(output, &FilterOptions::default()),
+            "✓ cargo test: 2 passed, 3 filtered out (1 suite, 0.25s)"
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_test_all_pass() {
+        let output = r#"   Compiling rtk v0.5.0
+    Finished test [unoptimized + debuginfo] target(s) in 2.53s
+     Running target/debug/deps/rtk-abc123
+
+running 15 tests
+test utils::tests::test_truncate_short_string ... ok
+test utils::tests::test_truncate_long_string ... ok
+test utils::tests::test_strip_ansi_simple ... ok
+
+test result: ok. 15 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(
+            result.contains("✓ cargo test: 15 passed (1 suite, 0.01s)"),
+            "Expected compact format, got: {}",
+            result
+        );
+        assert!(!result.contains("Compiling"));
+        assert!(!result.contains("test utils"));
+    }
+
+    #[test]
+    fn test_filter_cargo_test_failures() {
+        let output = r#"running 5 tests
+test foo::test_a ... ok
+test foo::test_b ... FAILED
+test foo::test_c ... ok
+
+failures:
+
+---- foo::test_b stdout ----
+thread 'foo::test_b' panicked at 'assert_eq!(1, 2)'
+
+failures:
+    foo::test_b
+
+test result: FAILED. 4 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(result.contains("FAILURES"));
+        assert!(result.contains("test_b"));
+        assert!(result.contains("test result:"));
+    }
+
+    #[test]
+    fn test_filter_cargo_test_failure_stdout_by_name() {
+        let output = r#"running 3 tests
+test tests::first ... FAILED
+test tests::second ... FAILED
+test tests::third ... FAILED
+
+failures:
+
+---- tests::first stdout ----
+loading fixture
+
+fixture: {"id": 1}
+thread 'tests::first' panicked at src/lib.rs:10:5:
+boom
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::second stdout ----
+thread 'tests::second' panicked at src/lib.rs:20:5:
+bang
+
+failures:
+    tests::first
+    tests::second
+    tests::third
+
+test result: FAILED. 0 passed; 3 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+"#;
+        let (failures, summary) = parse_libtest_text(output);
+        assert_eq!(
+            failures,
+            vec![
+                "---- tests::first stdout ----\nloading fixture\n\nfixture: {\"id\": 1}\nthread 'tests::first' panicked at src/lib.rs:10:5:\nboom",
+                "---- tests::second stdout ----\nthread 'tests::second' panicked at src/lib.rs:20:5:\nbang",
+                "---- tests::third stdout ----",
+            ]
+        );
+        assert_eq!(summary.len(), 1);
+
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(result.starts_with("FAILURES (3):"), "got: {}", result);
+        assert_eq!(result.matches("test result:").count(), 1);
+        assert!(!result.contains("RUST_BACKTRACE"));
+    }
+
+    #[test]
+    fn test_filter_cargo_test_multiline_assert_kept_whole() {
+        let output = r#"running 1 test
+test tests::parses_config ... FAILED
+
+failures:
+
+---- tests::parses_config stdout ----
+loading fixture tests/fixtures/config.toml
+parsed 12 keys, 3 sections, 0 warnings
+thread 'tests::parses_config' panicked at src/config.rs:88:9:
+assertion `left == right` failed
+  left: Config {
+    name: "app",
+    port: 8080,
+}
+ right: Config {
+    name: "app",
+    port: 9090,
+}
+
+failures:
+    tests::parses_config
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+"#;
+        let opts = FilterOptions {
+            failure_width: 120,
+            ..FilterOptions::default()
+        };
+        let result = filter_cargo_test(output, &opts);
+        assert!(
+            result.contains(
+                "assertion `left == right` failed\n  left: Config {\n    name: \"app\",\n    port: 8080,\n}\n right: Config {\n    name: \"app\",\n    port: 9090,\n}"
+            ),
+            "got: {}",
+            result
+        );
+        // The fixture chatter is what gets shortened
+        assert!(result.contains("1. ---- tests::parses_config stdout ----\n..."));
+    }
+
+    #[test]
+    fn test_truncate_failure() {
+        let failure = "---- t stdout ----\nthread 't' panicked at src/lib.rs:1:1:\nboom";
+        assert_eq!(truncate_failure(failure, 200), failure);
+        assert_eq!(
+            truncate_failure("---- t stdout ----\nno panic here", 10),
+            "---- t ..."
+        );
+        let long = format!("thread 't' panicked at src/lib.rs:1:1:\n{}", "x".repeat(50));
+        assert_eq!(
+            truncate_failure(&long, 20),
+            format!("thread 't' panick...\n{}...", "x".repeat(17))
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_test_condenses_backtrace() {
+        let output = r#"running 1 test
+test tests::parses ... FAILED
+
+failures:
+
+---- tests::parses stdout ----
+thread 'tests::parses' panicked at src/lib.rs:12:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+stack backtrace:
+   0: rust_begin_unwind
+             at /rustc/9b00956e56009bab2aa15d7bff10916599e3d6d6/library/std/src/panicking.rs:645:5
+   1: core::panicking::panic_fmt
+             at /rustc/9b00956e56009bab2aa15d7bff10916599e3d6d6/library/core/src/panicking.rs:72:14
+   2: core::panicking::assert_failed_inner
+   3: core::panicking::assert_failed
+             at /rustc/9b00956e56009bab2aa15d7bff10916599e3d6d6/library/core/src/panicking.rs:270:5
+   4: app::parse
+             at ./src/lib.rs:4:5
+   5: app::tests::parses
+             at ./src/lib.rs:12:9
+   6: app::tests::parses::{{closure}}
+             at ./src/lib.rs:11:16
+   7: core::ops::function::FnOnce::call_once
+             at /rustc/9b00956e56009bab2aa15d7bff10916599e3d6d6/library/core/src/ops/function.rs:250:5
+   8: serde_json::de::from_str
+             at /home/user/.cargo/registry/src/index.crates.io-6f17d22bba15001f/serde_json-1.0.114/src/de.rs:2676:5
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.
+
+failures:
+    tests::parses
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        let expected = "stack backtrace:
+  ... 4 std frames
+   4: app::parse
+             at ./src/lib.rs:4:5
+   5: app::tests::parses
+             at ./src/lib.rs:12:9
+   6: app::tests::parses::{{closure}}
+             at ./src/lib.rs:11:16
+  ... 2 std frames";
+        assert!(result.contains(expected), "got: {}", result);
+        assert!(result.contains("right: 2\nstack backtrace:"));
+        assert!(!result.contains("rust_begin_unwind"));
+        assert!(!result.contains("Some details are omitted"));
+    }
+
+    #[test]
+    fn test_filter_cargo_test_doctest_failures() {
+        let output = r#"     Running unittests src/lib.rs (target/debug/deps/app-1a2b3c)
+
+running 3 tests
+test tests::adds ... ok
+test tests::subs ... FAILED
+test tests::muls ... ok
+
+failures:
+
+---- tests::subs stdout ----
+thread 'tests::subs' panicked at src/lib.rs:30:9:
+boom
+
+failures:
+    tests::subs
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+   Doc-tests app
+
+running 3 tests
+test src/lib.rs - add (line 5) ... FAILED
+test src/lib.rs - mul (line 20) ... FAILED
+test src/lib.rs - sub (line 14) ... ok
+
+failures:
+
+---- src/lib.rs - add (line 5) stdout ----
+error[E0425]: cannot find value `x` in this scope
+ --> src/lib.rs:7:1
+  |
+3 | x
+  | ^ not found in this scope
+
+error: aborting due to 1 previous error
+
+For more information about this error, try `rustc --explain E0425`.
+Couldn't compile the test.
+---- src/lib.rs - mul (line 20) stdout ----
+Test executable failed (exit status: 101).
+
+stderr:
+thread 'main' panicked at src/lib.rs:6:1:
+assertion `left == right` failed
+  left: 3
+ right: 4
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+
+failures:
+    src/lib.rs - add (line 5)
+    src/lib.rs - mul (line 20)
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.21s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(result.starts_with("FAILURES (1):"), "got: {}", result);
+        let expected = "DOCTEST FAILURES (2):
+═══════════════════════════════════════
+1. src/lib.rs:5 add
+   error[E0425]: cannot find value `x` in this scope
+   --> src/lib.rs:7:1
+   (example does not compile)
+2. src/lib.rs:20 mul
+   thread 'main' panicked at src/lib.rs:6:1:
+   assertion `left == right` failed
+   left: 3
+   right: 4
+";
+        assert!(result.contains(expected), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_test_multi_suite_all_pass() {
+        let output = r#"   Compiling rtk v0.5.0
+    Finished test [unoptimized + debuginfo] target(s) in 2.53s
+     Running unittests src/lib.rs (target/debug/deps/rtk-abc123)
+
+running 50 tests
+test result: ok. 50 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.45s
+
+     Running unittests src/main.rs (target/debug/deps/rtk-def456)
+
+running 30 tests
+test result: ok. 30 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.30s
+
+     Running tests/integration.rs (target/debug/deps/integration-ghi789)
+
+running 25 tests
+test result: ok. 25 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.25s
+
+   Doc-tests rtk
+
+running 32 tests
+test result: ok. 32 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.45s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(
+            result.contains("✓ cargo test: 137 passed (4 suites, 1.45s)"),
+            "Expected aggregated format, got: {}",
+            result
+        );
+        assert!(!result.contains("running"));
+    }
+
+    #[test]
+    fn test_filter_cargo_test_multi_suite_with_failures() {
+        let output = r#"     Running unittests src/lib.rs
+
+running 20 tests
+test result: ok. 20 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.10s
+
+     Running unittests src/main.rs
+
+running 15 tests
+test foo::test_bad ... FAILED
+
+failures:
+
+---- foo::test_bad stdout ----
+thread panicked at 'assertion failed'
+
+test result: FAILED. 14 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.05s
+
+     Running tests/integration.rs
+
+running 10 tests
+test result: ok. 10 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.02s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        // Should NOT aggregate when there are failures
+        assert!(result.contains("FAILURES"), "got: {}", result);
+        assert!(result.contains("test_bad"), "got: {}", result);
+        assert!(result.contains("test result:"), "got: {}", result);
+        // Should show individual summaries
+        assert!(result.contains("20 passed"), "got: {}", result);
+        assert!(result.contains("14 passed"), "got: {}", result);
+        assert!(result.contains("10 passed"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_filter_cargo_test_all_suites_zero_tests() {
+        let output = r#"     Running unittests src/empty1.rs
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+     Running unittests src/empty2.rs
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+     Running tests/empty3.rs
+
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(
+            result.contains("✓ cargo test: 0 passed (3 suites, 0.00s)"),
+            "Expected compact format for zero tests, got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_test_with_ignored_and_filtered() {
+        let output = r#"     Running unittests src/lib.rs
+
+running 50 tests
+test result: ok. 45 passed; 0 failed; 3 ignored; 0 measured; 2 filtered out; finished in 0.50s
+
+     Running tests/integration.rs
+
+running 20 tests
+test result: ok. 18 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.20s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(
+            result.contains("✓ cargo test: 63 passed, 5 ignored, 2 filtered out (2 suites, 0.70s)"),
+            "Expected compact format with ignored and filtered, got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_test_single_suite_compact() {
+        let output = r#"     Running unittests src/main.rs
+
+running 15 tests
+test result: ok. 15 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(
+            result.contains("✓ cargo test: 15 passed (1 suite, 0.01s)"),
+            "Expected singular 'suite', got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_test_regex_fallback() {
+        let output = r#"     Running unittests src/main.rs
+
+running 15 tests
+test result: MALFORMED LINE WITHOUT PROPER FORMAT
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        // Should fallback to original behavior (show line with checkmark)
+        assert!(
+            result.contains("✓ test result: MALFORMED"),
+            "Expected fallback format, got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_filter_cargo_test_routes_nextest_output() {
+        let output = r#"    Starting 3 tests across 1 binary
+        PASS [   0.003s] rtk foo::test_a
+        FAIL [   0.012s] rtk foo::test_b
+
+--- STDERR:              rtk foo::test_b ---
+thread 'foo::test_b' panicked at src/foo.rs:10:5
+
+        PASS [   0.004s] rtk foo::test_c
+────────────
+     Summary [   0.020s] 3 tests run: 2 passed, 1 failed, 0 skipped
+"#;
+        let result = filter_cargo_test(output, &FilterOptions::default());
+        assert!(
+            result.contains("FAIL [   0.012s] rtk foo::test_b"),
+            "got: {}",
+            result
+        );
+        assert!(
+            result.contains("cargo nextest: 2 passed, 1 failed"),
+            "got: {}",
+            result
+        );
+        assert!(!result.contains("test_a"));
+    }
+
+    #[test]
+    fn test_parse_failed_tests() {
+        let output = "running 3 tests\n\
+test foo::test_a ... ok\n\
+test foo::test_b ... FAILED\n\
+test bar::test_c ... FAILED\n\
+        FAIL [   0.012s] rtk baz::test_d\n\
+test result: FAILED. 1 passed; 2 failed; 0 ignored\n";
+        assert_eq!(
+            parse_failed_tests(output),
+            vec!["foo::test_b", "bar::test_c", "baz::test_d"]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+//! `rtk cargo metadata`: a digest of the `cargo metadata` JSON.
+//!
+//! Prints the root package, workspace size, direct dependency count and target
+//! directory; `--deps` lists the direct dependencies with their resolved versions.
+//! `--format json` passes the metadata through untouched for tooling.
+
+use crate::cargo_cmd::FilterOptions;
+use crate::cargo_report::OutputFormat;
+use crate::tracking;
+use crate::utils::{exit_code, normalize_newlines, strip_ansi};
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+pub fn run(args: &[String], deps: bool, opts: &FilterOptions, verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = opts.cargo_command();
+    cmd.arg("metadata");
+    // Without it cargo warns that the default format version may change
+    if !args.iter().any(|a| a.starts_with("--format-version")) {
+        cmd.args(["--format-version", "1"]);
+    }
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    if verbose > 0 {
+        eprintln!("Running: cargo metadata {}", args.join(" "));
+    }
+
+    let output = cmd.output().context("Failed to run cargo metadata")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let status = exit_code(&output.status);
+
+    let filtered = if opts.format == OutputFormat::Json || opts.raw {
+        stdout.trim_end().to_string()
+    } else {
+        match serde_json::from_str::<Value>(stdout.trim()) {
+            Ok(json) => format_digest(&json, deps),
+            // Not metadata (manifest error, not a cargo project): keep cargo's own words
+            Err(_) => normalize_newlines(&strip_ansi(&raw)).trim().to_string(),
+        }
+    };
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("cargo metadata {}", args.join(" ")),
+        &format!("rtk cargo metadata {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if status != 0 {
+        std::process::exit(status);
+    }
+
+    Ok(())
+}
+
+/// Direct dependency of a workspace member: name -> (version requirement, kind)
+type DirectDeps = BTreeMap<String, (String, String)>;
+
+/// One-line summary plus target dir, and the dependency list with `deps`
+fn format_digest(json: &Value, deps: bool) -> String {
+    let packages: Vec<&Value> = json
+        .get("packages")
+        .and_then(|p| p.as_array())
+        .map(|p| p.iter().collect())
+        .unwrap_or_default();
+    let members: Vec<&str> = json
+        .get("workspace_members")
+        .and_then(|m| m.as_array())
+        .map(|m| m.iter().filter_map(|id| id.as_str()).collect())
+        .unwrap_or_default();
+    let str_field = |pkg: &Value, key: &str| {
+        pkg.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let package_by_id = |id: &str| {
+        packages
+            .iter()
+            .find(|p| p.get("id").and_then(|v| v.as_str()) == Some(id))
+    };
+
+    let root = json
+        .pointer("/resolve/root")
+        .and_then(|r| r.as_str())
+        .and_then(package_by_id)
+        .or_else(|| match members.as_slice() {
+            [only] => package_by_id(only),
+            _ => None,
+        });
+    let name = match root {
+        Some(pkg) => format!("{} {}", str_field(pkg, "name"), str_field(pkg, "version")),
+        None => "virtual workspace".to_string(),
+    };
+
+    let member_names: Vec<String> = members
+        .iter()
+        .filter_map(|id| package_by_id(id))
+        .map(|pkg| str_field(pkg, "name"))
+        .collect();
+    let mut direct = DirectDeps::new();
+    for id in &members {
+        let Some(pkg) = package_by_id(id) else {
+            continue;
+        };
+        for dep in pkg
+            .get("dependencies")
+            .and_then(|d| d.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let dep_name = str_field(dep, "name");
+            // Path dependencies on other members are part of the workspace, not deps
+            if member_names.contains(&dep_name) {
+                continue;
+            }
+            let kind = dep
+                .get("kind")
+                .and_then(|k| k.as_str())
+                .unwrap_or("normal")
+                .to_string();
+            direct
+                .entry(dep_name)
+                .or_insert_with(|| (str_field(dep, "req"), kind));
+        }
+    }
+
+    let mut result = format!(
+        "cargo metadata: {} ({} workspace member{})\n",
+        name,
+        members.len(),
+        if members.len() == 1 { "" } else { "s" }
+    );
+    let resolved = packages.len().saturating_sub(members.len());
+    result.push_str(&format!("  dependencies: {} direct", direct.len()));
+    if resolved > 0 {
+        result.push_str(&format!(" ({} resolved packages)", resolved));
+    }
+    result.push('\n');
+    result.push_str(&format!(
+        "  target dir: {}",
+        json.get("target_directory")
+            .and_then(|t| t.as_str())
+            .unwrap_or("?")
+    ));
+
+    if deps {
+        for (dep_name, (req, kind)) in &direct {
+            // Resolved version when exactly one package by that name is in the graph
+            let versions: Vec<String> = packages
+                .iter()
+                .filter(|p| str_field(p, "name") == *dep_name)
+                .map(|p| str_field(p, "version"))
+                .collect();
+            let version = match versions.as_slice() {
+                [only] => only.clone(),
+                _ => req.clone(),
+            };
+            result.push_str(&format!("\n  {} {}", dep_name, version));
+            if kind != "normal" {
+                result.push_str(&format!(" ({})", kind));
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+  "packages": [
+    {
+      "name": "app", "version": "0.3.1", "id": "path+file:///home/user/app#0.3.1",
+      "dependencies": [
+        { "name": "serde", "req": "^1", "kind": null },
+        { "name": "anyhow", "req": "^1.0", "kind": null },
+        { "name": "tempfile", "req": "^3", "kind": "dev" },
+        { "name": "app-core", "req": "*", "kind": null }
+      ]
+    },
+    {
+      "name": "app-core", "version": "0.3.1", "id": "path+file:///home/user/app/core#0.3.1",
+      "dependencies": [{ "name": "serde", "req": "^1.0.190", "kind": null }]
+    },
+    { "name": "serde", "version": "1.0.197", "id": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.197", "dependencies": [] },
+    { "name": "anyhow", "version": "1.0.81", "id": "registry+https://github.com/rust-lang/crates.io-index#anyhow@1.0.81", "dependencies": [] },
+    { "name": "tempfile", "version": "3.10.1", "id": "registry+https://github.com/rust-lang/crates.io-index#tempfile@3.10.1", "dependencies": [] },
+    { "name": "fastrand", "version": "2.0.2", "id": "registry+https://github.com/rust-lang/crates.io-index#fastrand@2.0.2", "dependencies": [] }
+  ],
+  "workspace_members": ["path+file:///home/user/app#0.3.1", "path+file:///home/user/app/core#0.3.1"],
+  "resolve": { "nodes": [], "root": "path+file:///home/user/app#0.3.1" },
+  "target_directory": "/home/user/app/target",
+  "version": 1,
+  "workspace_root": "/home/user/app"
+}"#;
+
+    #[test]
+    fn test_format_digest() {
+        let json: Value = serde_json::from_str(METADATA).unwrap();
+        assert_eq!(
+            format_digest(&json, false),
+            "cargo metadata: app 0.3.1 (2 workspace members)\n  dependencies: 3 direct (4 resolved packages)\n  target dir: /home/user/app/target"
+        );
+    }
+
+    #[test]
+    fn test_format_digest_deps() {
+        let json: Value = serde_json::from_str(METADATA).unwrap();
+        let result = format_digest(&json, true);
+        assert!(
+            result.ends_with("\n  anyhow 1.0.81\n  serde 1.0.197\n  tempfile 3.10.1 (dev)"),
+            "got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_format_digest_virtual_workspace_no_deps() {
+        let json: Value = serde_json::from_str(
+            r#"{"packages": [
+                {"name": "a", "version": "0.1.0", "id": "a", "dependencies": []},
+                {"name": "b", "version": "0.1.0", "id": "b", "dependencies": []}
+            ], "workspace_members": ["a", "b"], "resolve": null, "target_directory": "/ws/target"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            format_digest(&json, false),
+            "cargo metadata: virtual workspace (2 workspace members)\n  dependencies: 0 direct\n  target dir: /ws/target"
+        );
+    }
+}
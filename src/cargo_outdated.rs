@@ -0,0 +1,235 @@
+//! `rtk cargo outdated`: only the dependencies that are behind, one line each.
+//!
+//! Parses the cargo-outdated table (`Name Project Compat Latest Kind Platform`), where
+//! `---` means "nothing newer", and prints `serde 1.0.100 -> 1.0.200 (compat) / 1.1.0 (latest)`.
+
+use crate::cargo_cmd::FilterOptions;
+use crate::tracking;
+use crate::utils::{exit_code, normalize_newlines, strip_ansi};
+use anyhow::{Context, Result};
+
+/// One row of the cargo-outdated table
+#[derive(Debug, Clone, PartialEq)]
+struct Dependency {
+    name: String,
+    project: String,
+    /// Newest semver-compatible version, `None` for `---`
+    compat: Option<String>,
+    /// Newest version overall, `None` for `---`
+    latest: Option<String>,
+}
+
+impl Dependency {
+    fn is_outdated(&self) -> bool {
+        [&self.compat, &self.latest]
+            .iter()
+            .any(|v| v.as_deref().is_some_and(|v| v != self.project))
+    }
+
+    /// Whether the latest version is a semver-breaking upgrade (`0.x` minors count)
+    fn is_major(&self) -> bool {
+        let Some(latest) = self.latest.as_deref() else {
+            return false;
+        };
+        breaking_part(latest).is_some_and(|part| Some(part) != breaking_part(&self.project))
+    }
+}
+
+/// The version components that define compatibility: `1` for `1.2.3`, `0.4` for `0.4.1`
+fn breaking_part(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major = parts.next()?;
+    if major == "0" {
+        Some(format!("0.{}", parts.next()?))
+    } else {
+        Some(major.to_string())
+    }
+}
+
+pub fn run(args: &[String], major_only: bool, opts: &FilterOptions, verbose: u8) -> Result<()> {
+    opts.reject_json("cargo outdated")?;
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = opts.cargo_command();
+    cmd.arg("outdated");
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    if verbose > 0 {
+        eprintln!("Running: cargo outdated {}", args.join(" "));
+    }
+
+    let output = cmd.output().context("Failed to run cargo outdated")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let status = exit_code(&output.status);
+    let clean = normalize_newlines(&strip_ansi(&stdout));
+
+    let filtered = match parse_table(&clean) {
+        Some(deps) => format_outdated(&deps, major_only, opts),
+        None if clean.contains("up to date") => {
+            format!(
+                "{} cargo outdated: all dependencies up to date",
+                opts.theme.ok
+            )
+        }
+        // Not a cargo-outdated table (e.g. not installed): keep cargo's own words
+        None => normalize_newlines(&strip_ansi(&raw)).trim().to_string(),
+    };
+    println!("{}", opts.render(&filtered));
+
+    timer.track(
+        &format!("cargo outdated {}", args.join(" ")),
+        &format!("rtk cargo outdated {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if status != 0 {
+        std::process::exit(status);
+    }
+
+    Ok(())
+}
+
+/// Rows of every table in the output (workspaces print one per member); `None` without one
+fn parse_table(output: &str) -> Option<Vec<Dependency>> {
+    let mut deps: Vec<Dependency> = Vec::new();
+    let mut in_table = false;
+    let mut seen_header = false;
+
+    for line in output.lines() {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        match columns.as_slice() {
+            ["Name", "Project", "Compat", "Latest", ..] => {
+                in_table = true;
+                seen_header = true;
+            }
+            [] => in_table = false,
+            [first, ..] if first.starts_with("----") => {}
+            [name, project, compat, latest, ..] if in_table => {
+                let version = |v: &str| (v != "---").then(|| v.to_string());
+                let dep = Dependency {
+                    name: name.to_string(),
+                    project: project.to_string(),
+                    compat: version(compat),
+                    latest: version(latest),
+                };
+                // A dependency shared by workspace members is listed once
+                if !deps.contains(&dep) {
+                    deps.push(dep);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    seen_header.then_some(deps)
+}
+
+/// `serde 1.0.100 -> 1.0.200 (compat) / 1.1.0 (latest)`
+fn format_dependency(dep: &Dependency) -> String {
+    match (dep.compat.as_deref(), dep.latest.as_deref()) {
+        (Some(compat), Some(latest)) if compat == latest => {
+            format!("{} {} -> {}", dep.name, dep.project, latest)
+        }
+        (Some(compat), Some(latest)) if compat != dep.project => format!(
+            "{} {} -> {} (compat) / {} (latest)",
+            dep.name, dep.project, compat, latest
+        ),
+        (Some(compat), None) => format!("{} {} -> {} (compat)", dep.name, dep.project, compat),
+        (_, Some(latest)) => format!("{} {} -> {} (latest)", dep.name, dep.project, latest),
+        (_, None) => format!("{} {}", dep.name, dep.project),
+    }
+}
+
+fn format_outdated(deps: &[Dependency], major_only: bool, opts: &FilterOptions) -> String {
+    let outdated: Vec<&Dependency> = deps
+        .iter()
+        .filter(|d| d.is_outdated() && (!major_only || d.is_major()))
+        .collect();
+    let scope = if major_only { " (major)" } else { "" };
+
+    if outdated.is_empty() {
+        return format!(
+            "{} cargo outdated: 0 of {} dependencies outdated{}",
+            opts.theme.ok,
+            deps.len(),
+            scope
+        );
+    }
+
+    let mut result = format!(
+        "cargo outdated: {} of {} dependencies outdated{}\n",
+        outdated.len(),
+        deps.len(),
+        scope
+    );
+    for dep in &outdated {
+        result.push_str(&format_dependency(dep));
+        result.push('\n');
+    }
+    result.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: &str = "Name             Project  Compat   Latest   Kind         Platform
+----             -------  ------   ------   ----         --------
+anyhow           1.0.70   1.0.86   1.0.86   Normal       ---
+clap             4.0.0    ---      ---      Normal       ---
+rand             0.7.3    ---      0.8.5    Normal       ---
+serde            1.0.100  1.0.200  1.1.0    Normal       ---
+toml             0.5.11   0.5.11   0.8.19   Normal       ---
+";
+
+    #[test]
+    fn test_parse_table() {
+        let deps = parse_table(TABLE).unwrap();
+        assert_eq!(deps.len(), 5);
+        assert_eq!(
+            deps[2],
+            Dependency {
+                name: "rand".to_string(),
+                project: "0.7.3".to_string(),
+                compat: None,
+                latest: Some("0.8.5".to_string()),
+            }
+        );
+        assert!(!deps[1].is_outdated());
+        assert!(parse_table("error: no such command: `outdated`\n").is_none());
+    }
+
+    #[test]
+    fn test_format_outdated() {
+        let deps = parse_table(TABLE).unwrap();
+        assert_eq!(
+            format_outdated(&deps, false, &FilterOptions::default()),
+            "cargo outdated: 4 of 5 dependencies outdated\n\
+             anyhow 1.0.70 -> 1.0.86\n\
+             rand 0.7.3 -> 0.8.5 (latest)\n\
+             serde 1.0.100 -> 1.0.200 (compat) / 1.1.0 (latest)\n\
+             toml 0.5.11 -> 0.8.19 (latest)"
+        );
+    }
+
+    #[test]
+    fn test_major_only() {
+        let deps = parse_table(TABLE).unwrap();
+        assert_eq!(
+            format_outdated(&deps, true, &FilterOptions::default()),
+            "cargo outdated: 2 of 5 dependencies outdated (major)\n\
+             rand 0.7.3 -> 0.8.5 (latest)\n\
+             toml 0.5.11 -> 0.8.19 (latest)"
+        );
+        let current = &deps[1..2];
+        assert_eq!(
+            format_outdated(current, false, &FilterOptions::default()),
+            "✓ cargo outdated: 0 of 1 dependencies outdated"
+        );
+    }
+}
@@ -0,0 +1,986 @@
+//! Structured cargo diagnostics and machine-readable output formats.
+//!
+//! The text filters in `cargo_cmd` produce a compact human summary; this module
+//! parses the same (ANSI-stripped) output into canonical `LintIssue`s so they can
+//! be re-emitted as CI annotations, SARIF or a markdown PR comment.
+
+use crate::parser::{LintIssue, LintSeverity};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Output format for cargo commands (`--format`)
+#[derive(Debug, Clone, Copy, PartialEq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Compact human summary only
+    #[default]
+    Text,
+    /// Human summary followed by GitHub Actions `::error`/`::warning` workflow commands
+    Github,
+    /// A single SARIF 2.1.0 document instead of the summary (for code scanning uploads)
+    Sarif,
+    /// A single [`JsonReport`] document: counts, summary line and every diagnostic
+    /// (`cargo metadata` prints cargo's own JSON instead)
+    Json,
+    /// GitHub-flavored markdown for PR comments: summary, diagnostic bullets, collapsed details
+    #[value(alias = "md")]
+    Markdown,
+    /// One JSON object per diagnostic, streamed as cargo reports them, then a summary record
+    #[value(alias = "json-lines")]
+    Jsonl,
+    /// A single [`CountsRecord`] with counts, timing and sizes only (for dashboards)
+    JsonSummary,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "github" => Ok(OutputFormat::Github),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "json" => Ok(OutputFormat::Json),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            "jsonl" | "json-lines" => Ok(OutputFormat::Jsonl),
+            "json-summary" => Ok(OutputFormat::JsonSummary),
+            _ => Err(format!(
+                "Unknown output format: {} (text, github, sarif, json, markdown, jsonl, json-summary)",
+                s
+            )),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Format used when `--format` is not given: `github` inside GitHub Actions, text otherwise
+    pub fn detect() -> Self {
+        if std::env::var("GITHUB_ACTIONS").ok().as_deref() == Some("true") {
+            OutputFormat::Github
+        } else {
+            OutputFormat::Text
+        }
+    }
+
+    /// Whether the output is a standalone document that must not be mixed with hints
+    pub fn is_document(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Sarif
+                | OutputFormat::Json
+                | OutputFormat::Markdown
+                | OutputFormat::Jsonl
+                | OutputFormat::JsonSummary
+        )
+    }
+}
+
+/// Parse rustc/clippy diagnostics (`error[E0308]: ...` + `--> file:line:col`)
+///
+/// Summary lines such as `error: could not compile` or `warning: 3 warnings emitted`
+/// are skipped. Diagnostics without a `-->` span keep an empty `file_path`.
+pub fn parse_diagnostics(output: &str) -> Vec<LintIssue> {
+    static HEADER_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static SPAN_RE: OnceLock<regex::Regex> = OnceLock::new();
+    static LINT_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let header_re = HEADER_RE.get_or_init(|| {
+        regex::Regex::new(r"^(error|warning)(?:\[([^\]]+)\])?: (.+)$")
+            .expect("invalid header regex")
+    });
+    let span_re = SPAN_RE.get_or_init(|| {
+        regex::Regex::new(r"^\s*--> (.+?):(\d+):(\d+)\s*$").expect("invalid span regex")
+    });
+    let lint_re = LINT_RE.get_or_init(|| {
+        regex::Regex::new(r"#\[(?:warn|deny|forbid)\(([\w:]+)\)\]").expect("invalid lint regex")
+    });
+
+    let mut issues: Vec<LintIssue> = Vec::new();
+    let mut in_block = false;
+
+    for line in output.lines() {
+        if let Some(caps) = header_re.captures(line) {
+            let message = caps[3].trim();
+            if is_summary_message(message) {
+                in_block = false;
+                continue;
+            }
+            issues.push(LintIssue {
+                file_path: String::new(),
+                line: 0,
+                column: 0,
+                severity: if &caps[1] == "error" {
+                    LintSeverity::Error
+                } else {
+                    LintSeverity::Warning
+                },
+                rule_id: caps
+                    .get(2)
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_default(),
+                message: message.to_string(),
+            });
+            in_block = true;
+            continue;
+        }
+
+        if !in_block {
+            continue;
+        }
+        let Some(issue) = issues.last_mut() else {
+            continue;
+        };
+
+        if issue.file_path.is_empty() {
+            if let Some(caps) = span_re.captures(line) {
+                issue.file_path = caps[1].to_string();
+                issue.line = caps[2].parse().unwrap_or(0);
+                issue.column = caps[3].parse().unwrap_or(0);
+                continue;
+            }
+        }
+        if issue.rule_id.is_empty() {
+            if let Some(caps) = lint_re.captures(line) {
+                issue.rule_id = caps[1].to_string();
+            }
+        }
+    }
+
+    issues
+}
+
+/// Cargo's own trailer lines, which are not diagnostics
+fn is_summary_message(message: &str) -> bool {
+    message.starts_with("could not compile")
+        || message.starts_with("aborting due to")
+        || message.starts_with("build failed")
+        || message.starts_with("Compilation failed")
+        || (message.contains("warning") && message.contains("emitted"))
+        || (message.contains(" generated ") && message.contains("warning"))
+}
+
+/// Render issues as GitHub Actions workflow commands, one per line
+pub fn github_annotations(issues: &[LintIssue]) -> String {
+    issues
+        .iter()
+        .map(github_annotation)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn github_annotation(issue: &LintIssue) -> String {
+    let level = match issue.severity {
+        LintSeverity::Error => "error",
+        LintSeverity::Warning => "warning",
+        LintSeverity::Info => "notice",
+    };
+
+    let mut props = Vec::new();
+    if !issue.file_path.is_empty() {
+        props.push(format!("file={}", escape_property(&issue.file_path)));
+        props.push(format!("line={}", issue.line));
+        props.push(format!("col={}", issue.column));
+    }
+    if !issue.rule_id.is_empty() {
+        props.push(format!("title={}", escape_property(&issue.rule_id)));
+    }
+
+    if props.is_empty() {
+        format!("::{}::{}", level, escape_data(&issue.message))
+    } else {
+        format!(
+            "::{} {}::{}",
+            level,
+            props.join(","),
+            escape_data(&issue.message)
+        )
+    }
+}
+
+/// Escape a workflow command message
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Serialize issues as a minimal SARIF 2.1.0 log with one run for `tool`
+pub fn sarif(issues: &[LintIssue], tool: &str) -> String {
+    let mut rules: Vec<&str> = issues
+        .iter()
+        .map(|i| i.rule_id.as_str())
+        .filter(|id| !id.is_empty())
+        .collect();
+    rules.sort_unstable();
+    rules.dedup();
+
+    let results: Vec<serde_json::Value> = issues
+        .iter()
+        .map(|issue| {
+            let mut result = json!({
+                "level": match issue.severity {
+                    LintSeverity::Error => "error",
+                    LintSeverity::Warning => "warning",
+                    LintSeverity::Info => "note",
+                },
+                "message": { "text": issue.message },
+            });
+            if !issue.rule_id.is_empty() {
+                result["ruleId"] = json!(issue.rule_id);
+            }
+            if !issue.file_path.is_empty() {
+                result["locations"] = json!([{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": issue.file_path },
+                        "region": {
+                            "startLine": issue.line.max(1),
+                            "startColumn": issue.column.max(1),
+                        },
+                    },
+                }]);
+            }
+            result
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": tool,
+                    "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+/// Stable identity of a diagnostic across runs: level, code, file and message.
+///
+/// Line numbers are left out so an error doesn't count as "new" just because
+/// code above it moved.
+pub fn fingerprint(issue: &LintIssue) -> String {
+    let level = match issue.severity {
+        LintSeverity::Error => "error",
+        LintSeverity::Warning => "warning",
+        LintSeverity::Info => "note",
+    };
+    let code = if issue.rule_id.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", issue.rule_id)
+    };
+    let file = if issue.file_path.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", issue.file_path)
+    };
+    format!("{}{}{}: {}", level, code, file, issue.message)
+}
+
+/// Diagnostics compared with the previous run (`--diff`)
+#[derive(Debug, PartialEq)]
+pub struct DiagnosticDiff {
+    pub new: Vec<String>,
+    pub fixed: Vec<String>,
+    pub unchanged: usize,
+}
+
+pub fn diff_fingerprints(previous: &[String], current: &[String]) -> DiagnosticDiff {
+    let new: Vec<String> = current
+        .iter()
+        .filter(|f| !previous.contains(f))
+        .cloned()
+        .collect();
+    let fixed: Vec<String> = previous
+        .iter()
+        .filter(|f| !current.contains(f))
+        .cloned()
+        .collect();
+    DiagnosticDiff {
+        unchanged: current.len() - new.len(),
+        new,
+        fixed,
+    }
+}
+
+/// `diff: +2 new, -3 fixed, 5 unchanged` followed by the new (+) and fixed (-) entries
+pub fn format_diff(diff: &DiagnosticDiff, max_items: usize) -> String {
+    let mut out = format!(
+        "diff: +{} new, -{} fixed, {} unchanged",
+        diff.new.len(),
+        diff.fixed.len(),
+        diff.unchanged
+    );
+    for (sign, items) in [("+", &diff.new), ("-", &diff.fixed)] {
+        for item in items.iter().take(max_items) {
+            out.push_str(&format!("\n  {} {}", sign, item));
+        }
+        if items.len() > max_items {
+            out.push_str(&format!(
+                "\n  {} ... +{} more",
+                sign,
+                items.len() - max_items
+            ));
+        }
+    }
+    out
+}
+
+/// Apply the output format to a filtered summary
+///
+/// `tool` names the SARIF driver (`clippy` for clippy runs, `cargo` otherwise).
+pub fn format_output(format: OutputFormat, tool: &str, filtered: &str, clean_raw: &str) -> String {
+    match format {
+        OutputFormat::Text => filtered.to_string(),
+        OutputFormat::Github => {
+            let annotations = github_annotations(&parse_diagnostics(clean_raw));
+            if annotations.is_empty() {
+                filtered.to_string()
+            } else {
+                format!("{}\n{}", filtered, annotations)
+            }
+        }
+        OutputFormat::Sarif => sarif(&parse_diagnostics(clean_raw), tool),
+        OutputFormat::Json => json_report(filtered, &parse_diagnostics(clean_raw), tool),
+        OutputFormat::Markdown => markdown(filtered, &parse_diagnostics(clean_raw), tool),
+        OutputFormat::Jsonl => {
+            let issues = parse_diagnostics(clean_raw);
+            let mut lines: Vec<String> = issues.iter().map(jsonl_record).collect();
+            lines.push(jsonl_summary(filtered, &issues, tool));
+            lines.join("\n")
+        }
+        // The record needs the command and its timing, so the caller builds it with
+        // `counts_record` from this text summary
+        OutputFormat::JsonSummary => filtered.to_string(),
+    }
+}
+
+/// `--format json-summary`: one line per run for time-series ingestion
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct CountsRecord {
+    /// Cargo subcommand (`build`, `test`, ...)
+    pub command: String,
+    pub errors: usize,
+    pub warnings: usize,
+    /// `Compiling`/`Checking` lines
+    pub compiled: usize,
+    pub elapsed_ms: u64,
+    /// Size of cargo's output (color codes stripped), the input side of the tracked savings
+    pub bytes_raw: usize,
+    /// Size of the text summary rtk would have printed, the output side
+    pub bytes_filtered: usize,
+}
+
+/// [`CountsRecord`] line for a run: `raw` is cargo's output, `filtered` the text summary
+pub fn counts_record(command: &str, raw: &str, filtered: &str, elapsed_ms: u64) -> String {
+    let issues = parse_diagnostics(raw);
+    let count = |severity: LintSeverity| issues.iter().filter(|i| i.severity == severity).count();
+    let record = CountsRecord {
+        command: command.to_string(),
+        errors: count(LintSeverity::Error),
+        warnings: count(LintSeverity::Warning),
+        compiled: raw
+            .lines()
+            .map(str::trim_start)
+            .filter(|line| line.starts_with("Compiling ") || line.starts_with("Checking "))
+            .count(),
+        elapsed_ms,
+        bytes_raw: raw.len(),
+        bytes_filtered: filtered.len(),
+    };
+    serde_json::to_string(&record).unwrap_or_default()
+}
+
+/// Bumped on every breaking change to the `--format jsonl` records (removed or renamed
+/// fields, changed types); adding an optional field is not breaking
+pub const JSONL_SCHEMA_VERSION: u32 = 1;
+
+/// One `--format jsonl` diagnostic:
+/// `{"level":"error","code":"E0308","file":"src/main.rs","line":4,"message":"..."}`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticRecord {
+    /// `error`, `warning` or `note`
+    pub level: String,
+    /// Error code or lint name (`E0308`, `clippy::needless_return`)
+    pub code: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// The closing `{"type":"summary",...}` record of `--format jsonl`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SummaryRecord {
+    /// Always `summary`; diagnostic records have no `type`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub schema_version: u32,
+    pub tool: String,
+    pub errors: usize,
+    pub warnings: usize,
+    /// First line of the text summary
+    pub summary: String,
+}
+
+impl From<&LintIssue> for DiagnosticRecord {
+    fn from(issue: &LintIssue) -> Self {
+        let optional = |s: &str| (!s.is_empty()).then(|| s.to_string());
+        DiagnosticRecord {
+            level: match issue.severity {
+                LintSeverity::Error => "error",
+                LintSeverity::Warning => "warning",
+                LintSeverity::Info => "note",
+            }
+            .to_string(),
+            code: optional(&issue.rule_id),
+            file: optional(&issue.file_path),
+            line: (issue.line > 0).then_some(issue.line),
+            message: issue.message.clone(),
+        }
+    }
+}
+
+/// Bumped on every breaking change to the `--format json` document, like
+/// [`JSONL_SCHEMA_VERSION`] for the jsonl records
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// The `--format json` document:
+/// `{"schema_version":1,"tool":"cargo","errors":1,"warnings":0,"summary":"...","diagnostics":[...]}`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct JsonReport {
+    pub schema_version: u32,
+    pub tool: String,
+    pub errors: usize,
+    pub warnings: usize,
+    /// First line of the text summary
+    pub summary: String,
+    pub diagnostics: Vec<DiagnosticRecord>,
+}
+
+/// [`JsonReport`] for a run: counts plus the filter's summary line and every diagnostic
+pub fn json_report(filtered: &str, issues: &[LintIssue], tool: &str) -> String {
+    let count = |severity: LintSeverity| issues.iter().filter(|i| i.severity == severity).count();
+    let report = JsonReport {
+        schema_version: JSON_SCHEMA_VERSION,
+        tool: tool.to_string(),
+        errors: count(LintSeverity::Error),
+        warnings: count(LintSeverity::Warning),
+        summary: filtered.lines().next().unwrap_or("").trim().to_string(),
+        diagnostics: issues.iter().map(DiagnosticRecord::from).collect(),
+    };
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+/// One diagnostic as a [`DiagnosticRecord`] line
+pub fn jsonl_record(issue: &LintIssue) -> String {
+    serde_json::to_string(&DiagnosticRecord::from(issue)).unwrap_or_default()
+}
+
+/// Closing [`SummaryRecord`] line: counts plus the filter's summary line
+pub fn jsonl_summary(filtered: &str, issues: &[LintIssue], tool: &str) -> String {
+    let count = |severity: LintSeverity| issues.iter().filter(|i| i.severity == severity).count();
+    let record = SummaryRecord {
+        kind: "summary".to_string(),
+        schema_version: JSONL_SCHEMA_VERSION,
+        tool: tool.to_string(),
+        errors: count(LintSeverity::Error),
+        warnings: count(LintSeverity::Warning),
+        summary: filtered.lines().next().unwrap_or("").trim().to_string(),
+    };
+    serde_json::to_string(&record).unwrap_or_default()
+}
+
+/// JSON Schema of a [`DiagnosticRecord`], shared by the jsonl and json schemas
+fn diagnostic_schema() -> serde_json::Value {
+    let nullable = |ty: &str| json!({ "type": [ty, "null"] });
+    json!({
+        "title": "diagnostic",
+        "type": "object",
+        "properties": {
+            "level": { "enum": ["error", "warning", "note"] },
+            "code": nullable("string"),
+            "file": nullable("string"),
+            "line": nullable("integer"),
+            "message": { "type": "string" },
+        },
+        "required": ["level", "code", "file", "line", "message"],
+    })
+}
+
+/// JSON Schema of the `--format json` document (`rtk schema json`)
+pub fn json_schema() -> String {
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "rtk --format json report",
+        "version": JSON_SCHEMA_VERSION,
+        "type": "object",
+        "properties": {
+            "schema_version": { "const": JSON_SCHEMA_VERSION },
+            "tool": { "type": "string" },
+            "errors": { "type": "integer", "minimum": 0 },
+            "warnings": { "type": "integer", "minimum": 0 },
+            "summary": { "type": "string" },
+            "diagnostics": { "type": "array", "items": diagnostic_schema() },
+        },
+        "required": ["schema_version", "tool", "errors", "warnings", "summary", "diagnostics"],
+    });
+    serde_json::to_string_pretty(&schema).unwrap_or_default()
+}
+
+/// JSON Schema for a `--format jsonl` line (`rtk schema`): either record type
+pub fn jsonl_schema() -> String {
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "rtk --format jsonl record",
+        "version": JSONL_SCHEMA_VERSION,
+        "oneOf": [
+            diagnostic_schema(),
+            {
+                "title": "summary",
+                "type": "object",
+                "properties": {
+                    "type": { "const": "summary" },
+                    "schema_version": { "const": JSONL_SCHEMA_VERSION },
+                    "tool": { "type": "string" },
+                    "errors": { "type": "integer", "minimum": 0 },
+                    "warnings": { "type": "integer", "minimum": 0 },
+                    "summary": { "type": "string" },
+                },
+                "required": ["type", "schema_version", "tool", "errors", "warnings", "summary"],
+            },
+        ],
+    });
+    serde_json::to_string_pretty(&schema).unwrap_or_default()
+}
+
+/// Turns cargo's stderr into [`jsonl_record`]s line by line, as each diagnostic completes.
+///
+/// A diagnostic ends at the blank line rustc prints after it (or at the next header),
+/// so records reach a consumer while a long build is still running.
+#[derive(Default)]
+pub struct JsonlStream {
+    block: String,
+}
+
+impl JsonlStream {
+    /// Feed one line (without its newline); returns the records completed by it
+    pub fn push_line(&mut self, line: &str) -> Vec<String> {
+        let is_header = line.starts_with("error") || line.starts_with("warning");
+        let mut records = Vec::new();
+        if line.trim().is_empty() || is_header {
+            records = self.finish();
+        }
+        if is_header || !self.block.is_empty() {
+            self.block.push_str(line);
+            self.block.push('\n');
+        }
+        records
+    }
+
+    /// Records for whatever diagnostic is still open at the end of the output
+    pub fn finish(&mut self) -> Vec<String> {
+        let block = std::mem::take(&mut self.block);
+        parse_diagnostics(&block).iter().map(jsonl_record).collect()
+    }
+}
+
+/// Markdown for a PR comment: the summary line fenced, then a rule table for clippy or a
+/// bullet per diagnostic otherwise, then the rest of the filtered output in `<details>`
+pub fn markdown(filtered: &str, issues: &[LintIssue], tool: &str) -> String {
+    let (summary, body) = filtered.split_once('\n').unwrap_or((filtered, ""));
+    let mut out = format!("```\n{}\n```", summary.trim());
+
+    if tool == "clippy" && !issues.is_empty() {
+        out.push_str("\n\n");
+        out.push_str(&markdown_rule_table(issues));
+    } else if !issues.is_empty() {
+        out.push('\n');
+        for issue in issues {
+            let level = match issue.severity {
+                LintSeverity::Error => "",
+                LintSeverity::Warning => "warning: ",
+                LintSeverity::Info => "note: ",
+            };
+            out.push_str(&format!(
+                "\n- {} — {}{}",
+                markdown_location(issue),
+                level,
+                issue.message
+            ));
+        }
+    }
+
+    let body = body.trim();
+    if !body.is_empty() {
+        out.push_str(&format!(
+            "\n\n<details>\n<summary>Full output</summary>\n\n```\n{}\n```\n\n</details>",
+            body
+        ));
+    }
+    out
+}
+
+/// `` `src/foo.rs:10` ``, or `(no location)` for linker/manifest errors
+fn markdown_location(issue: &LintIssue) -> String {
+    if issue.file_path.is_empty() {
+        "(no location)".to_string()
+    } else {
+        format!("`{}:{}`", issue.file_path, issue.line)
+    }
+}
+
+/// `| Rule | Count | Locations |`, most frequent rule first, three locations per rule
+fn markdown_rule_table(issues: &[LintIssue]) -> String {
+    let mut rules: Vec<(&str, Vec<&LintIssue>)> = Vec::new();
+    for issue in issues {
+        let rule = if issue.rule_id.is_empty() {
+            issue.message.as_str()
+        } else {
+            issue.rule_id.as_str()
+        };
+        match rules.iter_mut().find(|(r, _)| *r == rule) {
+            Some((_, group)) => group.push(issue),
+            None => rules.push((rule, vec![issue])),
+        }
+    }
+    rules.sort_by_key(|(_, group)| std::cmp::Reverse(group.len()));
+
+    let mut table = "| Rule | Count | Locations |\n| --- | ---: | --- |".to_string();
+    for (rule, group) in &rules {
+        let mut locations: Vec<String> =
+            group.iter().take(3).map(|i| markdown_location(i)).collect();
+        if group.len() > 3 {
+            locations.push(format!("+{} more", group.len() - 3));
+        }
+        table.push_str(&format!(
+            "\n| `{}` | {} | {} |",
+            rule.replace('|', "\\|"),
+            group.len(),
+            locations.join(", ").replace('|', "\\|")
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUILD_OUTPUT: &str = r#"   Compiling rtk v0.5.0
+error[E0308]: mismatched types
+  --> src/foo.rs:10:5
+   |
+10 |     "hello"
+   |     ^^^^^^^ expected `i32`, found `&str`
+
+warning: unneeded `return` statement
+ --> src/bar.rs:3:5
+  |
+3 |     return x;
+  |     ^^^^^^^^
+  |
+  = note: `#[warn(clippy::needless_return)]` on by default
+
+warning: `rtk` (bin "rtk") generated 1 warning
+error: could not compile `rtk` (bin "rtk") due to 1 previous error
+"#;
+
+    #[test]
+    fn test_parse_diagnostics_spans_and_codes() {
+        let issues = parse_diagnostics(BUILD_OUTPUT);
+        assert_eq!(issues.len(), 2);
+
+        assert_eq!(issues[0].severity, LintSeverity::Error);
+        assert_eq!(issues[0].rule_id, "E0308");
+        assert_eq!(issues[0].file_path, "src/foo.rs");
+        assert_eq!((issues[0].line, issues[0].column), (10, 5));
+        assert_eq!(issues[0].message, "mismatched types");
+
+        assert_eq!(issues[1].severity, LintSeverity::Warning);
+        assert_eq!(issues[1].rule_id, "clippy::needless_return");
+        assert_eq!(issues[1].file_path, "src/bar.rs");
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        let out = github_annotations(&parse_diagnostics(BUILD_OUTPUT));
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines[0],
+            "::error file=src/foo.rs,line=10,col=5,title=E0308::mismatched types"
+        );
+        assert!(lines[1].starts_with("::warning file=src/bar.rs,line=3,col=5,"));
+        assert!(lines[1].contains("title=clippy%3A%3Aneedless_return"));
+    }
+
+    #[test]
+    fn test_github_annotation_without_span() {
+        let issues = parse_diagnostics("error: linking with `cc` failed: 100%\n");
+        assert_eq!(
+            github_annotations(&issues),
+            "::error::linking with `cc` failed: 100%25"
+        );
+    }
+
+    #[test]
+    fn test_format_output_text_is_unchanged() {
+        assert_eq!(
+            format_output(OutputFormat::Text, "cargo", "summary", BUILD_OUTPUT),
+            "summary"
+        );
+        let github = format_output(OutputFormat::Github, "cargo", "summary", BUILD_OUTPUT);
+        assert!(github.starts_with("summary\n::error "));
+    }
+
+    #[test]
+    fn test_markdown_build() {
+        let filtered = "cargo build: 1 errors, 1 warnings (1 crates)\n═══\nerror[E0308]: mismatched types\n  --> src/foo.rs:10:5";
+        assert_eq!(
+            format_output(OutputFormat::Markdown, "cargo", filtered, BUILD_OUTPUT),
+            "```\ncargo build: 1 errors, 1 warnings (1 crates)\n```\n\n- `src/foo.rs:10` — mismatched types\n- `src/bar.rs:3` — warning: unneeded `return` statement\n\n<details>\n<summary>Full output</summary>\n\n```\n═══\nerror[E0308]: mismatched types\n  --> src/foo.rs:10:5\n```\n\n</details>"
+        );
+        assert_eq!(
+            format_output(
+                OutputFormat::Markdown,
+                "cargo",
+                "✓ cargo build (3 crates compiled)",
+                ""
+            ),
+            "```\n✓ cargo build (3 crates compiled)\n```"
+        );
+    }
+
+    #[test]
+    fn test_markdown_clippy_table() {
+        let out = markdown(
+            "cargo clippy: 1 errors, 1 warnings",
+            &parse_diagnostics(BUILD_OUTPUT),
+            "clippy",
+        );
+        assert!(out.contains("| Rule | Count | Locations |\n| --- | ---: | --- |\n"));
+        assert!(out.contains("| `E0308` | 1 | `src/foo.rs:10` |"));
+        assert!(out.contains("| `clippy::needless_return` | 1 | `src/bar.rs:3` |"));
+    }
+
+    #[test]
+    fn test_jsonl_records() {
+        let out = format_output(
+            OutputFormat::Jsonl,
+            "clippy",
+            "cargo clippy: 1 errors, 1 warnings\n═══",
+            BUILD_OUTPUT,
+        );
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            r#"{"level":"error","code":"E0308","file":"src/foo.rs","line":10,"message":"mismatched types"}"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"{"level":"warning","code":"clippy::needless_return","file":"src/bar.rs","line":3,"message":"unneeded `return` statement"}"#
+        );
+        assert_eq!(
+            lines[2],
+            r#"{"type":"summary","schema_version":1,"tool":"clippy","errors":1,"warnings":1,"summary":"cargo clippy: 1 errors, 1 warnings"}"#
+        );
+        assert_eq!(
+            "json-lines".parse::<OutputFormat>(),
+            Ok(OutputFormat::Jsonl)
+        );
+    }
+
+    #[test]
+    fn test_counts_record() {
+        let raw = "   Compiling foo v0.1.0\n    Checking bar v0.2.0\nwarning: unused variable: `x`\n --> src/lib.rs:3:9\n\nerror[E0308]: mismatched types\n --> src/main.rs:4:5\n";
+        let filtered = "cargo build: 1 errors, 1 warnings (2 crates)";
+        let line = counts_record("build", raw, filtered, 1523);
+        let record: CountsRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(
+            record,
+            CountsRecord {
+                command: "build".to_string(),
+                errors: 1,
+                warnings: 1,
+                compiled: 2,
+                elapsed_ms: 1523,
+                bytes_raw: raw.len(),
+                bytes_filtered: filtered.len(),
+            }
+        );
+        assert!(line.starts_with(r#"{"command":"build","errors":1,"warnings":1,"compiled":2,"#));
+        assert_eq!(
+            "json-summary".parse::<OutputFormat>(),
+            Ok(OutputFormat::JsonSummary)
+        );
+        assert!(OutputFormat::JsonSummary.is_document());
+    }
+
+    #[test]
+    fn test_jsonl_schema_matches_records() {
+        let schema: serde_json::Value = serde_json::from_str(&jsonl_schema()).unwrap();
+        assert_eq!(schema["version"], JSONL_SCHEMA_VERSION);
+
+        let out = format_output(
+            OutputFormat::Jsonl,
+            "cargo",
+            "cargo build: 1 errors",
+            BUILD_OUTPUT,
+        );
+        let lines: Vec<&str> = out.lines().collect();
+        let diagnostic: DiagnosticRecord = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(diagnostic.code.as_deref(), Some("E0308"));
+        let summary: SummaryRecord = serde_json::from_str(lines.last().unwrap()).unwrap();
+        assert_eq!(summary.schema_version, JSONL_SCHEMA_VERSION);
+
+        // Every serialized field is declared and required, and nothing else is
+        for (line, variant) in [(lines[0], 0), (*lines.last().unwrap(), 1)] {
+            let record: serde_json::Value = serde_json::from_str(line).unwrap();
+            let mut keys: Vec<&str> = record
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(|k| k.as_str())
+                .collect();
+            let mut required: Vec<&str> = schema["oneOf"][variant]["required"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|k| k.as_str().unwrap())
+                .collect();
+            keys.sort_unstable();
+            required.sort_unstable();
+            assert_eq!(keys, required);
+        }
+    }
+
+    #[test]
+    fn test_json_report_matches_schema() {
+        let out = format_output(
+            OutputFormat::Json,
+            "cargo",
+            "cargo build: 1 errors, 1 warnings (2 crates)\n═══",
+            BUILD_OUTPUT,
+        );
+        let report: JsonReport = serde_json::from_str(&out).unwrap();
+        assert_eq!(report.schema_version, JSON_SCHEMA_VERSION);
+        assert_eq!((report.errors, report.warnings), (1, 1));
+        assert_eq!(
+            report.summary,
+            "cargo build: 1 errors, 1 warnings (2 crates)"
+        );
+        assert_eq!(report.diagnostics[0].code.as_deref(), Some("E0308"));
+
+        let schema: serde_json::Value = serde_json::from_str(&json_schema()).unwrap();
+        assert_eq!(schema["version"], JSON_SCHEMA_VERSION);
+        let document: serde_json::Value = serde_json::from_str(&out).unwrap();
+        let keys = |value: &serde_json::Value| {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort_unstable();
+            keys
+        };
+        let required = |value: &serde_json::Value| {
+            let mut keys: Vec<String> = value["required"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|k| k.as_str().unwrap().to_string())
+                .collect();
+            keys.sort_unstable();
+            keys
+        };
+        assert_eq!(keys(&document), required(&schema));
+        assert_eq!(
+            keys(&document["diagnostics"][0]),
+            required(&schema["properties"]["diagnostics"]["items"])
+        );
+    }
+
+    #[test]
+    fn test_jsonl_stream_emits_per_diagnostic() {
+        let mut stream = JsonlStream::default();
+        let mut emitted: Vec<(usize, String)> = Vec::new();
+        for (i, line) in BUILD_OUTPUT.lines().enumerate() {
+            emitted.extend(stream.push_line(line).into_iter().map(|r| (i, r)));
+        }
+        emitted.extend(stream.finish().into_iter().map(|r| (usize::MAX, r)));
+
+        let all = format_output(OutputFormat::Jsonl, "clippy", "", BUILD_OUTPUT);
+        let records: Vec<&str> = emitted.iter().map(|(_, r)| r.as_str()).collect();
+        assert_eq!(records, all.lines().take(2).collect::<Vec<_>>());
+        // The error is emitted at the blank line that closes it, before the warning starts
+        assert_eq!(emitted[0].0, 6);
+    }
+
+    #[test]
+    fn test_sarif_document() {
+        let doc: serde_json::Value =
+            serde_json::from_str(&sarif(&parse_diagnostics(BUILD_OUTPUT), "clippy")).unwrap();
+        assert_eq!(doc["version"], "2.1.0");
+        let run = &doc["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "clippy");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+
+        let first = &run["results"][0];
+        assert_eq!(first["ruleId"], "E0308");
+        assert_eq!(first["level"], "error");
+        assert_eq!(first["message"]["text"], "mismatched types");
+        let location = &first["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/foo.rs");
+        assert_eq!(location["region"]["startLine"], 10);
+        assert_eq!(location["region"]["startColumn"], 5);
+        assert_eq!(run["results"][1]["level"], "warning");
+    }
+
+    #[test]
+    fn test_sarif_empty_is_well_formed() {
+        let doc: serde_json::Value = serde_json::from_str(&sarif(&[], "cargo")).unwrap();
+        assert!(doc["runs"][0]["results"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_numbers() {
+        let moved = BUILD_OUTPUT.replace("src/foo.rs:10:5", "src/foo.rs:42:5");
+        let before: Vec<String> = parse_diagnostics(BUILD_OUTPUT)
+            .iter()
+            .map(fingerprint)
+            .collect();
+        let after: Vec<String> = parse_diagnostics(&moved).iter().map(fingerprint).collect();
+        assert_eq!(before, after);
+        assert_eq!(before[0], "error[E0308] src/foo.rs: mismatched types");
+    }
+
+    #[test]
+    fn test_diff_fingerprints() {
+        let previous = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let current = vec!["b".to_string(), "c".to_string(), "d".to_string()];
+        let diff = diff_fingerprints(&previous, &current);
+        assert_eq!(diff.new, vec!["d"]);
+        assert_eq!(diff.fixed, vec!["a"]);
+        assert_eq!(diff.unchanged, 2);
+        assert_eq!(
+            format_diff(&diff, 10),
+            "diff: +1 new, -1 fixed, 2 unchanged\n  + d\n  - a"
+        );
+    }
+
+    #[test]
+    fn test_output_format_parsing() {
+        assert_eq!("github".parse::<OutputFormat>(), Ok(OutputFormat::Github));
+        assert_eq!("TEXT".parse::<OutputFormat>(), Ok(OutputFormat::Text));
+        assert_eq!("sarif".parse::<OutputFormat>(), Ok(OutputFormat::Sarif));
+        assert_eq!("md".parse::<OutputFormat>(), Ok(OutputFormat::Markdown));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
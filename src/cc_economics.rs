@@ -10,7 +10,7 @@ use std::collections::HashMap;
 
 use crate::ccusage::{self, CcusagePeriod, Granularity};
 use crate::tracking::{DayStats, MonthStats, Tracker, WeekStats};
-use crate::utils::{format_cpt, format_tokens, format_usd};
+use crate::utils::{format_cpt, format_tokens, format_usd, theme};
 
 // ── Constants ──
 
@@ -249,7 +249,11 @@ fn merge_weekly(cc: Option<Vec<CcusagePeriod>>, rtk: Vec<WeekStats>) -> Vec<Peri
         let monday_key = match convert_saturday_to_monday(&entry.week_start) {
             Some(m) => m,
             None => {
-                eprintln!("⚠️  Invalid week_start format: {}", entry.week_start);
+                eprintln!(
+                    "{} Invalid week_start format: {}",
+                    theme().warn,
+                    entry.week_start
+                );
                 continue;
             }
         };
@@ -442,7 +446,7 @@ fn display_summary(tracker: &Tracker, verbose: u8) -> Result<()> {
     let totals = compute_totals(&periods);
 
     println!("💰 Claude Code Economics");
-    println!("════════════════════════════════════════════════════");
+    println!("{}", theme().rule(52));
     println!();
 
     println!(
@@ -550,7 +554,7 @@ fn display_daily(tracker: &Tracker, verbose: u8) -> Result<()> {
     let periods = merge_daily(cc_daily, rtk_daily);
 
     println!("📅 Daily Economics");
-    println!("════════════════════════════════════════════════════");
+    println!("{}", theme().rule(52));
     print_period_table(&periods, verbose);
     Ok(())
 }
@@ -564,7 +568,7 @@ fn display_weekly(tracker: &Tracker, verbose: u8) -> Result<()> {
     let periods = merge_weekly(cc_weekly, rtk_weekly);
 
     println!("📅 Weekly Economics");
-    println!("════════════════════════════════════════════════════");
+    println!("{}", theme().rule(52));
     print_period_table(&periods, verbose);
     Ok(())
 }
@@ -578,7 +582,7 @@ fn display_monthly(tracker: &Tracker, verbose: u8) -> Result<()> {
     let periods = merge_monthly(cc_monthly, rtk_monthly);
 
     println!("📅 Monthly Economics");
-    println!("════════════════════════════════════════════════════");
+    println!("{}", theme().rule(52));
     print_period_table(&periods, verbose);
     Ok(())
 }
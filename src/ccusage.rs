@@ -4,6 +4,7 @@
 //! Claude Code API usage metrics. Handles subprocess execution, JSON parsing,
 //! and graceful degradation when ccusage is unavailable.
 
+use crate::utils::theme;
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::process::Command;
@@ -128,7 +129,10 @@ pub fn fetch(granularity: Granularity) -> Result<Option<Vec<CcusagePeriod>>> {
     let mut cmd = match build_command() {
         Some(cmd) => cmd,
         None => {
-            eprintln!("⚠️  ccusage not found. Install: npm i -g ccusage (or use npx ccusage)");
+            eprintln!(
+                "{} ccusage not found. Install: npm i -g ccusage (or use npx ccusage)",
+                theme().warn
+            );
             return Ok(None);
         }
     };
@@ -148,7 +152,7 @@ pub fn fetch(granularity: Granularity) -> Result<Option<Vec<CcusagePeriod>>> {
 
     let output = match output {
         Err(e) => {
-            eprintln!("⚠️  ccusage execution failed: {}", e);
+            eprintln!("{} ccusage execution failed: {}", theme().warn, e);
             return Ok(None);
         }
         Ok(o) => o,
@@ -157,7 +161,8 @@ pub fn fetch(granularity: Granularity) -> Result<Option<Vec<CcusagePeriod>>> {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         eprintln!(
-            "⚠️  ccusage exited with {}: {}",
+            "{} ccusage exited with {}: {}",
+            theme().warn,
             output.status,
             stderr.trim()
         );
@@ -0,0 +1,276 @@
+//! `rtk ci`: cargo check, clippy and test in one consolidated report.
+//!
+//! Stages run in order by default; `--parallel` runs them concurrently in
+//! separate target dirs and prints each section as it completes.
+
+use crate::cargo_cmd::{self, CargoOutput, FilterKind, FilterOptions};
+use crate::tracking;
+use crate::utils::Spinner;
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+/// A CI stage: cargo subcommand and the filter applied to its output
+struct Stage {
+    name: &'static str,
+    kind: FilterKind,
+}
+
+const STAGES: &[Stage] = &[
+    Stage {
+        name: "check",
+        kind: FilterKind::Check,
+    },
+    Stage {
+        name: "clippy",
+        kind: FilterKind::Clippy,
+    },
+    Stage {
+        name: "test",
+        kind: FilterKind::Test,
+    },
+];
+
+/// Outcome of one stage; `None` exit code means it was skipped
+struct StageResult {
+    name: &'static str,
+    filtered: String,
+    exit_code: Option<i32>,
+}
+
+pub fn run(keep_going: bool, parallel: bool, opts: &FilterOptions, verbose: u8) -> Result<()> {
+    opts.reject_json("ci")?;
+    let timer = tracking::TimedExecution::start();
+
+    let mut raw_all = String::new();
+    let (mut results, report) = if parallel {
+        run_parallel(opts, verbose, &mut raw_all)?
+    } else {
+        let results = run_sequential(keep_going, opts, verbose, &mut raw_all)?;
+        let report = format_report(&results, opts);
+        println!("{}", opts.render(&report));
+        (results, report)
+    };
+
+    timer.track(
+        "cargo check && cargo clippy && cargo test",
+        "rtk ci",
+        &raw_all,
+        &report,
+    );
+
+    // The first failing stage (in stage order) decides the exit status
+    results.sort_by_key(|r| STAGES.iter().position(|s| s.name == r.name));
+    if let Some(code) = results
+        .iter()
+        .filter_map(|r| r.exit_code)
+        .find(|code| *code != 0)
+    {
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
+
+/// Run stages one after another, stopping at the first failure unless `keep_going`
+fn run_sequential(
+    keep_going: bool,
+    opts: &FilterOptions,
+    verbose: u8,
+    raw_all: &mut String,
+) -> Result<Vec<StageResult>> {
+    let mut results: Vec<StageResult> = Vec::new();
+    let mut failed = false;
+
+    for stage in STAGES {
+        if failed && !keep_going {
+            results.push(StageResult {
+                name: stage.name,
+                filtered: String::new(),
+                exit_code: None,
+            });
+            continue;
+        }
+
+        let spinner = Spinner::start(&format!("cargo {}", stage.name), &opts.theme);
+        let output = cargo_cmd::capture(stage.name, &[], None, opts, verbose)?;
+        spinner.finish();
+
+        let result = stage_result(stage, &output, opts, raw_all);
+        failed |= result.exit_code != Some(0);
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+/// Run all stages at once, each in its own target dir so they don't wait on
+/// cargo's build-directory lock, printing each section as soon as it finishes.
+fn run_parallel(
+    opts: &FilterOptions,
+    verbose: u8,
+    raw_all: &mut String,
+) -> Result<(Vec<StageResult>, String)> {
+    let base = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target"));
+
+    let mut results: Vec<StageResult> = Vec::new();
+    let mut report = String::new();
+
+    std::thread::scope(|scope| -> Result<()> {
+        let (tx, rx) = mpsc::channel();
+        for stage in STAGES {
+            let tx = tx.clone();
+            let dir = base.join(format!("rtk-ci-{}", stage.name));
+            scope.spawn(move || {
+                let output = cargo_cmd::capture(stage.name, &[], Some(&dir), opts, verbose);
+                let _ = tx.send((stage, output));
+            });
+        }
+        drop(tx);
+
+        for (stage, output) in rx {
+            let result = stage_result(stage, &output?, opts, raw_all);
+            let section = format_section(&result);
+            println!("{}", opts.render(&section));
+            report.push_str(&section);
+            report.push('\n');
+            results.push(result);
+        }
+        Ok(())
+    })?;
+
+    let verdict = format_verdict(&results, opts);
+    println!("{}", opts.render(&verdict));
+    report.push_str(&verdict);
+
+    Ok((results, report))
+}
+
+/// Filter one stage's output, attaching the tee hint when it failed
+fn stage_result(
+    stage: &Stage,
+    output: &CargoOutput,
+    opts: &FilterOptions,
+    raw_all: &mut String,
+) -> StageResult {
+    let raw = output.raw();
+    let mut filtered = cargo_cmd::filter(stage.kind, &raw, opts);
+    if output.exit_code != 0 {
+        if let Some(hint) =
+            crate::tee::tee_and_hint(&raw, &format!("cargo_{}", stage.name), output.exit_code)
+        {
+            filtered = format!("{}\n{}", filtered, hint);
+        }
+    }
+    raw_all.push_str(&raw);
+
+    StageResult {
+        name: stage.name,
+        filtered,
+        exit_code: Some(output.exit_code),
+    }
+}
+
+/// `[stage]` header followed by the filtered summary, or a skipped marker
+fn format_section(result: &StageResult) -> String {
+    match result.exit_code {
+        Some(_) => format!("[{}]\n{}\n", result.name, result.filtered),
+        None => format!("[{}] skipped\n", result.name),
+    }
+}
+
+/// Overall PASS/FAIL line naming the failed stages in stage order
+fn format_verdict(results: &[StageResult], opts: &FilterOptions) -> String {
+    let failed: Vec<&str> = STAGES
+        .iter()
+        .map(|s| s.name)
+        .filter(|name| {
+            results
+                .iter()
+                .any(|r| r.name == *name && r.exit_code.is_some_and(|code| code != 0))
+        })
+        .collect();
+
+    if failed.is_empty() {
+        format!("{} ci: PASS", opts.theme.ok)
+    } else {
+        format!("ci: FAIL ({})", failed.join(", "))
+    }
+}
+
+/// One section per stage, then an overall PASS/FAIL line
+fn format_report(results: &[StageResult], opts: &FilterOptions) -> String {
+    let mut report = String::new();
+    for result in results {
+        report.push_str(&format_section(result));
+        report.push('\n');
+    }
+    report.push_str(&format_verdict(results, opts));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(name: &'static str, filtered: &str, exit_code: Option<i32>) -> StageResult {
+        StageResult {
+            name,
+            filtered: filtered.to_string(),
+            exit_code,
+        }
+    }
+
+    #[test]
+    fn test_format_report_pass() {
+        let results = vec![
+            stage("check", "✓ cargo build (3 crates compiled)", Some(0)),
+            stage("clippy", "✓ cargo clippy: No issues found", Some(0)),
+            stage("test", "✓ cargo test: 12 passed (1 suite, 0.10s)", Some(0)),
+        ];
+        let report = format_report(&results, &FilterOptions::default());
+        assert!(report.starts_with("[check]\n✓ cargo build"));
+        assert!(report.contains("[clippy]\n"));
+        assert!(report.ends_with("✓ ci: PASS"));
+    }
+
+    #[test]
+    fn test_format_report_short_circuit() {
+        let results = vec![
+            stage("check", "cargo build: 1 errors, 0 warnings", Some(101)),
+            stage("clippy", "", None),
+            stage("test", "", None),
+        ];
+        let report = format_report(&results, &FilterOptions::default());
+        assert!(report.contains("[clippy] skipped"));
+        assert!(report.contains("[test] skipped"));
+        assert!(report.ends_with("ci: FAIL (check)"));
+    }
+
+    #[test]
+    fn test_format_verdict_uses_stage_order() {
+        // Parallel runs finish in any order; the verdict still lists failures check → test
+        let results = vec![
+            stage("test", "FAILURES (1):", Some(101)),
+            stage("check", "cargo build: 1 errors", Some(101)),
+            stage("clippy", "ok", Some(0)),
+        ];
+        assert_eq!(
+            format_verdict(&results, &FilterOptions::default()),
+            "ci: FAIL (check, test)"
+        );
+    }
+
+    #[test]
+    fn test_format_report_keep_going_lists_every_failure() {
+        let results = vec![
+            stage("check", "ok", Some(0)),
+            stage("clippy", "cargo clippy: 2 errors", Some(101)),
+            stage("test", "FAILURES (1):", Some(101)),
+        ];
+        let report = format_report(&results, &FilterOptions::default());
+        assert!(report.ends_with("ci: FAIL (clippy, test)"));
+    }
+}
@@ -0,0 +1,35 @@
+//! `rtk completions <shell>`: shell completion scripts generated by `clap_complete` from
+//! the clap command tree, so subcommands, flags and flag values (`--format`, `--rtk-color`)
+//! stay in sync with the CLI definition.
+
+use anyhow::Result;
+use clap_complete::Shell;
+
+/// Print the completion script for `shell` to stdout
+pub fn run(shell: Shell, mut cmd: clap::Command) -> Result<()> {
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn script(shell: Shell) -> String {
+        let mut out = Vec::new();
+        clap_complete::generate(shell, &mut crate::Cli::command(), "rtk", &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_completions_cover_subcommands_and_flag_values() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let script = script(shell);
+            assert!(script.contains("cargo"), "{shell}");
+            assert!(script.contains("--format"), "{shell}");
+            assert!(script.contains("json-summary"), "{shell}");
+        }
+    }
+}
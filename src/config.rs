@@ -1,6 +1,62 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Project-local config file name, searched from the current directory upwards
+pub const PROJECT_CONFIG_FILE: &str = ".rtk.toml";
+
+/// `config.toml` written by `rtk config init`: the built-in defaults, documented
+const DEFAULT_CONFIG_TOML: &str = r#"# rtk configuration
+# Overridden by a project .rtk.toml, then RTK_* environment variables, then CLI flags.
+# `rtk config show` prints the effective value of every key and where it came from.
+
+[tracking]
+# Record token savings in the history database
+enabled = true
+# Days of history kept before pruning
+history_retention = 30
+# Newest entries kept before pruning
+history_max_entries = 10000
+# History database location (env: RTK_DB_PATH)
+# database_path = "/path/to/history.db"
+
+[display]
+colors = true
+# Unicode glyphs (✓/⚠/═══); false for ASCII (env: RTK_ASCII=1, flag: --ascii)
+emoji = true
+max_width = 120
+# Max error blocks shown by build/check/install filters (env: RTK_MAX_ERRORS, flag: --max-errors)
+max_errors = 15
+# Max characters kept per test failure; unset follows the terminal width, 200 when piped
+# (env: RTK_FAILURE_WIDTH, flag: --failure-width)
+# failure_width = 200
+# Diagnostics that always survive filtering: "re:<regex>" or a plain substring
+keep = []
+
+[filters]
+ignore_dirs = [".git", "node_modules", "target", "__pycache__", ".venv", "vendor"]
+ignore_files = ["*.lock", "*.min.js", "*.min.css"]
+
+[tee]
+# Save raw output of failed commands so it can be read back in full
+enabled = true
+# failures, always or never
+mode = "failures"
+max_files = 20
+max_file_size = 1048576
+# directory = "/path/to/tee"
+
+[platform]
+# claude, gemini, cursor or windsurf
+ai_platform = "claude"
+
+[watch]
+# Quiet period after the last change before `rtk watch` re-runs
+debounce_ms = 300
+paths = ["src", "Cargo.toml"]
+"#;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -18,6 +74,7 @@ impl Default for AIPlatform {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PlatformConfig {
     /// Which AI coding assistant platform is being used
     pub ai_platform: AIPlatform,
@@ -43,12 +100,19 @@ pub struct Config {
     pub tee: crate::tee::TeeConfig,
     #[serde(default)]
     pub platform: PlatformConfig,
+    #[serde(default)]
+    pub watch: WatchConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TrackingConfig {
     pub enabled: bool,
-    pub history_days: u32,
+    /// Days of history kept before pruning
+    #[serde(alias = "history_days")]
+    pub history_retention: u32,
+    /// Newest entries kept before pruning
+    pub history_max_entries: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub database_path: Option<PathBuf>,
 }
@@ -57,17 +121,26 @@ impl Default for TrackingConfig {
     fn default() -> Self {
         Self {
             enabled: true,
-            history_days: 90,
+            history_retention: 30,
+            history_max_entries: 10_000,
             database_path: None,
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DisplayConfig {
     pub colors: bool,
     pub emoji: bool,
     pub max_width: usize,
+    /// Max error blocks shown by build/check/install filters
+    pub max_errors: usize,
+    /// Max characters kept per test failure; `None` follows the terminal width
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_width: Option<usize>,
+    /// Diagnostics that always survive filtering: `re:<regex>` or a plain substring
+    pub keep: Vec<String>,
 }
 
 impl Default for DisplayConfig {
@@ -76,11 +149,15 @@ impl Default for DisplayConfig {
             colors: true,
             emoji: true,
             max_width: 120,
+            max_errors: 15,
+            failure_width: None,
+            keep: Vec::new(),
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FilterConfig {
     pub ignore_dirs: Vec<String>,
     pub ignore_files: Vec<String>,
@@ -102,57 +179,332 @@ impl Default for FilterConfig {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Quiet period after the last change before `rtk watch` re-runs
+    pub debounce_ms: u64,
+    /// Files and directories watched for changes
+    pub paths: Vec<String>,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 300,
+            paths: vec!["src".into(), "Cargo.toml".into()],
+        }
+    }
+}
+
 impl Config {
+    /// Load the effective configuration.
+    ///
+    /// Precedence (lowest to highest): built-in defaults < global
+    /// `~/.config/rtk/config.toml` < project `.rtk.toml` (nearest one walking up
+    /// from the current directory) < CLI flags (applied by the caller).
     pub fn load() -> Result<Self> {
-        let path = get_config_path()?;
+        let mut merged = toml::Value::Table(toml::map::Map::new());
 
+        let path = get_config_path()?;
         if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
-            let config: Config = toml::from_str(&content)?;
-            Ok(config)
-        } else {
-            Ok(Config::default())
+            merge_toml(&mut merged, read_toml(&path)?);
         }
+
+        if let Some(project) = find_project_config() {
+            let (layer, ignored) = project_layer(read_toml(&project)?);
+            warn_ignored(&project, &ignored);
+            merge_toml(&mut merged, layer);
+        }
+
+        let config: Config = merged.try_into().context("Invalid rtk configuration")?;
+        Ok(config)
     }
 
-    pub fn save(&self) -> Result<()> {
-        let path = get_config_path()?;
+    /// [`Config::load`], falling back to the defaults with a warning on stderr (printed once
+    /// per run) so a broken `.rtk.toml` never silently drops the user's settings
+    pub fn load_or_default() -> Self {
+        static WARNED: AtomicBool = AtomicBool::new(false);
+        Self::load().unwrap_or_else(|err| {
+            if !WARNED.swap(true, Ordering::Relaxed) {
+                let message = format!("{:#}", err);
+                eprintln!("rtk: ignoring invalid config: {}", message.trim_end());
+            }
+            Self::default()
+        })
+    }
+}
+
+/// Write the commented default `config.toml`, refusing to replace an existing one unless `force`
+pub fn init(force: bool) -> Result<PathBuf> {
+    let path = get_config_path()?;
+    write_default_config(&path, force)?;
+    Ok(path)
+}
+
+fn write_default_config(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists (pass --force to overwrite)",
+            path.display()
+        );
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, DEFAULT_CONFIG_TOML)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Layer an effective setting came from, lowest precedence first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Default,
+    Global,
+    Project,
+    Env(&'static str),
+    Flag(&'static str),
+}
 
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::Global => write!(f, "global"),
+            Source::Project => write!(f, "project"),
+            Source::Env(name) => write!(f, "env {}", name),
+            Source::Flag(name) => write!(f, "flag {}", name),
         }
+    }
+}
 
-        let content = toml::to_string_pretty(self)?;
-        std::fs::write(&path, content)?;
-        Ok(())
+/// A single setting applied on top of the files: dotted key, value, and where it came from
+pub type Override = (&'static str, toml::Value, Source);
+
+/// Environment variables that override config keys (the ones `FilterOptions` and tracking read)
+pub fn env_overrides<F>(var: F) -> Vec<Override>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut overrides = Vec::new();
+    if var("RTK_ASCII").as_deref() == Some("1") {
+        overrides.push((
+            "display.emoji",
+            toml::Value::Boolean(false),
+            Source::Env("RTK_ASCII"),
+        ));
     }
+    for (name, key) in [
+        ("RTK_MAX_ERRORS", "display.max_errors"),
+        ("RTK_FAILURE_WIDTH", "display.failure_width"),
+    ] {
+        if let Some(n) = var(name).and_then(|v| v.trim().parse::<i64>().ok()) {
+            overrides.push((key, toml::Value::Integer(n), Source::Env(name)));
+        }
+    }
+    if let Some(path) = var("RTK_DB_PATH") {
+        overrides.push((
+            "tracking.database_path",
+            toml::Value::String(path),
+            Source::Env("RTK_DB_PATH"),
+        ));
+    }
+    overrides
+}
 
-    pub fn create_default() -> Result<PathBuf> {
-        let config = Config::default();
-        config.save()?;
-        get_config_path()
+/// Every leaf setting of the merged configuration with the layer that set it last
+fn annotate(
+    global: Option<toml::Value>,
+    project: Option<toml::Value>,
+    overrides: &[Override],
+) -> Result<Vec<(String, toml::Value, Source)>> {
+    let mut merged = toml::Value::try_from(Config::default())?;
+    let mut sources = Vec::new();
+    flatten("", &merged, Source::Default, &mut sources);
+
+    for (layer, source) in [(global, Source::Global), (project, Source::Project)] {
+        if let Some(layer) = layer {
+            flatten("", &layer, source, &mut sources);
+            merge_toml(&mut merged, layer);
+        }
+    }
+    for (key, value, source) in overrides {
+        let (section, name) = key.split_once('.').unwrap_or(("", key));
+        let mut table = toml::map::Map::new();
+        table.insert(name.to_string(), value.clone());
+        let mut layer = toml::map::Map::new();
+        layer.insert(section.to_string(), toml::Value::Table(table));
+        merge_toml(&mut merged, toml::Value::Table(layer));
+        sources.push((key.to_string(), value.clone(), *source));
+    }
+
+    // Reject what `Config::load` would reject instead of annotating an unusable config
+    let _: Config = merged
+        .clone()
+        .try_into()
+        .context("Invalid rtk configuration")?;
+
+    let mut leaves = Vec::new();
+    flatten("", &merged, Source::Default, &mut leaves);
+    Ok(leaves
+        .into_iter()
+        .map(|(key, value, _)| {
+            let source = sources
+                .iter()
+                .rev()
+                .find(|(k, _, _)| *k == key)
+                .map_or(Source::Default, |(_, _, s)| *s);
+            (key, value, source)
+        })
+        .collect())
+}
+
+/// Dotted `section.key` leaves of a TOML table (arrays count as one value)
+fn flatten(
+    prefix: &str,
+    value: &toml::Value,
+    source: Source,
+    out: &mut Vec<(String, toml::Value, Source)>,
+) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(&path, value, source, out);
+            }
+        }
+        leaf => out.push((prefix.to_string(), leaf.clone(), source)),
+    }
+}
+
+/// `[section]` headers followed by `key = value  # source` lines
+fn format_annotated(settings: &[(String, toml::Value, Source)]) -> String {
+    let mut lines = Vec::new();
+    let mut section = "";
+    for (key, value, source) in settings {
+        let (sec, name) = key.split_once('.').unwrap_or(("", key));
+        if sec != section {
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("[{}]", sec));
+            section = sec;
+        }
+        lines.push(format!("{} = {}  # {}", name, value, source));
     }
+    lines.join("\n")
 }
 
-fn get_config_path() -> Result<PathBuf> {
+pub fn get_config_path() -> Result<PathBuf> {
     let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
     Ok(config_dir.join("rtk").join("config.toml"))
 }
 
-pub fn show_config() -> Result<()> {
-    let path = get_config_path()?;
-    println!("Config: {}", path.display());
-    println!();
+fn read_toml(path: &Path) -> Result<toml::Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Find the nearest `.rtk.toml` walking up from the current directory
+pub fn find_project_config() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    find_project_config_from(&cwd)
+}
+
+fn find_project_config_from(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Sections a project `.rtk.toml` may set. A cloned repository must not be able to move
+/// where rtk writes (`tracking.database_path`, `tee.directory`) or turn tracking off.
+const PROJECT_SECTIONS: &[&str] = &["display", "filters"];
 
+/// Keep only the [`PROJECT_SECTIONS`] of a project layer, returning the dropped keys
+fn project_layer(value: toml::Value) -> (toml::Value, Vec<String>) {
+    let toml::Value::Table(table) = value else {
+        return (toml::Value::Table(toml::map::Map::new()), Vec::new());
+    };
+    let mut ignored = Vec::new();
+    let mut kept = toml::map::Map::new();
+    for (key, value) in table {
+        if PROJECT_SECTIONS.contains(&key.as_str()) && value.is_table() {
+            kept.insert(key, value);
+        } else if let toml::Value::Table(section) = value {
+            ignored.extend(section.keys().map(|name| format!("{}.{}", key, name)));
+        } else {
+            ignored.push(key);
+        }
+    }
+    (toml::Value::Table(kept), ignored)
+}
+
+/// Warn (once per run) about project keys that only the global config may set
+fn warn_ignored(path: &Path, ignored: &[String]) {
+    static WARNED: AtomicBool = AtomicBool::new(false);
+    if !ignored.is_empty() && !WARNED.swap(true, Ordering::Relaxed) {
+        eprintln!(
+            "rtk: {}: ignoring {} (a project config may only set [{}])",
+            path.display(),
+            ignored.join(", "),
+            PROJECT_SECTIONS.join("], [")
+        );
+    }
+}
+
+/// Deep-merge `overlay` into `base`: tables merge key by key, other values replace
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base_table.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Print the effective configuration, each value annotated with the layer that set it.
+/// `flags` are the CLI overrides given to `rtk config show` itself.
+pub fn show_config(flags: &[Override]) -> Result<()> {
+    let path = get_config_path()?;
+    let project = find_project_config();
     if path.exists() {
-        let config = Config::load()?;
-        println!("{}", toml::to_string_pretty(&config)?);
+        println!("Global: {}", path.display());
     } else {
-        println!("(default config, file not created)");
-        println!();
-        let config = Config::default();
-        println!("{}", toml::to_string_pretty(&config)?);
+        println!(
+            "Global: {} (not created, run `rtk config init`)",
+            path.display()
+        );
     }
+    if let Some(project) = &project {
+        println!("Project: {}", project.display());
+    }
+    println!();
+
+    let global = path.exists().then(|| read_toml(&path)).transpose()?;
+    let project = project
+        .map(|p| read_toml(&p).map(|layer| project_layer(layer).0))
+        .transpose()?;
+    let mut overrides = env_overrides(|name| std::env::var(name).ok());
+    overrides.extend_from_slice(flags);
+    println!(
+        "{}",
+        format_annotated(&annotate(global, project, &overrides)?)
+    );
 
     Ok(())
 }
@@ -209,4 +561,140 @@ mod tests {
             ));
         }
     }
+
+    #[test]
+    fn test_partial_display_section_uses_defaults() {
+        let config: Config = toml::from_str("[display]\nmax_errors = 50\n").unwrap();
+        assert_eq!(config.display.max_errors, 50);
+        assert_eq!(config.display.failure_width, None);
+        assert!(config.display.emoji);
+    }
+
+    #[test]
+    fn test_merge_toml_project_overrides_global() {
+        let mut global: toml::Value =
+            toml::from_str("[display]\nemoji = true\nmax_errors = 20\n[tee]\nenabled = false\n")
+                .unwrap();
+        let project: toml::Value = toml::from_str("[display]\nemoji = false\n").unwrap();
+        merge_toml(&mut global, project);
+
+        let config: Config = global.try_into().unwrap();
+        assert!(!config.display.emoji);
+        assert_eq!(config.display.max_errors, 20);
+        assert!(!config.tee.enabled);
+    }
+
+    #[test]
+    fn test_project_layer_cannot_move_database_path() {
+        let mut global: toml::Value =
+            toml::from_str("[tracking]\ndatabase_path = \"/home/me/rtk.db\"\n").unwrap();
+        let project: toml::Value = toml::from_str(
+            "[display]\nmax_errors = 50\n[tracking]\ndatabase_path = \"/tmp/evil.db\"\nenabled = false\n[tee]\ndirectory = \"/tmp/tee\"\n",
+        )
+        .unwrap();
+        let (layer, ignored) = project_layer(project);
+        merge_toml(&mut global, layer);
+
+        let config: Config = global.try_into().unwrap();
+        assert_eq!(config.display.max_errors, 50);
+        assert_eq!(
+            config.tracking.database_path,
+            Some(PathBuf::from("/home/me/rtk.db"))
+        );
+        assert!(config.tracking.enabled);
+        assert_eq!(config.tee.directory, None);
+        assert_eq!(
+            ignored,
+            [
+                "tee.directory",
+                "tracking.database_path",
+                "tracking.enabled"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_config_template_matches_defaults() {
+        let config: Config = toml::from_str(DEFAULT_CONFIG_TOML).unwrap();
+        assert_eq!(
+            toml::to_string(&config).unwrap(),
+            toml::to_string(&Config::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_default_config_refuses_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rtk").join("config.toml");
+        write_default_config(&path, false).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# rtk configuration"));
+
+        std::fs::write(&path, "[display]\n").unwrap();
+        let err = write_default_config(&path, false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[display]\n");
+
+        write_default_config(&path, true).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), DEFAULT_CONFIG_TOML);
+    }
+
+    #[test]
+    fn test_annotate_sources() {
+        let global = toml::from_str("[display]\nmax_errors = 20\nemoji = false\n").unwrap();
+        let project = toml::from_str("[display]\nmax_errors = 30\n").unwrap();
+        let mut overrides = env_overrides(|name| match name {
+            "RTK_FAILURE_WIDTH" => Some("400".to_string()),
+            "RTK_MAX_ERRORS" => Some("lots".to_string()),
+            _ => None,
+        });
+        overrides.push((
+            "display.max_errors",
+            toml::Value::Integer(5),
+            Source::Flag("--max-errors"),
+        ));
+
+        let settings = annotate(Some(global), Some(project), &overrides).unwrap();
+        let find = |key: &str| {
+            settings
+                .iter()
+                .find(|(k, _, _)| k == key)
+                .map(|(_, v, s)| (v.to_string(), s.to_string()))
+                .unwrap()
+        };
+        assert_eq!(
+            find("display.max_errors"),
+            ("5".into(), "flag --max-errors".into())
+        );
+        assert_eq!(find("display.emoji"), ("false".into(), "global".into()));
+        assert_eq!(
+            find("display.failure_width"),
+            ("400".into(), "env RTK_FAILURE_WIDTH".into())
+        );
+        assert_eq!(find("watch.debounce_ms"), ("300".into(), "default".into()));
+
+        let shown = format_annotated(&settings);
+        assert!(shown.contains("[display]\ncolors = true  # default\nemoji = false  # global"));
+    }
+
+    #[test]
+    fn test_annotate_rejects_invalid_config() {
+        let project = toml::from_str("[display]\nmax_errors = \"many\"\n").unwrap();
+        assert!(annotate(None, Some(project), &[]).is_err());
+    }
+
+    #[test]
+    fn test_find_project_config_walks_up() {
+        let root = tempfile::tempdir().unwrap();
+        let nested = root.path().join("crates").join("foo");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert!(find_project_config_from(&nested).is_none());
+
+        std::fs::write(root.path().join(PROJECT_CONFIG_FILE), "[display]\n").unwrap();
+        assert_eq!(
+            find_project_config_from(&nested),
+            Some(root.path().join(PROJECT_CONFIG_FILE))
+        );
+    }
 }
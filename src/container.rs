@@ -1,4 +1,5 @@
 use crate::tracking;
+use crate::utils::theme;
 use anyhow::{Context, Result};
 use std::ffi::OsString;
 use std::process::Command;
@@ -254,13 +255,13 @@ fn kubectl_pods(args: &[String], _verbose: u8) -> Result<()> {
 
     let mut parts = Vec::new();
     if running > 0 {
-        parts.push(format!("{} ✓", running));
+        parts.push(format!("{} {}", running, theme().ok));
     }
     if pending > 0 {
         parts.push(format!("{} pending", pending));
     }
     if failed > 0 {
-        parts.push(format!("{} ✗", failed));
+        parts.push(format!("{} {}", failed, theme().fail));
     }
     if restarts_total > 0 {
         parts.push(format!("{} restarts", restarts_total));
@@ -268,7 +269,7 @@ fn kubectl_pods(args: &[String], _verbose: u8) -> Result<()> {
 
     rtk.push_str(&format!("☸️  {} pods: {}\n", pods.len(), parts.join(", ")));
     if !issues.is_empty() {
-        rtk.push_str("⚠️  Issues:\n");
+        rtk.push_str(&format!("{} Issues:\n", theme().warn));
         for issue in issues.iter().take(10) {
             rtk.push_str(&format!("  {}\n", issue));
         }
@@ -4,7 +4,7 @@
 //! a unified trait-based system for displaying daily/weekly/monthly data.
 
 use crate::tracking::{DayStats, MonthStats, WeekStats};
-use crate::utils::format_tokens;
+use crate::utils::{format_tokens, theme};
 
 /// Format duration in milliseconds to human-readable string
 pub fn format_duration(ms: u64) -> String {
@@ -66,7 +66,7 @@ pub fn print_period_table<T: PeriodStats>(data: &[T]) {
     }
 
     let period_width = T::period_width();
-    let separator = "═".repeat(T::separator_width());
+    let separator = theme().rule(T::separator_width());
 
     println!(
         "\n{} {} Breakdown ({} {}s)",
@@ -0,0 +1,226 @@
+//! `rtk doctor`: check the toolchain and rtk's own files, with a fix for each failure.
+
+use crate::cargo_cmd::FilterOptions;
+use crate::config;
+use crate::tracking;
+use crate::utils::Theme;
+use anyhow::Result;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::Command;
+
+/// One line of the checklist
+struct Check {
+    name: &'static str,
+    ok: bool,
+    detail: String,
+    /// How to fix it, shown only when the check fails
+    hint: &'static str,
+}
+
+pub fn run(verbose: u8) -> Result<()> {
+    let opts = FilterOptions::from_config();
+    let cargo = opts.cargo_command().get_program().to_owned();
+
+    let mut checks = vec![
+        version_check(
+            "cargo",
+            &cargo,
+            &["--version"],
+            "install Rust with rustup: https://rustup.rs (or set --cargo-bin / $CARGO)",
+        ),
+        version_check(
+            "rustc",
+            OsStr::new("rustc"),
+            &["--version"],
+            "install a toolchain: rustup default stable",
+        ),
+        version_check(
+            "clippy",
+            &cargo,
+            &["clippy", "--version"],
+            "rustup component add clippy",
+        ),
+        version_check(
+            "git",
+            OsStr::new("git"),
+            &["--version"],
+            "install git from your package manager or https://git-scm.com",
+        ),
+    ];
+    checks.push(match config::get_config_path() {
+        Ok(path) => file_check(
+            "config",
+            &path,
+            "fix permissions on the config file or its directory",
+        ),
+        Err(e) => failed("config", e.to_string(), "set $HOME / $XDG_CONFIG_HOME"),
+    });
+    checks.push(match tracking::get_db_path() {
+        Ok(path) => file_check(
+            "history",
+            &path,
+            "fix permissions, or point RTK_DB_PATH / tracking.database_path elsewhere",
+        ),
+        Err(e) => failed("history", e.to_string(), "set RTK_DB_PATH"),
+    });
+
+    if verbose > 0 {
+        eprintln!("cargo program: {}", cargo.to_string_lossy());
+    }
+    println!("{}", format_checks(&checks, &opts.theme));
+
+    if checks.iter().any(|c| !c.ok) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn failed(name: &'static str, detail: String, hint: &'static str) -> Check {
+    Check {
+        name,
+        ok: false,
+        detail,
+        hint,
+    }
+}
+
+/// Run `<program> <args>` and report the first line of its output
+fn version_check(name: &'static str, program: &OsStr, args: &[&str], hint: &'static str) -> Check {
+    match Command::new(program).args(args).output() {
+        Ok(output) if output.status.success() => Check {
+            name,
+            ok: true,
+            detail: String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string(),
+            hint,
+        },
+        Ok(_) => failed(name, "not installed".to_string(), hint),
+        Err(_) => failed(
+            name,
+            format!("{} not found on PATH", program.to_string_lossy()),
+            hint,
+        ),
+    }
+}
+
+/// Readable and writable when it exists; otherwise its directory must accept new files
+fn file_check(name: &'static str, path: &Path, hint: &'static str) -> Check {
+    let shown = path.display().to_string();
+    if path.exists() {
+        let readable = std::fs::File::open(path).is_ok();
+        let writable = std::fs::OpenOptions::new().append(true).open(path).is_ok();
+        return match (readable, writable) {
+            (true, true) => Check {
+                name,
+                ok: true,
+                detail: shown,
+                hint,
+            },
+            (false, _) => failed(name, format!("{} is not readable", shown), hint),
+            (true, false) => failed(name, format!("{} is not writable", shown), hint),
+        };
+    }
+
+    // Not created yet: the nearest existing ancestor must be writable
+    let Some(dir) = path.ancestors().skip(1).find(|dir| dir.exists()) else {
+        return failed(name, format!("{} has no existing parent", shown), hint);
+    };
+    if tempfile::tempfile_in(dir).is_ok() {
+        Check {
+            name,
+            ok: true,
+            detail: format!("{} (not created yet)", shown),
+            hint,
+        }
+    } else {
+        failed(
+            name,
+            format!(
+                "{} cannot be created ({} is not writable)",
+                shown,
+                dir.display()
+            ),
+            hint,
+        )
+    }
+}
+
+/// `✓ name: detail` per check, failures followed by an indented fix
+fn format_checks(checks: &[Check], theme: &Theme) -> String {
+    let mut lines = vec!["rtk doctor".to_string()];
+    for check in checks {
+        let mark = if check.ok { theme.ok } else { theme.fail };
+        lines.push(format!("{} {}: {}", mark, check.name, check.detail));
+        if !check.ok {
+            lines.push(format!("    → {}", check.hint));
+        }
+    }
+    let failures = checks.iter().filter(|c| !c.ok).count();
+    lines.push(if failures == 0 {
+        "all checks passed".to_string()
+    } else {
+        format!(
+            "{} check{} failed",
+            failures,
+            if failures == 1 { "" } else { "s" }
+        )
+    });
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_checks() {
+        let checks = vec![
+            Check {
+                name: "cargo",
+                ok: true,
+                detail: "cargo 1.80.0 (376290515 2024-07-16)".to_string(),
+                hint: "install Rust",
+            },
+            failed(
+                "clippy",
+                "not installed".to_string(),
+                "rustup component add clippy",
+            ),
+        ];
+        assert_eq!(
+            format_checks(&checks, &Theme::unicode()),
+            "rtk doctor\n✓ cargo: cargo 1.80.0 (376290515 2024-07-16)\n✗ clippy: not installed\n    → rustup component add clippy\n1 check failed"
+        );
+        assert!(format_checks(&checks, &Theme::ascii()).contains("FAIL clippy"));
+    }
+
+    #[test]
+    fn test_version_check_missing_program() {
+        let check = version_check(
+            "tool",
+            OsStr::new("rtk-definitely-missing-binary"),
+            &["--version"],
+            "install it",
+        );
+        assert!(!check.ok);
+        assert!(check.detail.contains("not found on PATH"));
+    }
+
+    #[test]
+    fn test_file_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("config.toml");
+        std::fs::write(&existing, "").unwrap();
+        assert!(file_check("config", &existing, "").ok);
+
+        let missing = dir.path().join("rtk").join("history.db");
+        let check = file_check("history", &missing, "");
+        assert!(check.ok);
+        assert!(check.detail.ends_with("(not created yet)"));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::prettier_cmd;
 use crate::ruff_cmd;
 use crate::tracking;
-use crate::utils::package_manager_exec;
+use crate::utils::{package_manager_exec, theme};
 use anyhow::{Context, Result};
 use std::path::Path;
 use std::process::Command;
@@ -227,7 +227,10 @@ fn filter_black_output(output: &str) -> String {
 
     if !needs_formatting && (all_done || files_unchanged > 0) {
         // All files formatted correctly
-        result.push_str("✓ Format (black): All files formatted");
+        result.push_str(&format!(
+            "{} Format (black): All files formatted",
+            theme().ok
+        ));
         if files_unchanged > 0 {
             result.push_str(&format!(" ({} files checked)", files_unchanged));
         }
@@ -243,7 +246,7 @@ fn filter_black_output(output: &str) -> String {
             "Format (black): {} files need formatting\n",
             count
         ));
-        result.push_str("═══════════════════════════════════════\n");
+        result.push_str(&format!("{}\n", theme().separator));
 
         if !files_to_format.is_empty() {
             for (i, file) in files_to_format.iter().take(10).enumerate() {
@@ -260,7 +263,8 @@ fn filter_black_output(output: &str) -> String {
 
         if files_unchanged > 0 {
             result.push_str(&format!(
-                "\n✓ {} files already formatted\n",
+                "\n{} {} files already formatted\n",
+                theme().ok,
                 files_unchanged
             ));
         }
@@ -1,6 +1,6 @@
 use crate::display_helpers::{format_duration, print_period_table};
 use crate::tracking::{DayStats, MonthStats, Tracker, WeekStats};
-use crate::utils::format_tokens;
+use crate::utils::{format_tokens, theme};
 use anyhow::{Context, Result};
 use colored::Colorize; // added: terminal colors
 use serde::Serialize;
@@ -41,7 +41,7 @@ pub fn run(
     if !daily && !weekly && !monthly && !all {
         // added: styled header with bold title
         println!("{}", styled("RTK Token Savings (Global Scope)", true));
-        println!("{}", "═".repeat(60));
+        println!("{}", theme().rule(60));
         println!();
 
         // added: KPI-style aligned output
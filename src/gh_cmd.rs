@@ -6,7 +6,7 @@
 use crate::git;
 use crate::json_cmd;
 use crate::tracking;
-use crate::utils::{ok_confirmation, truncate};
+use crate::utils::{ok_confirmation, theme, truncate};
 use anyhow::{Context, Result};
 use serde_json::Value;
 use std::process::Command;
@@ -200,8 +200,8 @@ fn view_pr(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
     print!("{}", line);
 
     let mergeable_str = match mergeable {
-        "MERGEABLE" => "✓",
-        "CONFLICTING" => "✗",
+        "MERGEABLE" => theme().ok,
+        "CONFLICTING" => theme().fail,
         _ => "?",
     };
     let line = format!("  {} | {}\n", state, mergeable_str);
@@ -249,11 +249,11 @@ fn view_pr(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
 
         if ultra_compact {
             if failed > 0 {
-                let line = format!("  ✗{}/{}  {} fail\n", passed, total, failed);
+                let line = format!("  {}{}/{}  {} fail\n", theme().fail, passed, total, failed);
                 filtered.push_str(&line);
                 print!("{}", line);
             } else {
-                let line = format!("  ✓{}/{}\n", passed, total);
+                let line = format!("  {}{}/{}\n", theme().ok, passed, total);
                 filtered.push_str(&line);
                 print!("{}", line);
             }
@@ -262,7 +262,7 @@ fn view_pr(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
             filtered.push_str(&line);
             print!("{}", line);
             if failed > 0 {
-                let line = format!("  ⚠️  {} checks failed\n", failed);
+                let line = format!("  {} {} checks failed\n", theme().warn, failed);
                 filtered.push_str(&line);
                 print!("{}", line);
             }
@@ -656,8 +656,8 @@ fn list_runs(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
 
             let icon = if ultra_compact {
                 match conclusion {
-                    "success" => "✓",
-                    "failure" => "✗",
+                    "success" => theme().ok,
+                    "failure" => theme().fail,
                     "cancelled" => "X",
                     _ => {
                         if status == "in_progress" {
@@ -1,22 +1,41 @@
 use crate::tracking;
+use crate::utils::theme;
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::process::Command;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone)]
 pub enum GitCommand {
     Diff,
     Log,
     Status,
-    Show,
+    /// `full`: append the complete patch after the header and diffstat
+    Show {
+        full: bool,
+    },
     Add,
-    Commit { message: String },
+    Commit {
+        message: String,
+    },
     Push,
     Pull,
-    Branch,
+    /// `stale`: flag branches whose last commit is older than this many days
+    Branch {
+        stale: Option<u64>,
+    },
     Fetch,
-    Stash { subcommand: Option<String> },
+    Clone,
+    Stash {
+        subcommand: Option<String>,
+    },
     Worktree,
+    Blame,
+    /// `limit`: tags listed, newest first
+    Tag {
+        limit: usize,
+    },
 }
 
 pub fn run(cmd: GitCommand, args: &[String], max_lines: Option<usize>, verbose: u8) -> Result<()> {
@@ -24,15 +43,18 @@ pub fn run(cmd: GitCommand, args: &[String], max_lines: Option<usize>, verbose:
         GitCommand::Diff => run_diff(args, max_lines, verbose),
         GitCommand::Log => run_log(args, max_lines, verbose),
         GitCommand::Status => run_status(args, verbose),
-        GitCommand::Show => run_show(args, max_lines, verbose),
+        GitCommand::Show { full } => run_show(args, full, max_lines, verbose),
         GitCommand::Add => run_add(args, verbose),
         GitCommand::Commit { message } => run_commit(&message, verbose),
         GitCommand::Push => run_push(args, verbose),
         GitCommand::Pull => run_pull(args, verbose),
-        GitCommand::Branch => run_branch(args, verbose),
+        GitCommand::Branch { stale } => run_branch(args, stale, verbose),
         GitCommand::Fetch => run_fetch(args, verbose),
+        GitCommand::Clone => run_clone(args, verbose),
         GitCommand::Stash { subcommand } => run_stash(subcommand.as_deref(), args, verbose),
         GitCommand::Worktree => run_worktree(args, verbose),
+        GitCommand::Blame => run_blame(args, verbose),
+        GitCommand::Tag { limit } => run_tag(args, limit, verbose),
     }
 }
 
@@ -92,7 +114,8 @@ fn run_diff(args: &[String], max_lines: Option<usize>, verbose: u8) -> Result<()
     }
 
     // Print stat summary first
-    println!("{}", stat_stdout.trim());
+    let stat = format_diff_stat(&stat_stdout);
+    println!("{}", stat);
 
     // Now get actual diff but compact it
     let mut diff_cmd = Command::new("git");
@@ -104,7 +127,7 @@ fn run_diff(args: &[String], max_lines: Option<usize>, verbose: u8) -> Result<()
     let diff_output = diff_cmd.output().context("Failed to run git diff")?;
     let diff_stdout = String::from_utf8_lossy(&diff_output.stdout);
 
-    let mut final_output = stat_stdout.to_string();
+    let mut final_output = stat;
     if !diff_stdout.is_empty() {
         println!("\n--- Changes ---");
         let compacted = compact_diff(&diff_stdout, max_lines.unwrap_or(100));
@@ -123,7 +146,7 @@ fn run_diff(args: &[String], max_lines: Option<usize>, verbose: u8) -> Result<()
     Ok(())
 }
 
-fn run_show(args: &[String], max_lines: Option<usize>, verbose: u8) -> Result<()> {
+fn run_show(args: &[String], full: bool, max_lines: Option<usize>, verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
     // If user wants --stat or --format only, pass through
@@ -171,52 +194,58 @@ fn run_show(args: &[String], max_lines: Option<usize>, verbose: u8) -> Result<()
         .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
         .unwrap_or_default();
 
-    // Step 1: one-line commit summary
-    let mut summary_cmd = Command::new("git");
-    summary_cmd.args(["show", "--no-patch", "--pretty=format:%h %s (%ar) <%an>"]);
+    // Step 1: commit header (fields NUL-separated so any subject/body survives)
+    let mut header_cmd = Command::new("git");
+    header_cmd.args([
+        "show",
+        "--no-patch",
+        "--date=short",
+        "--pretty=format:%h%x00%an%x00%ad%x00%s%x00%b",
+    ]);
     for arg in args {
-        summary_cmd.arg(arg);
+        header_cmd.arg(arg);
     }
-    let summary_output = summary_cmd.output().context("Failed to run git show")?;
-    if !summary_output.status.success() {
-        let stderr = String::from_utf8_lossy(&summary_output.stderr);
+    let header_output = header_cmd.output().context("Failed to run git show")?;
+    if !header_output.status.success() {
+        let stderr = String::from_utf8_lossy(&header_output.stderr);
         eprintln!("{}", stderr);
-        std::process::exit(summary_output.status.code().unwrap_or(1));
+        std::process::exit(header_output.status.code().unwrap_or(1));
     }
-    let summary = String::from_utf8_lossy(&summary_output.stdout);
-    println!("{}", summary.trim());
+    let header = format_show_header(&String::from_utf8_lossy(&header_output.stdout));
+    println!("{}", header);
 
-    // Step 2: --stat summary
+    // Step 2: --stat summary, as `rtk git diff` shows it
     let mut stat_cmd = Command::new("git");
     stat_cmd.args(["show", "--stat", "--pretty=format:"]);
     for arg in args {
         stat_cmd.arg(arg);
     }
     let stat_output = stat_cmd.output().context("Failed to run git show --stat")?;
-    let stat_stdout = String::from_utf8_lossy(&stat_output.stdout);
-    let stat_text = stat_stdout.trim();
+    let stat_text = format_diff_stat(&String::from_utf8_lossy(&stat_output.stdout));
+    let mut final_output = header;
     if !stat_text.is_empty() {
-        println!("{}", stat_text);
+        println!("\n{}", stat_text);
+        final_output.push_str(&format!("\n\n{}", stat_text));
     }
 
-    // Step 3: compacted diff
-    let mut diff_cmd = Command::new("git");
-    diff_cmd.args(["show", "--pretty=format:"]);
-    for arg in args {
-        diff_cmd.arg(arg);
-    }
-    let diff_output = diff_cmd.output().context("Failed to run git show (diff)")?;
-    let diff_stdout = String::from_utf8_lossy(&diff_output.stdout);
-    let diff_text = diff_stdout.trim();
-
-    let mut final_output = summary.to_string();
-    if !diff_text.is_empty() {
-        if verbose > 0 {
-            println!("\n--- Changes ---");
+    // Step 3: the patch itself, only with --full, compacted like `rtk git diff`
+    if full {
+        let mut diff_cmd = Command::new("git");
+        diff_cmd.args(["show", "--pretty=format:"]);
+        for arg in args {
+            diff_cmd.arg(arg);
+        }
+        let diff_output = diff_cmd.output().context("Failed to run git show (diff)")?;
+        let diff_stdout = String::from_utf8_lossy(&diff_output.stdout);
+        let diff_text = diff_stdout.trim();
+        if !diff_text.is_empty() {
+            if verbose > 0 {
+                println!("\n--- Changes ---");
+            }
+            let compacted = compact_diff(diff_text, max_lines.unwrap_or(100));
+            println!("{}", compacted);
+            final_output.push_str(&format!("\n{}", compacted));
         }
-        let compacted = compact_diff(diff_text, max_lines.unwrap_or(100));
-        println!("{}", compacted);
-        final_output.push_str(&format!("\n{}", compacted));
     }
 
     timer.track(
@@ -229,6 +258,55 @@ fn run_show(args: &[String], max_lines: Option<usize>, verbose: u8) -> Result<()
     Ok(())
 }
 
+/// `abc1234 Alice 2023-05-01` over the subject, then the body indented with blank runs
+/// collapsed. Reads `%h%x00%an%x00%ad%x00%s%x00%b`.
+fn format_show_header(raw: &str) -> String {
+    let mut fields = raw.splitn(5, '\0');
+    let mut next = || fields.next().unwrap_or("").trim();
+    let (hash, author, date, subject, body) = (next(), next(), next(), next(), next());
+
+    let mut lines = vec![format!("{} {} {}", hash, author, date), subject.to_string()];
+    let mut previous_blank = true;
+    for line in body.lines() {
+        let blank = line.trim().is_empty();
+        if blank && previous_blank {
+            continue;
+        }
+        previous_blank = blank;
+        lines.push(if blank {
+            String::new()
+        } else {
+            format!("  {}", line.trim_end())
+        });
+    }
+    if lines.len() > 2 {
+        lines.insert(2, String::new());
+    }
+    lines.join("\n").trim_end().to_string()
+}
+
+/// `git --stat` output without the `+++--` graph: `src/main.rs | 12` per file, then the
+/// `N files changed, ...` summary. Shared by `rtk git diff` and `rtk git show`.
+fn format_diff_stat(stat: &str) -> String {
+    stat.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.rsplit_once(" | ") {
+            Some((path, change)) => {
+                let change = change.trim();
+                // `12 +++---` keeps the count; `Bin 0 -> 1234 bytes` stays as is
+                let count = match change.split_once(' ') {
+                    Some((n, graph)) if graph.chars().all(|c| c == '+' || c == '-') => n,
+                    _ => change,
+                };
+                format!("{} | {}", path.trim_end(), count)
+            }
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub(crate) fn compact_diff(diff: &str, max_lines: usize) -> String {
     let mut result = Vec::new();
     let mut current_file = String::new();
@@ -480,7 +558,11 @@ fn format_status_output(porcelain: &str) -> String {
     }
 
     if conflicts > 0 {
-        output.push_str(&format!("⚠️  Conflicts: {} files\n", conflicts));
+        output.push_str(&format!(
+            "{} Conflicts: {} files\n",
+            theme().warn,
+            conflicts
+        ));
     }
 
     output.trim_end().to_string()
@@ -517,7 +599,7 @@ fn filter_status_with_args(output: &str) -> String {
     }
 
     if result.is_empty() {
-        "ok ✓".to_string()
+        format!("ok {}", theme().ok)
     } else {
         result.join("\n")
     }
@@ -626,9 +708,9 @@ fn run_add(args: &[String], verbose: u8) -> Result<()> {
             // Parse "1 file changed, 5 insertions(+)" format
             let short = stat.lines().last().unwrap_or("").trim();
             if short.is_empty() {
-                "ok ✓".to_string()
+                format!("ok {}", theme().ok)
             } else {
-                format!("ok ✓ {}", short)
+                format!("ok {} {}", theme().ok, short)
             }
         };
 
@@ -674,22 +756,7 @@ fn run_commit(message: &str, verbose: u8) -> Result<()> {
     let raw_output = format!("{}\n{}", stdout, stderr);
 
     if output.status.success() {
-        // Extract commit hash from output like "[main abc1234] message"
-        let compact = if let Some(line) = stdout.lines().next() {
-            if let Some(hash_start) = line.find(' ') {
-                let hash = line[1..hash_start].split(' ').last().unwrap_or("");
-                if !hash.is_empty() && hash.len() >= 7 {
-                    format!("ok ✓ {}", &hash[..7.min(hash.len())])
-                } else {
-                    "ok ✓".to_string()
-                }
-            } else {
-                "ok ✓".to_string()
-            }
-        } else {
-            "ok ✓".to_string()
-        };
-
+        let compact = format_commit_output(&stdout);
         println!("{}", compact);
 
         timer.track(
@@ -699,28 +766,93 @@ fn run_commit(message: &str, verbose: u8) -> Result<()> {
             &compact,
         );
     } else {
-        if stderr.contains("nothing to commit") || stdout.contains("nothing to commit") {
-            println!("ok (nothing to commit)");
-            timer.track(
-                &format!("git commit -m \"{}\"", message),
-                "rtk git commit",
-                &raw_output,
-                "ok (nothing to commit)",
-            );
-        } else {
-            eprintln!("FAILED: git commit");
-            if !stderr.trim().is_empty() {
-                eprintln!("{}", stderr);
-            }
-            if !stdout.trim().is_empty() {
-                eprintln!("{}", stdout);
-            }
-        }
+        // Nothing staged or a pre-commit hook rejected it: show why, not the template noise
+        let error = format_commit_error(&stdout, &stderr);
+        eprintln!("{}", error);
+        timer.track(
+            &format!("git commit -m \"{}\"", message),
+            "rtk git commit",
+            &raw_output,
+            &error,
+        );
+        std::process::exit(output.status.code().unwrap_or(1));
     }
 
     Ok(())
 }
 
+/// `[main abc1234] subject` + ` 5 files changed, 120 insertions(+), 30 deletions(-)`
+/// → `✓ committed abc1234: subject (5 files, +120 -30)`
+fn format_commit_output(stdout: &str) -> String {
+    let Some((hash, subject)) = stdout.lines().find_map(parse_commit_header) else {
+        return format!("ok {}", theme().ok);
+    };
+
+    let mut result = format!("{} committed {}: {}", theme().ok, hash, subject);
+    if let Some(stats) = stdout.lines().find_map(parse_commit_stats) {
+        result.push_str(&format!(" ({})", stats));
+    }
+    result
+}
+
+/// `[branch (root-commit) abc1234] subject` → (`abc1234`, `subject`)
+fn parse_commit_header(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('[')?;
+    let (refs, subject) = rest.split_once("] ")?;
+    let hash = refs.rsplit(' ').next()?;
+    if hash.len() < 7 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((hash, subject.trim()))
+}
+
+/// ` 5 files changed, 120 insertions(+), 30 deletions(-)` → `5 files, +120 -30`
+fn parse_commit_stats(line: &str) -> Option<String> {
+    let line = line.trim();
+    if !line.contains(" changed") {
+        return None;
+    }
+    let count = |marker: &str| {
+        line.split(", ")
+            .find(|part| part.contains(marker))
+            .and_then(|part| part.split_whitespace().next())
+            .and_then(|n| n.parse::<usize>().ok())
+            .unwrap_or(0)
+    };
+    let files = count(" changed");
+    Some(format!(
+        "{} file{}, +{} -{}",
+        files,
+        if files == 1 { "" } else { "s" },
+        count("insertion"),
+        count("deletion")
+    ))
+}
+
+/// The reason a commit was refused: "nothing to commit" or the hook's own output
+fn format_commit_error(stdout: &str, stderr: &str) -> String {
+    let combined = format!("{}\n{}", stdout, stderr);
+    if let Some(line) = combined
+        .lines()
+        .map(str::trim)
+        .find(|l| l.starts_with("nothing to commit") || l.starts_with("no changes added to commit"))
+    {
+        return format!("{} git commit: {}", theme().fail, line);
+    }
+
+    let details: Vec<&str> = stderr
+        .lines()
+        .chain(stdout.lines())
+        .map(str::trim_end)
+        .filter(|l| !l.trim().is_empty() && !l.starts_with("hint:"))
+        .collect();
+    if details.is_empty() {
+        format!("{} git commit failed", theme().fail)
+    } else {
+        format!("{} git commit failed\n{}", theme().fail, details.join("\n"))
+    }
+}
+
 fn run_push(args: &[String], verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
@@ -741,26 +873,7 @@ fn run_push(args: &[String], verbose: u8) -> Result<()> {
     let raw = format!("{}{}", stdout, stderr);
 
     if output.status.success() {
-        let compact = if stderr.contains("Everything up-to-date") {
-            "ok (up-to-date)".to_string()
-        } else {
-            let mut result = String::new();
-            for line in stderr.lines() {
-                if line.contains("->") {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 3 {
-                        result = format!("ok ✓ {}", parts[parts.len() - 1]);
-                        break;
-                    }
-                }
-            }
-            if !result.is_empty() {
-                result
-            } else {
-                "ok ✓".to_string()
-            }
-        };
-
+        let compact = filter_push_output(&raw);
         println!("{}", compact);
 
         timer.track(
@@ -771,12 +884,11 @@ fn run_push(args: &[String], verbose: u8) -> Result<()> {
         );
     } else {
         eprintln!("FAILED: git push");
-        if !stderr.trim().is_empty() {
-            eprintln!("{}", stderr);
-        }
-        if !stdout.trim().is_empty() {
-            eprintln!("{}", stdout);
-        }
+        eprintln!(
+            "{}",
+            filter_git_failure(&raw, &["[rejected]", "[remote rejected]"])
+        );
+        std::process::exit(output.status.code().unwrap_or(1));
     }
 
     Ok(())
@@ -802,50 +914,7 @@ fn run_pull(args: &[String], verbose: u8) -> Result<()> {
     let raw_output = format!("{}\n{}", stdout, stderr);
 
     if output.status.success() {
-        let compact =
-            if stdout.contains("Already up to date") || stdout.contains("Already up-to-date") {
-                "ok (up-to-date)".to_string()
-            } else {
-                // Count files changed
-                let mut files = 0;
-                let mut insertions = 0;
-                let mut deletions = 0;
-
-                for line in stdout.lines() {
-                    if line.contains("file") && line.contains("changed") {
-                        // Parse "3 files changed, 10 insertions(+), 2 deletions(-)"
-                        for part in line.split(',') {
-                            let part = part.trim();
-                            if part.contains("file") {
-                                files = part
-                                    .split_whitespace()
-                                    .next()
-                                    .and_then(|n| n.parse().ok())
-                                    .unwrap_or(0);
-                            } else if part.contains("insertion") {
-                                insertions = part
-                                    .split_whitespace()
-                                    .next()
-                                    .and_then(|n| n.parse().ok())
-                                    .unwrap_or(0);
-                            } else if part.contains("deletion") {
-                                deletions = part
-                                    .split_whitespace()
-                                    .next()
-                                    .and_then(|n| n.parse().ok())
-                                    .unwrap_or(0);
-                            }
-                        }
-                    }
-                }
-
-                if files > 0 {
-                    format!("ok ✓ {} files +{} -{}", files, insertions, deletions)
-                } else {
-                    "ok ✓".to_string()
-                }
-            };
-
+        let compact = filter_pull_output(&raw_output);
         println!("{}", compact);
 
         timer.track(
@@ -856,18 +925,115 @@ fn run_pull(args: &[String], verbose: u8) -> Result<()> {
         );
     } else {
         eprintln!("FAILED: git pull");
-        if !stderr.trim().is_empty() {
-            eprintln!("{}", stderr);
-        }
-        if !stdout.trim().is_empty() {
-            eprintln!("{}", stdout);
-        }
+        eprintln!("{}", filter_git_failure(&raw_output, &["CONFLICT"]));
+        std::process::exit(output.status.code().unwrap_or(1));
     }
 
     Ok(())
 }
 
-fn run_branch(args: &[String], verbose: u8) -> Result<()> {
+/// Transfer meters git redraws in place with `\r` (prefixed `remote: ` when server-side)
+const GIT_PROGRESS_PREFIXES: &[&str] = &[
+    "Enumerating objects",
+    "Counting objects",
+    "Compressing objects",
+    "Writing objects",
+    "Receiving objects",
+    "Resolving deltas",
+    "Unpacking objects",
+    "Delta compression",
+    "Total ",
+];
+
+/// Final state of each line (text after the last `\r`), minus progress meters and blanks
+fn strip_git_progress(output: &str) -> Vec<&str> {
+    output
+        .split('\n')
+        .filter_map(|line| line.trim_end_matches('\r').rsplit('\r').next())
+        .filter(|line| {
+            let text = line.trim_start();
+            let text = text.strip_prefix("remote:").map_or(text, str::trim_start);
+            !text.is_empty() && !GIT_PROGRESS_PREFIXES.iter().any(|p| text.starts_with(p))
+        })
+        .collect()
+}
+
+/// `ok ✓ abc1234..def5678 main -> main` per updated ref
+fn filter_push_output(output: &str) -> String {
+    let lines = strip_git_progress(output);
+    if lines.iter().any(|l| l.contains("Everything up-to-date")) {
+        return "ok (up-to-date)".to_string();
+    }
+
+    let updates: Vec<String> = lines
+        .iter()
+        .filter(|l| l.contains(" -> ") && !l.trim_start().starts_with("remote:"))
+        .map(|l| {
+            format!(
+                "ok {} {}",
+                theme().ok,
+                l.split_whitespace().collect::<Vec<_>>().join(" ")
+            )
+        })
+        .collect();
+    if updates.is_empty() {
+        format!("ok {}", theme().ok)
+    } else {
+        updates.join("\n")
+    }
+}
+
+/// `ok ✓ Fast-forward abc1234..def5678: 3 files, +10 -2`
+fn filter_pull_output(output: &str) -> String {
+    let lines = strip_git_progress(output);
+    if lines
+        .iter()
+        .any(|l| l.contains("Already up to date") || l.contains("Already up-to-date"))
+    {
+        return "ok (up-to-date)".to_string();
+    }
+
+    let range = lines
+        .iter()
+        .find_map(|l| l.strip_prefix("Updating "))
+        .map(str::trim);
+    let how = lines
+        .iter()
+        .map(|l| l.trim())
+        .find(|l| *l == "Fast-forward" || l.starts_with("Merge made by"))
+        .map(|l| l.trim_end_matches('.'));
+
+    let mut result = format!("ok {}", theme().ok);
+    for part in [how, range].into_iter().flatten() {
+        result.push(' ');
+        result.push_str(part);
+    }
+    if let Some(stats) = lines.iter().find_map(|l| parse_commit_stats(l)) {
+        result.push_str(&format!(": {}", stats));
+    }
+    result
+}
+
+/// Why a push/pull failed: `error:`/`fatal:` lines plus any lines containing `markers`
+fn filter_git_failure(output: &str, markers: &[&str]) -> String {
+    let lines = strip_git_progress(output);
+    let relevant: Vec<&str> = lines
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| {
+            l.starts_with("error:")
+                || l.starts_with("fatal:")
+                || markers.iter().any(|m| l.contains(m))
+        })
+        .collect();
+    if relevant.is_empty() {
+        lines.join("\n")
+    } else {
+        relevant.join("\n")
+    }
+}
+
+fn run_branch(args: &[String], stale: Option<u64>, verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
     if verbose > 0 {
@@ -891,8 +1057,9 @@ fn run_branch(args: &[String], verbose: u8) -> Result<()> {
         let stderr = String::from_utf8_lossy(&output.stderr);
         let combined = format!("{}{}", stdout, stderr);
 
+        let ok = format!("ok {}", theme().ok);
         let msg = if output.status.success() {
-            "ok ✓"
+            ok.as_str()
         } else {
             &combined
         };
@@ -905,7 +1072,7 @@ fn run_branch(args: &[String], verbose: u8) -> Result<()> {
         );
 
         if output.status.success() {
-            println!("ok ✓");
+            println!("ok {}", theme().ok);
         } else {
             eprintln!("FAILED: git branch");
             if !stderr.trim().is_empty() {
@@ -928,7 +1095,20 @@ fn run_branch(args: &[String], verbose: u8) -> Result<()> {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let raw = stdout.to_string();
 
-    let filtered = filter_branch_output(&stdout);
+    // Upstream tracking and last commit date of local branches, for the annotations
+    let refs = Command::new("git")
+        .args([
+            "for-each-ref",
+            "--format=%(refname:short)%09%(upstream:short)%09%(upstream:track)%09%(committerdate:unix)",
+            "refs/heads",
+        ])
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        .unwrap_or_default();
+    let now = chrono::Utc::now().timestamp();
+    let status = branch_status(&refs, now, stale);
+
+    let filtered = filter_branch_output(&stdout, &status);
     println!("{}", filtered);
 
     timer.track(
@@ -941,7 +1121,46 @@ fn run_branch(args: &[String], verbose: u8) -> Result<()> {
     Ok(())
 }
 
-fn filter_branch_output(output: &str) -> String {
+/// `(up to date)` / `(3 ahead, 1 behind, stale 45d)` per local branch, from
+/// `git for-each-ref` lines of `name<TAB>upstream<TAB>[ahead 3, behind 1]<TAB>unix time`
+fn branch_status(refs: &str, now: i64, stale_days: Option<u64>) -> HashMap<String, String> {
+    let mut status = HashMap::new();
+    for line in refs.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [name, upstream, track, date] = fields[..] else {
+            continue;
+        };
+
+        let mut parts: Vec<String> = Vec::new();
+        let track = track.trim_start_matches('[').trim_end_matches(']');
+        if track == "gone" {
+            parts.push("upstream gone".to_string());
+        } else if !track.is_empty() {
+            // "ahead 3, behind 1" -> "3 ahead, 1 behind"
+            for part in track.split(", ") {
+                if let Some((word, n)) = part.split_once(' ') {
+                    parts.push(format!("{} {}", n, word));
+                }
+            }
+        } else if !upstream.is_empty() {
+            parts.push("up to date".to_string());
+        }
+
+        if let (Some(days), Ok(committed)) = (stale_days, date.parse::<i64>()) {
+            let age = (now - committed).max(0) / 86_400;
+            if age as u64 > days {
+                parts.push(format!("stale {}d", age));
+            }
+        }
+
+        if !parts.is_empty() {
+            status.insert(name.to_string(), format!(" ({})", parts.join(", ")));
+        }
+    }
+    status
+}
+
+fn filter_branch_output(output: &str, status: &HashMap<String, String>) -> String {
     let mut current = String::new();
     let mut local: Vec<String> = Vec::new();
     let mut remote: Vec<String> = Vec::new();
@@ -966,12 +1185,13 @@ fn filter_branch_output(output: &str) -> String {
         }
     }
 
+    let annotation = |name: &str| status.get(name).map_or("", String::as_str);
     let mut result = Vec::new();
-    result.push(format!("* {}", current));
+    result.push(format!("* {}{}", current, annotation(&current)));
 
     if !local.is_empty() {
         for b in &local {
-            result.push(format!("  {}", b));
+            result.push(format!("  {}{}", b, annotation(b)));
         }
     }
 
@@ -1021,17 +1241,8 @@ fn run_fetch(args: &[String], verbose: u8) -> Result<()> {
         return Ok(());
     }
 
-    // Count new refs from stderr (git fetch outputs to stderr)
-    let new_refs: usize = stderr
-        .lines()
-        .filter(|l| l.contains("->") || l.contains("[new"))
-        .count();
-
-    let msg = if new_refs > 0 {
-        format!("ok fetched ({} new refs)", new_refs)
-    } else {
-        "ok fetched".to_string()
-    };
+    // git fetch reports on stderr
+    let msg = filter_fetch_output(&stderr);
 
     println!("{}", msg);
     timer.track("git fetch", "rtk git fetch", &raw, &msg);
@@ -1039,6 +1250,188 @@ fn run_fetch(args: &[String], verbose: u8) -> Result<()> {
     Ok(())
 }
 
+/// `fetched: 3 refs updated, 1 new branch` over the ref lines (`abc..def main -> origin/main`,
+/// `[new branch] topic -> origin/topic`, `pruned origin/old`); `remote:` and transfer
+/// progress is dropped, including the `\r`-overwritten steps.
+fn filter_fetch_output(stderr: &str) -> String {
+    let mut refs = Vec::new();
+    let (mut updated, mut branches, mut tags, mut pruned) = (0, 0, 0, 0);
+
+    for line in stderr.split(['\n', '\r']) {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if !words.contains(&"->") {
+            continue;
+        }
+        // Leading flag: ' ' fast-forward, '+' forced, '*' new, '-' pruned, 't' tag update
+        let words = match words[0] {
+            "+" | "*" | "-" | "t" | "!" | "=" => &words[1..],
+            _ => &words[..],
+        };
+        let entry = words.join(" ");
+        if entry.starts_with("[up to date]") {
+            continue;
+        }
+        if entry.starts_with("[deleted]") {
+            pruned += 1;
+            refs.push(format!("pruned {}", words.last().unwrap_or(&"")));
+            continue;
+        }
+        if entry.starts_with("[new branch]") || entry.starts_with("[new ref]") {
+            branches += 1;
+        } else if entry.starts_with("[new tag]") {
+            tags += 1;
+        } else {
+            updated += 1;
+        }
+        refs.push(entry);
+    }
+
+    if refs.is_empty() {
+        return format!("{} git fetch: up to date", theme().ok);
+    }
+
+    let plural =
+        |n: usize, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+    let mut parts = Vec::new();
+    if updated > 0 {
+        parts.push(plural(updated, "ref updated", "refs updated"));
+    }
+    if branches > 0 {
+        parts.push(plural(branches, "new branch", "new branches"));
+    }
+    if tags > 0 {
+        parts.push(plural(tags, "new tag", "new tags"));
+    }
+    if pruned > 0 {
+        parts.push(plural(pruned, "pruned", "pruned"));
+    }
+
+    let mut lines = vec![format!("fetched: {}", parts.join(", "))];
+    lines.extend(refs.iter().map(|r| format!("  {}", r)));
+    lines.join("\n")
+}
+
+fn run_clone(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("git clone {}", args.join(" "));
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.arg("clone");
+    // Without a terminal git skips the progress that carries the object count and size
+    if !args
+        .iter()
+        .any(|a| a == "-q" || a == "--quiet" || a == "--no-progress")
+    {
+        cmd.arg("--progress");
+    }
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    let output = cmd.output().context("Failed to run git clone")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}{}", stdout, stderr);
+
+    if !output.status.success() {
+        eprintln!("FAILED: git clone");
+        eprintln!("{}", filter_git_failure(&raw, &[]));
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let msg = filter_clone_output(clone_repository(args).unwrap_or(""), &stderr);
+    println!("{}", msg);
+    timer.track(
+        &format!("git clone {}", args.join(" ")),
+        &format!("rtk git clone {}", args.join(" ")),
+        &raw,
+        &msg,
+    );
+
+    Ok(())
+}
+
+/// The repository operand of `git clone [options] <repo> [<dir>]`
+fn clone_repository(args: &[String]) -> Option<&str> {
+    // Options whose value is a separate argument
+    const WITH_VALUE: &[&str] = &[
+        "-b",
+        "--branch",
+        "-o",
+        "--origin",
+        "-c",
+        "--config",
+        "-u",
+        "--upload-pack",
+        "-j",
+        "--jobs",
+        "--depth",
+        "--reference",
+        "--reference-if-able",
+        "--separate-git-dir",
+        "--template",
+        "--filter",
+        "--shallow-since",
+        "--shallow-exclude",
+        "--server-option",
+    ];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if WITH_VALUE.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with('-') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+/// `✓ git clone: <repo> (1,234 objects, 5.20 MiB)` from the final `Receiving objects:
+/// 100% (N/N), X done` step; local clones, which receive nothing, get the bare line.
+fn filter_clone_output(repo: &str, stderr: &str) -> String {
+    static RECEIVING_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let receiving_re = RECEIVING_RE.get_or_init(|| {
+        regex::Regex::new(r"Receiving objects: 100% \(\d+/(\d+)\)(?:, ([\d.]+ \w+))?")
+            .expect("invalid receiving regex")
+    });
+
+    let received = stderr
+        .split(['\n', '\r'])
+        .filter_map(|line| receiving_re.captures(line))
+        .last();
+    match received {
+        Some(caps) => {
+            let objects = thousands(&caps[1]);
+            match caps.get(2) {
+                Some(size) => format!(
+                    "{} git clone: {} ({} objects, {})",
+                    theme().ok,
+                    repo,
+                    objects,
+                    size.as_str()
+                ),
+                None => format!("{} git clone: {} ({} objects)", theme().ok, repo, objects),
+            }
+        }
+        None => format!("{} git clone: {}", theme().ok, repo),
+    }
+}
+
+/// `1234567` -> `1,234,567`
+fn thousands(digits: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(c);
+    }
+    result
+}
+
 fn run_stash(subcommand: Option<&str>, args: &[String], verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
@@ -1048,8 +1441,9 @@ fn run_stash(subcommand: Option<&str>, args: &[String], verbose: u8) -> Result<(
 
     match subcommand {
         Some("list") => {
+            // Selector, reflog subject and creation time, tab-separated, for the age column
             let output = Command::new("git")
-                .args(["stash", "list"])
+                .args(["stash", "list", "--format=%gd%x09%gs%x09%ct"])
                 .output()
                 .context("Failed to run git stash list")?;
             let stdout = String::from_utf8_lossy(&output.stdout);
@@ -1062,7 +1456,7 @@ fn run_stash(subcommand: Option<&str>, args: &[String], verbose: u8) -> Result<(
                 return Ok(());
             }
 
-            let filtered = filter_stash_list(&stdout);
+            let filtered = filter_stash_list(&stdout, chrono::Utc::now().timestamp());
             println!("{}", filtered);
             timer.track("git stash list", "rtk git stash list", &raw, &filtered);
         }
@@ -1156,23 +1550,205 @@ fn run_stash(subcommand: Option<&str>, args: &[String], verbose: u8) -> Result<(
     Ok(())
 }
 
-fn filter_stash_list(output: &str) -> String {
-    // Format: "stash@{0}: WIP on main: abc1234 commit message"
+/// `stash@{0}: on main "fix login" — 2d ago`, one line per stash.
+///
+/// Reads `stash@{N}<TAB>subject<TAB>unix time` (from `--format=%gd%x09%gs%x09%ct`);
+/// plain `git stash list` lines (`stash@{0}: WIP on main: abc1234 msg`) work too, without the age.
+fn filter_stash_list(output: &str, now: i64) -> String {
     let mut result = Vec::new();
-    for line in output.lines() {
-        if let Some(colon_pos) = line.find(": ") {
-            let index = &line[..colon_pos];
-            let rest = &line[colon_pos + 2..];
-            // Compact: strip "WIP on branch:" prefix if present
-            let message = if let Some(second_colon) = rest.find(": ") {
-                rest[second_colon + 2..].trim()
-            } else {
-                rest.trim()
-            };
-            result.push(format!("{}: {}", index, message));
-        } else {
-            result.push(line.to_string());
+    for line in output.lines().filter(|l| !l.trim().is_empty()) {
+        let (index, subject, created) = match line.split('\t').collect::<Vec<_>>()[..] {
+            [index, subject, created] => (index, subject, created.trim().parse::<i64>().ok()),
+            _ => match line.split_once(": ") {
+                Some((index, subject)) => (index, subject, None),
+                None => {
+                    result.push(line.to_string());
+                    continue;
+                }
+            },
+        };
+
+        // "WIP on main: abc1234 fix login" / "On feature: wip"
+        let (branch, message) = match subject.split_once(": ") {
+            Some((head, message)) => {
+                let branch = head
+                    .strip_prefix("WIP on ")
+                    .or_else(|| head.strip_prefix("On "))
+                    .unwrap_or(head);
+                (Some(branch), message.trim())
+            }
+            None => (None, subject.trim()),
+        };
+
+        let mut entry = match branch {
+            Some(branch) => format!("{}: on {} \"{}\"", index, branch, message),
+            None => format!("{}: \"{}\"", index, message),
+        };
+        if let Some(created) = created {
+            entry.push_str(&format!(" — {}", relative_age(now - created)));
+        }
+        result.push(entry);
+    }
+    result.join("\n")
+}
+
+/// `just now`, `5m ago`, `3h ago`, `2d ago`
+fn relative_age(seconds: i64) -> String {
+    match seconds.max(0) {
+        s if s < 60 => "just now".to_string(),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86_400 => format!("{}h ago", s / 3600),
+        s => format!("{}d ago", s / 86_400),
+    }
+}
+
+fn run_tag(args: &[String], limit: usize, verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("git tag {}", args.join(" "));
+    }
+
+    // Creating, deleting or verifying a tag: pass through
+    let listing = args.is_empty() || args.iter().any(|a| a == "-l" || a == "--list");
+    if !listing {
+        let output = Command::new("git")
+            .arg("tag")
+            .args(args)
+            .output()
+            .context("Failed to run git tag")?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let raw = format!("{}{}", stdout, stderr);
+        if !output.status.success() {
+            eprintln!("FAILED: git tag");
+            eprintln!("{}", filter_git_failure(&raw, &[]));
+            std::process::exit(output.status.code().unwrap_or(1));
+        }
+        let msg = match stdout.trim() {
+            "" => format!("ok {}", theme().ok),
+            out => out.to_string(),
+        };
+        println!("{}", msg);
+        timer.track(
+            &format!("git tag {}", args.join(" ")),
+            &format!("rtk git tag {}", args.join(" ")),
+            &raw,
+            &msg,
+        );
+        return Ok(());
+    }
+
+    let output = Command::new("git")
+        .args([
+            "tag",
+            "--list",
+            "--format=%(refname:short)%09%(creatordate:unix)",
+        ])
+        .args(args.iter().filter(|a| *a != "-l" && *a != "--list"))
+        .output()
+        .context("Failed to run git tag")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("FAILED: git tag");
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let filtered = filter_tag_list(&stdout, limit, chrono::Utc::now().timestamp());
+    println!("{}", filtered);
+    timer.track(
+        &format!("git tag {}", args.join(" ")),
+        &format!("rtk git tag {}", args.join(" ")),
+        &stdout,
+        &filtered,
+    );
+
+    Ok(())
+}
+
+/// `(major, minor, patch, pre-release)` of `v1.2.3`, `1.2`, `v2.0.0-rc.1`
+fn tag_version(tag: &str) -> Option<(u64, u64, u64, Option<&str>)> {
+    let version = tag.strip_prefix('v').unwrap_or(tag);
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch, pre))
+}
+
+/// `42 tags, latest v2.3.1 (3d ago)` over the first `limit` tags with their dates.
+///
+/// Version-like tags come first, highest version first (`v1.10` above `v1.9`, a release
+/// above its pre-releases); other tags follow, newest first.
+fn filter_tag_list(output: &str, limit: usize, now: i64) -> String {
+    let mut tags: Vec<(&str, Option<i64>)> = output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| match line.split_once('\t') {
+            Some((name, date)) => (name, date.trim().parse().ok()),
+            None => (line, None),
+        })
+        .collect();
+    if tags.is_empty() {
+        return "No tags".to_string();
+    }
+
+    tags.sort_by(|(a, a_date), (b, b_date)| {
+        match (tag_version(a), tag_version(b)) {
+            (Some((a1, a2, a3, a_pre)), Some((b1, b2, b3, b_pre))) => (b1, b2, b3)
+                .cmp(&(a1, a2, a3))
+                // No pre-release sorts above any pre-release of the same version
+                .then_with(|| match (a_pre, b_pre) {
+                    (None, None) => std::cmp::Ordering::Equal,
+                    (None, Some(_)) => std::cmp::Ordering::Less,
+                    (Some(_), None) => std::cmp::Ordering::Greater,
+                    (Some(a_pre), Some(b_pre)) => b_pre.cmp(a_pre),
+                }),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b_date.cmp(a_date),
         }
+        .then_with(|| a.cmp(b))
+    });
+
+    let age = |date: Option<i64>| date.map(|d| format!(" ({})", relative_age(now - d)));
+    let (latest, latest_date) = tags[0];
+    let mut result = vec![format!(
+        "{} tag{}, latest {}{}",
+        tags.len(),
+        if tags.len() == 1 { "" } else { "s" },
+        latest,
+        age(latest_date).unwrap_or_default()
+    )];
+
+    let width = tags
+        .iter()
+        .take(limit)
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (name, date) in tags.iter().take(limit) {
+        let date = date
+            .and_then(|d| chrono::DateTime::from_timestamp(d, 0))
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        result.push(
+            format!("  {:<width$}  {}", name, date, width = width)
+                .trim_end()
+                .to_string(),
+        );
+    }
+    if tags.len() > limit {
+        result.push(format!("  ... +{} more", tags.len() - limit));
     }
     result.join("\n")
 }
@@ -1200,8 +1776,9 @@ fn run_worktree(args: &[String], verbose: u8) -> Result<()> {
         let stderr = String::from_utf8_lossy(&output.stderr);
         let combined = format!("{}{}", stdout, stderr);
 
+        let ok = format!("ok {}", theme().ok);
         let msg = if output.status.success() {
-            "ok ✓"
+            ok.as_str()
         } else {
             &combined
         };
@@ -1214,7 +1791,7 @@ fn run_worktree(args: &[String], verbose: u8) -> Result<()> {
         );
 
         if output.status.success() {
-            println!("ok ✓");
+            println!("ok {}", theme().ok);
         } else {
             eprintln!("FAILED: git worktree {}", args.join(" "));
             if !stderr.trim().is_empty() {
@@ -1267,6 +1844,151 @@ fn filter_worktree_list(output: &str) -> String {
     result.join("\n")
 }
 
+fn run_blame(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    // Machine formats were asked for explicitly: pass through
+    let wants_porcelain = args.iter().any(|a| {
+        a == "-p" || a == "--porcelain" || a == "--line-porcelain" || a == "--incremental"
+    });
+
+    let mut cmd = Command::new("git");
+    cmd.arg("blame");
+    if !wants_porcelain {
+        cmd.arg("--line-porcelain");
+    }
+    cmd.args(args);
+    if verbose > 0 {
+        eprintln!("Running: git blame --line-porcelain {}", args.join(" "));
+    }
+
+    let output = cmd.output().context("Failed to run git blame")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let filtered = if wants_porcelain {
+        stdout.trim().to_string()
+    } else {
+        filter_blame(&stdout)
+    };
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("git blame {}", args.join(" ")),
+        &format!("rtk git blame {}", args.join(" ")),
+        &stdout,
+        &filtered,
+    );
+
+    Ok(())
+}
+
+/// One blamed line from `--line-porcelain` output
+struct BlameLine {
+    commit: String,
+    line: usize,
+    author: String,
+    time: i64,
+    summary: String,
+}
+
+/// `lines 10-24: abc1234 Alice 2023-05-01 "subject"`, consecutive lines from the same
+/// commit collapsed into one range, under a `src/foo.rs: 15 lines, 3 commits` header.
+fn filter_blame(output: &str) -> String {
+    let mut file = String::new();
+    let mut lines: Vec<BlameLine> = Vec::new();
+    let mut current: Option<BlameLine> = None;
+
+    for line in output.lines() {
+        // Content lines are tab-prefixed and close the entry
+        if line.starts_with('\t') {
+            lines.extend(current.take());
+            continue;
+        }
+        let Some(entry) = current.as_mut() else {
+            // "<40-hex sha> <orig line> <final line> [<group size>]"
+            let mut fields = line.split_whitespace();
+            let (Some(commit), Some(_), Some(final_line)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            if commit.len() == 40 && commit.chars().all(|c| c.is_ascii_hexdigit()) {
+                current = Some(BlameLine {
+                    commit: commit.to_string(),
+                    line: final_line.parse().unwrap_or(0),
+                    author: String::new(),
+                    time: 0,
+                    summary: String::new(),
+                });
+            }
+            continue;
+        };
+        if let Some(author) = line.strip_prefix("author ") {
+            entry.author = author.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            entry.time = time.trim().parse().unwrap_or(0);
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            entry.summary = summary.to_string();
+        } else if let Some(name) = line.strip_prefix("filename ") {
+            file = name.to_string();
+        }
+    }
+
+    if lines.is_empty() {
+        return "(no lines blamed)".to_string();
+    }
+
+    // (first index, last index) into `lines` for each run of one commit on adjacent lines
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (i, blamed) in lines.iter().enumerate() {
+        match ranges.last_mut() {
+            Some((_, last))
+                if lines[*last].commit == blamed.commit && lines[*last].line + 1 == blamed.line =>
+            {
+                *last = i;
+            }
+            _ => ranges.push((i, i)),
+        }
+    }
+
+    let mut commits: Vec<&str> = lines.iter().map(|l| l.commit.as_str()).collect();
+    commits.sort_unstable();
+    commits.dedup();
+
+    let mut result = vec![format!(
+        "{}: {} lines, {} commit{}",
+        file,
+        lines.len(),
+        commits.len(),
+        if commits.len() == 1 { "" } else { "s" }
+    )];
+    for (first, last) in ranges {
+        let (start, end) = (&lines[first], &lines[last]);
+        let span = if first == last {
+            format!("line {}", start.line)
+        } else {
+            format!("lines {}-{}", start.line, end.line)
+        };
+        let date = chrono::DateTime::from_timestamp(start.time, 0)
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        result.push(format!(
+            "{}: {} {} {} \"{}\"",
+            span,
+            &start.commit[..7],
+            start.author,
+            date,
+            start.summary
+        ));
+    }
+    result.join("\n")
+}
+
 /// Runs an unsupported git subcommand by passing it through directly
 pub fn run_passthrough(args: &[OsString], verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
@@ -1310,10 +2032,79 @@ mod tests {
         assert!(result.contains("+"));
     }
 
+    #[test]
+    fn test_format_commit_output() {
+        let stdout = "[main 3f2a9c1] Add commit filter\n 5 files changed, 120 insertions(+), 30 deletions(-)\n create mode 100644 src/new.rs\n";
+        assert_eq!(
+            format_commit_output(stdout),
+            "✓ committed 3f2a9c1: Add commit filter (5 files, +120 -30)"
+        );
+
+        let root =
+            "[feature/x (root-commit) 0a1b2c3] Initial commit\n 1 file changed, 3 insertions(+)\n";
+        assert_eq!(
+            format_commit_output(root),
+            "✓ committed 0a1b2c3: Initial commit (1 file, +3 -0)"
+        );
+    }
+
+    #[test]
+    fn test_strip_git_progress() {
+        let stderr = "Enumerating objects: 5, done.\nCounting objects:  20% (1/5)\rCounting objects: 100% (5/5), done.\r\nWriting objects: 100% (3/3), 310 bytes | 310.00 KiB/s, done.\nTotal 3 (delta 2), reused 0 (delta 0)\nremote: Resolving deltas: 100% (2/2)\nTo github.com:me/app.git\n";
+        assert_eq!(strip_git_progress(stderr), vec!["To github.com:me/app.git"]);
+    }
+
+    #[test]
+    fn test_filter_push_output() {
+        let stderr = "Writing objects:  50% (1/2)\rWriting objects: 100% (2/2), done.\nTo github.com:me/app.git\n   3f2a9c1..8b7e6d5  main -> main\n * [new branch]      feat -> feat\n";
+        assert_eq!(
+            filter_push_output(stderr),
+            "ok ✓ 3f2a9c1..8b7e6d5 main -> main\nok ✓ * [new branch] feat -> feat"
+        );
+        assert_eq!(
+            filter_push_output("Everything up-to-date\n"),
+            "ok (up-to-date)"
+        );
+
+        let rejected = "To github.com:me/app.git\n ! [rejected]        main -> main (fetch first)\nerror: failed to push some refs to 'github.com:me/app.git'\nhint: Updates were rejected because the remote contains work\n";
+        assert_eq!(
+            filter_git_failure(rejected, &["[rejected]"]),
+            "! [rejected]        main -> main (fetch first)\nerror: failed to push some refs to 'github.com:me/app.git'"
+        );
+    }
+
+    #[test]
+    fn test_filter_pull_output() {
+        let output = "Updating 3f2a9c1..8b7e6d5\nFast-forward\n src/main.rs | 12 +++++++++---\n src/lib.rs  |  2 +-\n 2 files changed, 10 insertions(+), 4 deletions(-)\n\nremote: Counting objects: 100% (4/4)\rremote: Counting objects: 100% (4/4), done.\nUnpacking objects: 100% (4/4), done.\n";
+        assert_eq!(
+            filter_pull_output(output),
+            "ok ✓ Fast-forward 3f2a9c1..8b7e6d5: 2 files, +10 -4"
+        );
+        assert_eq!(
+            filter_pull_output("Already up to date.\n"),
+            "ok (up-to-date)"
+        );
+    }
+
+    #[test]
+    fn test_format_commit_error() {
+        let nothing = "On branch main\nnothing to commit, working tree clean\n";
+        assert_eq!(
+            format_commit_error(nothing, ""),
+            "✗ git commit: nothing to commit, working tree clean"
+        );
+
+        let hook = "cargo fmt check failed:\nDiff in src/main.rs at line 3\n";
+        assert_eq!(
+            format_commit_error("", hook),
+            "✗ git commit failed\ncargo fmt check failed:\nDiff in src/main.rs at line 3"
+        );
+    }
+
     #[test]
     fn test_filter_branch_output() {
         let output = "* main\n  feature/auth\n  fix/bug-123\n  remotes/origin/HEAD -> origin/main\n  remotes/origin/main\n  remotes/origin/feature/auth\n  remotes/origin/release/v2\n";
-        let result = filter_branch_output(output);
+        let result = filter_branch_output(output, &HashMap::new());
         assert!(result.contains("* main"));
         assert!(result.contains("feature/auth"));
         assert!(result.contains("fix/bug-123"));
@@ -1322,22 +2113,211 @@ mod tests {
         assert!(result.contains("release/v2"));
     }
 
+    #[test]
+    fn test_branch_status_ahead_behind_and_stale() {
+        let now = 1_700_000_000;
+        let day = 86_400;
+        let refs = format!(
+            "main\torigin/main\t\t{}\nfeature-x\torigin/feature-x\t[ahead 3]\t{}\nfix\torigin/fix\t[ahead 1, behind 2]\t{}\nold\torigin/old\t[gone]\t{}\nscratch\t\t\t{}\n",
+            now - day,
+            now - 45 * day,
+            now,
+            now - 90 * day,
+            now,
+        );
+        let status = branch_status(&refs, now, Some(30));
+        let output = "* main\n  feature-x\n  fix\n  old\n  scratch\n";
+        assert_eq!(
+            filter_branch_output(output, &status),
+            "* main (up to date)\n  feature-x (3 ahead, stale 45d)\n  fix (1 ahead, 2 behind)\n  old (upstream gone, stale 90d)\n  scratch"
+        );
+
+        let status = branch_status(&refs, now, None);
+        assert_eq!(status["feature-x"], " (3 ahead)");
+    }
+
     #[test]
     fn test_filter_branch_no_remotes() {
         let output = "* main\n  develop\n";
-        let result = filter_branch_output(output);
+        let result = filter_branch_output(output, &HashMap::new());
         assert!(result.contains("* main"));
         assert!(result.contains("develop"));
         assert!(!result.contains("remote-only"));
     }
 
+    #[test]
+    fn test_filter_fetch_output() {
+        let stderr = "remote: Enumerating objects: 12, done.\n\
+remote: Counting objects:  50% (6/12)\rremote: Counting objects: 100% (12/12), done.\n\
+Unpacking objects:  50% (3/6)\rUnpacking objects: 100% (6/6), 1.2 KiB | 300.00 KiB/s, done.\n\
+From github.com:org/repo\n\
+   abc1234..def5678  main       -> origin/main\n\
+ + 1111111...2222222 feature    -> origin/feature  (forced update)\n\
+ * [new branch]      topic      -> origin/topic\n\
+ * [new tag]         v1.0       -> v1.0\n\
+ - [deleted]         (none)     -> origin/old\n";
+        assert_eq!(
+            filter_fetch_output(stderr),
+            "fetched: 2 refs updated, 1 new branch, 1 new tag, 1 pruned\n\
+             \x20 abc1234..def5678 main -> origin/main\n\
+             \x20 1111111...2222222 feature -> origin/feature (forced update)\n\
+             \x20 [new branch] topic -> origin/topic\n\
+             \x20 [new tag] v1.0 -> v1.0\n\
+             \x20 pruned origin/old"
+        );
+        assert_eq!(filter_fetch_output(""), "✓ git fetch: up to date");
+        assert_eq!(
+            filter_fetch_output("remote: Counting objects: 100% (1/1), done.\r\n"),
+            "✓ git fetch: up to date"
+        );
+    }
+
+    #[test]
+    fn test_filter_tag_list() {
+        let day = 86_400;
+        let now = 1_700_000_000;
+        let output = format!(
+            "v1.9.0\t{}\nv1.10.0\t{}\nv2.0.0-rc.1\t{}\nv2.0.0\t{}\nnightly\t{}\nv1.2\t{}\n",
+            now - 90 * day,
+            now - 30 * day,
+            now - 10 * day,
+            now - 3 * day,
+            now - day,
+            now - 200 * day
+        );
+        assert_eq!(
+            filter_tag_list(&output, 4, now),
+            "6 tags, latest v2.0.0 (3d ago)\n\
+             \x20 v2.0.0       2023-11-11\n\
+             \x20 v2.0.0-rc.1  2023-11-04\n\
+             \x20 v1.10.0      2023-10-15\n\
+             \x20 v1.9.0       2023-08-16\n\
+             \x20 ... +2 more"
+        );
+        assert!(filter_tag_list(&output, 10, now)
+            .ends_with("  v1.2         2023-04-28\n  nightly      2023-11-13"));
+        assert_eq!(filter_tag_list("", 10, now), "No tags");
+    }
+
+    #[test]
+    fn test_filter_tag_list_by_date() {
+        let output = "beta\t1000\nalpha\t3000\ngamma\t2000\n";
+        assert_eq!(
+            filter_tag_list(output, 10, 3000 + 7200),
+            "3 tags, latest alpha (2h ago)\n\
+             \x20 alpha  1970-01-01\n\
+             \x20 gamma  1970-01-01\n\
+             \x20 beta   1970-01-01"
+        );
+        assert_eq!(tag_version("v1.2.3-rc.1"), Some((1, 2, 3, Some("rc.1"))));
+        assert_eq!(tag_version("1.2"), Some((1, 2, 0, None)));
+        assert_eq!(tag_version("release-2023"), None);
+        assert_eq!(tag_version("v1.2.3.4"), None);
+    }
+
+    #[test]
+    fn test_filter_clone_output() {
+        let stderr = "Cloning into 'rtk'...\n\
+remote: Enumerating objects: 1234, done.\n\
+remote: Counting objects:  50% (617/1234)\rremote: Counting objects: 100% (1234/1234), done.\n\
+Receiving objects:  10% (124/1234)\rReceiving objects: 100% (1234/1234), 5.20 MiB | 8.00 MiB/s, done.\n\
+Resolving deltas: 100% (700/700), done.\n";
+        assert_eq!(
+            filter_clone_output("https://github.com/org/rtk", stderr),
+            "✓ git clone: https://github.com/org/rtk (1,234 objects, 5.20 MiB)"
+        );
+        assert_eq!(
+            filter_clone_output("repo", "Receiving objects: 100% (3/3), done.\n"),
+            "✓ git clone: repo (3 objects)"
+        );
+        assert_eq!(
+            filter_clone_output("../repo", "Cloning into 'repo'...\ndone.\n"),
+            "✓ git clone: ../repo"
+        );
+    }
+
+    #[test]
+    fn test_clone_repository() {
+        let args: Vec<String> = ["--depth", "1", "-b", "main", "git@host:org/repo.git", "dir"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(clone_repository(&args), Some("git@host:org/repo.git"));
+        assert_eq!(clone_repository(&[]), None);
+        assert_eq!(thousands("1234567"), "1,234,567");
+        assert_eq!(thousands("999"), "999");
+    }
+
+    #[test]
+    fn test_format_diff_stat_drops_graph() {
+        let stat = " src/git.rs           | 42 +++++++++++++++++++++---------\n assets/logo.png      | Bin 0 -> 1234 bytes\n README.md            |  3 +++\n 3 files changed, 35 insertions(+), 10 deletions(-)\n";
+        assert_eq!(
+            format_diff_stat(stat),
+            "src/git.rs | 42\nassets/logo.png | Bin 0 -> 1234 bytes\nREADME.md | 3\n3 files changed, 35 insertions(+), 10 deletions(-)"
+        );
+    }
+
+    #[test]
+    fn test_format_show_header() {
+        let raw = "abc1234\0Alice\x002023-05-01\0Fix login redirect\0Keep the original URL.\n\n\n\nCloses #12\n";
+        assert_eq!(
+            format_show_header(raw),
+            "abc1234 Alice 2023-05-01\nFix login redirect\n\n  Keep the original URL.\n\n  Closes #12"
+        );
+        assert_eq!(
+            format_show_header("abc1234\0Alice\x002023-05-01\0Bump version\0"),
+            "abc1234 Alice 2023-05-01\nBump version"
+        );
+    }
+
+    #[test]
+    fn test_filter_blame_collapses_ranges() {
+        let entry = |sha: char, line: usize, author: &str, time: i64, summary: &str| {
+            format!(
+                "{sha} {line} {line}\nauthor {author}\nauthor-mail <a@x>\nauthor-time {time}\nauthor-tz +0000\ncommitter {author}\nsummary {summary}\nfilename src/lib.rs\n\tcode {line}\n",
+                sha = sha.to_string().repeat(40),
+            )
+        };
+        let output = [
+            entry('a', 10, "Alice", 1_682_899_200, "Add parser"),
+            entry('a', 11, "Alice", 1_682_899_200, "Add parser"),
+            entry('a', 12, "Alice", 1_682_899_200, "Add parser"),
+            entry('b', 13, "Bob Smith", 1_700_000_000, "Fix off-by-one"),
+            entry('a', 14, "Alice", 1_682_899_200, "Add parser"),
+        ]
+        .concat();
+        assert_eq!(
+            filter_blame(&output),
+            "src/lib.rs: 5 lines, 2 commits\n\
+             lines 10-12: aaaaaaa Alice 2023-05-01 \"Add parser\"\n\
+             line 13: bbbbbbb Bob Smith 2023-11-14 \"Fix off-by-one\"\n\
+             line 14: aaaaaaa Alice 2023-05-01 \"Add parser\""
+        );
+        assert_eq!(filter_blame(""), "(no lines blamed)");
+    }
+
     #[test]
     fn test_filter_stash_list() {
         let output =
             "stash@{0}: WIP on main: abc1234 fix login\nstash@{1}: On feature: def5678 wip\n";
-        let result = filter_stash_list(output);
-        assert!(result.contains("stash@{0}: abc1234 fix login"));
-        assert!(result.contains("stash@{1}: def5678 wip"));
+        let result = filter_stash_list(output, 0);
+        assert!(result.contains("stash@{0}: on main \"abc1234 fix login\""));
+        assert!(result.contains("stash@{1}: on feature \"def5678 wip\""));
+    }
+
+    #[test]
+    fn test_filter_stash_list_with_age() {
+        let now = 1_700_000_000;
+        let output = format!(
+            "stash@{{0}}\tOn main: wip\t{}\nstash@{{1}}\tWIP on feature/auth: abc1234 add login\t{}\nstash@{{2}}\tcustom message\t{}\n",
+            now - 2 * 86_400,
+            now - 3 * 3600,
+            now - 30
+        );
+        assert_eq!(
+            filter_stash_list(&output, now),
+            "stash@{0}: on main \"wip\" — 2d ago\nstash@{1}: on feature/auth \"abc1234 add login\" — 3h ago\nstash@{2}: \"custom message\" — just now"
+        );
     }
 
     #[test]
@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{theme, truncate};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -307,8 +307,10 @@ fn filter_go_test_json(output: &str) -> String {
 
     if total_fail == 0 {
         return format!(
-            "✓ Go test: {} passed in {} packages",
-            total_pass, total_packages
+            "{} Go test: {} passed in {} packages",
+            theme().ok,
+            total_pass,
+            total_packages
         );
     }
 
@@ -321,7 +323,7 @@ fn filter_go_test_json(output: &str) -> String {
         result.push_str(&format!(", {} skipped", total_skip));
     }
     result.push_str(&format!(" in {} packages\n", total_packages));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Show failed tests grouped by package
     for (package, pkg_result) in packages.iter() {
@@ -390,12 +392,12 @@ fn filter_go_build(output: &str) -> String {
     }
 
     if errors.is_empty() {
-        return "✓ Go build: Success".to_string();
+        return format!("{} Go build: Success", theme().ok);
     }
 
     let mut result = String::new();
     result.push_str(&format!("Go build: {} errors\n", errors.len()));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     for (i, error) in errors.iter().take(20).enumerate() {
         result.push_str(&format!("{}. {}\n", i + 1, truncate(error, 120)));
@@ -422,12 +424,12 @@ fn filter_go_vet(output: &str) -> String {
     }
 
     if issues.is_empty() {
-        return "✓ Go vet: No issues found".to_string();
+        return format!("{} Go vet: No issues found", theme().ok);
     }
 
     let mut result = String::new();
     result.push_str(&format!("Go vet: {} issues\n", issues.len()));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     for (i, issue) in issues.iter().take(20).enumerate() {
         result.push_str(&format!("{}. {}\n", i + 1, truncate(issue, 120)));
@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{theme, truncate};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -103,7 +103,7 @@ fn filter_golangci_json(output: &str) -> String {
     let issues = golangci_output.issues;
 
     if issues.is_empty() {
-        return "✓ golangci-lint: No issues found".to_string();
+        return format!("{} golangci-lint: No issues found", theme().ok);
     }
 
     let total_issues = issues.len();
@@ -134,7 +134,7 @@ fn filter_golangci_json(output: &str) -> String {
         "golangci-lint: {} issues in {} files\n",
         total_issues, total_files
     ));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Show top linters
     let mut linter_counts: Vec<_> = by_linter.iter().collect();
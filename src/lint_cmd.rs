@@ -1,6 +1,6 @@
 use crate::ruff_cmd;
 use crate::tracking;
-use crate::utils::{package_manager_exec, truncate};
+use crate::utils::{package_manager_exec, theme, truncate};
 use anyhow::{Context, Result};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -143,7 +143,10 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
     // Check if process was killed by signal (SIGABRT, SIGKILL, etc.)
     if !output.status.success() && output.status.code().is_none() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("⚠️  Linter process terminated abnormally (possibly out of memory)");
+        eprintln!(
+            "{} Linter process terminated abnormally (possibly out of memory)",
+            theme().warn
+        );
         if !stderr.is_empty() {
             eprintln!(
                 "stderr: {}",
@@ -165,7 +168,7 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
             if !stdout.trim().is_empty() {
                 ruff_cmd::filter_ruff_check_json(&stdout)
             } else {
-                "✓ Ruff: No issues found".to_string()
+                format!("{} Ruff: No issues found", theme().ok)
             }
         }
         "pylint" => filter_pylint_json(&stdout),
@@ -215,7 +218,7 @@ fn filter_eslint_json(output: &str) -> String {
     let total_files = results.iter().filter(|r| !r.messages.is_empty()).count();
 
     if total_errors == 0 && total_warnings == 0 {
-        return "✓ ESLint: No issues found".to_string();
+        return format!("{} ESLint: No issues found", theme().ok);
     }
 
     // Group messages by rule
@@ -242,7 +245,7 @@ fn filter_eslint_json(output: &str) -> String {
         "ESLint: {} errors, {} warnings in {} files\n",
         total_errors, total_warnings, total_files
     ));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Show top rules
     let mut rule_counts: Vec<_> = by_rule.iter().collect();
@@ -302,7 +305,7 @@ fn filter_pylint_json(output: &str) -> String {
     };
 
     if diagnostics.is_empty() {
-        return "✓ Pylint: No issues found".to_string();
+        return format!("{} Pylint: No issues found", theme().ok);
     }
 
     // Count by type
@@ -360,7 +363,7 @@ fn filter_pylint_json(output: &str) -> String {
         result.push('\n');
     }
 
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Show top symbols (rules)
     let mut symbol_counts: Vec<_> = by_symbol.iter().collect();
@@ -438,7 +441,7 @@ fn filter_mypy_output(output: &str) -> String {
     if issues.is_empty() {
         // Check if mypy output contains "Success" or similar
         if output.contains("Success") || output.trim().is_empty() {
-            return "✓ Mypy: No issues found".to_string();
+            return format!("{} Mypy: No issues found", theme().ok);
         }
         // Fallback to generic output if no regex matches
         return format!("Mypy output:\n{}", truncate(output, 500));
@@ -479,7 +482,7 @@ fn filter_mypy_output(output: &str) -> String {
         result.push('\n');
     }
 
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Show top error codes
     let mut code_counts: Vec<_> = by_code.iter().collect();
@@ -539,12 +542,12 @@ fn filter_generic_lint(output: &str) -> String {
     }
 
     if errors == 0 && warnings == 0 {
-        return "✓ Lint: No issues found".to_string();
+        return format!("{} Lint: No issues found", theme().ok);
     }
 
     let mut result = String::new();
     result.push_str(&format!("Lint: {} errors, {} warnings\n", errors, warnings));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     for issue in issues.iter().take(20) {
         result.push_str(&format!("{}\n", truncate(issue, 100)));
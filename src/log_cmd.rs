@@ -1,4 +1,5 @@
 use crate::tracking;
+use crate::utils::theme;
 use anyhow::Result;
 use regex::Regex;
 use std::collections::HashMap;
@@ -108,7 +109,8 @@ fn analyze_logs(content: &str) -> String {
         error_counts.len()
     ));
     result.push(format!(
-        "   ⚠️  {} warnings ({} unique)",
+        "   {} {} warnings ({} unique)",
+        theme().warn,
         total_warnings,
         warn_counts.len()
     ));
@@ -159,7 +161,7 @@ fn analyze_logs(content: &str) -> String {
 
     // Warnings with counts
     if !unique_warnings.is_empty() {
-        result.push("⚠️  WARNINGS:".to_string());
+        result.push(format!("{} WARNINGS:", theme().warn));
 
         let mut warn_list: Vec<_> = warn_counts.iter().collect();
         warn_list.sort_by(|a, b| b.1.cmp(a.1));
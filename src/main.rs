@@ -1,6 +1,15 @@
+mod cache_cmd;
+mod cargo_audit;
 mod cargo_cmd;
+mod cargo_deny;
+mod cargo_filters;
+mod cargo_metadata;
+mod cargo_outdated;
+mod cargo_report;
 mod cc_economics;
 mod ccusage;
+mod ci_cmd;
+mod completions;
 mod config;
 mod container;
 mod curl_cmd;
@@ -8,6 +17,7 @@ mod deps;
 mod diff_cmd;
 mod discover;
 mod display_helpers;
+mod doctor_cmd;
 mod env_cmd;
 mod filter;
 mod find_cmd;
@@ -45,17 +55,30 @@ mod tree;
 mod tsc_cmd;
 mod utils;
 mod vitest_cmd;
+mod watch_cmd;
 mod wget_cmd;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 
+/// `rtk --version`: crate version plus the build metadata from `build.rs`, for bug reports
+const LONG_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    "\ncommit: ",
+    env!("RTK_GIT_COMMIT"),
+    "\nrustc: ",
+    env!("RTK_RUSTC_VERSION"),
+    "\ntarget: ",
+    env!("RTK_TARGET"),
+);
+
 #[derive(Parser)]
 #[command(
     name = "rtk",
     version,
+    long_version = LONG_VERSION,
     about = "Rust Token Killer - Minimize LLM token consumption",
     long_about = "A high-performance CLI proxy designed to filter and summarize system outputs before they reach your LLM context."
 )]
@@ -74,6 +97,10 @@ struct Cli {
     /// Set SKIP_ENV_VALIDATION=1 for child processes (Next.js, tsc, lint, prisma)
     #[arg(long = "skip-env", global = true)]
     skip_env: bool,
+
+    /// ASCII-only output: OK/WARN/FAIL and dashes instead of ✓/⚠/✗/═══ (env: RTK_ASCII=1, config: display.emoji = false)
+    #[arg(long, global = true)]
+    ascii: bool,
 }
 
 #[derive(Subcommand)]
@@ -324,6 +351,9 @@ enum Commands {
         /// Output format: text, json, csv
         #[arg(short, long, default_value = "text")]
         format: String,
+        /// Prune history older than this many days before reporting
+        #[arg(long, value_name = "DAYS")]
+        retention: Option<u32>,
     },
 
     /// Claude Code economics: spending (ccusage) vs savings (rtk) analysis
@@ -345,11 +375,35 @@ enum Commands {
         format: String,
     },
 
-    /// Show or create configuration file
+    /// Show the effective configuration or create the config file
     Config {
-        /// Create default config file
-        #[arg(long)]
+        /// Create default config file (same as `rtk config init`)
+        #[arg(long, hide = true)]
         create: bool,
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
+    },
+
+    /// Manage the tracking history database
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
+    /// Check cargo, rustc, clippy, git and rtk's config/history files, with fixes
+    Doctor,
+
+    /// Print the versioned JSON Schema of `--format jsonl` records (or the `json` document)
+    Schema {
+        /// Output format to describe
+        #[arg(value_parser = ["jsonl", "json"], default_value = "jsonl")]
+        format: String,
+    },
+
+    /// Print a shell completion script (bash, elvish, fish, powershell, zsh) to stdout
+    Completions {
+        /// Shell to generate for: bash, elvish, fish, powershell, zsh
+        shell: clap_complete::Shell,
     },
 
     /// Vitest commands with compact output
@@ -406,8 +460,52 @@ enum Commands {
         args: Vec<String>,
     },
 
+    /// Filter captured cargo output from stdin or a file (e.g. `rtk filter build < ci.log`)
+    Filter {
+        /// Filter kind: build, check, test, clippy, install, nextest, publish, clean, auto (sniff content)
+        #[arg(ignore_case = true)]
+        kind: cargo_cmd::FilterKind,
+        /// Read the captured output from this file instead of stdin
+        #[arg(long, value_name = "PATH")]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        flags: CargoFlags,
+    },
+
+    /// Run cargo check, clippy and test with one combined PASS/FAIL report
+    Ci {
+        /// Run every stage even after one fails (default: stop at the first failure)
+        #[arg(long)]
+        keep_going: bool,
+        /// Run all stages concurrently in separate target dirs (implies --keep-going)
+        #[arg(long)]
+        parallel: bool,
+        #[command(flatten)]
+        flags: CargoFlags,
+    },
+
+    /// Re-run a filtered cargo command (build, check, test, clippy) whenever files change
+    Watch {
+        /// Cargo command to re-run: build, check, test, clippy
+        #[arg(ignore_case = true)]
+        command: cargo_cmd::FilterKind,
+        /// Quiet period in milliseconds before re-running (config: watch.debounce_ms, default 300)
+        #[arg(long, value_name = "MS")]
+        debounce: Option<u64>,
+        /// File or directory to watch, repeatable (config: watch.paths, default src and Cargo.toml)
+        #[arg(long = "path", value_name = "PATH")]
+        paths: Vec<PathBuf>,
+        #[command(flatten)]
+        flags: CargoFlags,
+        /// Additional cargo arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
     /// Cargo commands with compact output
     Cargo {
+        #[command(flatten)]
+        flags: CargoFlags,
         #[command(subcommand)]
         command: CargoCommands,
     },
@@ -528,6 +626,46 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Write a commented default config.toml to the platform config dir
+    Init {
+        /// Overwrite an existing config.toml
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print every effective setting with where it came from (default, global, project, env, flag)
+    Show {
+        /// Show the effect of --max-errors
+        #[arg(long)]
+        max_errors: Option<usize>,
+        /// Show the effect of --failure-width
+        #[arg(long)]
+        failure_width: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Delete the tracking history database
+    Clear {
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Print the path of the tracking history database
+    Path,
+    /// Drop old entries now (also done automatically every 100 commands)
+    Prune {
+        /// Keep this many days of history (default: tracking.history_retention)
+        #[arg(long, value_name = "DAYS")]
+        retention: Option<u32>,
+        /// Keep at most this many entries (default: tracking.history_max_entries)
+        #[arg(long, value_name = "N")]
+        max_entries: Option<usize>,
+    },
+}
+
 #[derive(Subcommand)]
 enum GitCommands {
     /// Condensed diff output
@@ -548,8 +686,11 @@ enum GitCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
-    /// Compact show (commit summary + stat + compacted diff)
+    /// Compact show (commit header + diffstat; the patch only with --full)
     Show {
+        /// Also print the full patch (give it before the revision)
+        #[arg(long)]
+        full: bool,
         /// Git arguments (supports all git show flags)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -560,36 +701,54 @@ enum GitCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
-    /// Commit → "ok ✓ \<hash\>"
+    /// Commit → "✓ committed \<hash\>: \<subject\> (N files, +X -Y)"
     Commit {
         /// Commit message
         #[arg(short, long)]
         message: String,
     },
-    /// Push → "ok ✓ \<branch\>"
+    /// Push → "ok ✓ \<old\>..\<new\> \<branch\> -> \<branch\>"
     Push {
         /// Git push arguments (supports -u, remote, branch, etc.)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
-    /// Pull → "ok ✓ \<stats\>"
+    /// Pull → "ok ✓ Fast-forward \<old\>..\<new\>: \<stats\>"
     Pull {
         /// Git pull arguments (supports --rebase, remote, branch, etc.)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
-    /// Compact branch listing (current/local/remote)
+    /// Compact branch listing (current/local/remote) with ahead/behind per local branch
     Branch {
+        /// Flag local branches whose last commit is older than this many days
+        #[arg(long, value_name = "DAYS")]
+        stale: Option<u64>,
         /// Git branch arguments (supports -d, -D, -m, etc.)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
-    /// Fetch → "ok fetched (N new refs)"
+    /// Fetch → "fetched: N refs updated, N new branches" + ref lines
     Fetch {
         /// Git fetch arguments
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Clone → "✓ git clone: <repo> (N objects, size)", or the fatal: line
+    Clone {
+        /// Git clone arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Tags → "42 tags, latest v2.3.1 (3d ago)" + newest tags by version, then date
+    Tag {
+        /// Tags listed
+        #[arg(long, value_name = "N", default_value_t = 10)]
+        limit: usize,
+        /// Git tag arguments (list patterns; -d, -a, etc. pass through)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Stash management (list, show, pop, apply, drop)
     Stash {
         /// Subcommand: list, show, pop, apply, drop, push
@@ -604,6 +763,12 @@ enum GitCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Blame collapsed to ranges → "lines 10-24: \<hash\> \<author\> \<date\> \"\<subject\>\""
+    Blame {
+        /// Git blame arguments (supports -L start,end, revisions, etc.)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Passthrough: runs any unsupported git subcommand directly
     #[command(external_subcommand)]
     Other(Vec<OsString>),
@@ -630,8 +795,8 @@ enum PnpmCommands {
     Install {
         /// Packages to install
         packages: Vec<String>,
-        /// Additional pnpm arguments
-        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        /// Additional pnpm arguments, after `--` (e.g. `rtk pnpm install react -- -D`)
+        #[arg(last = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
     /// Build (delegates to next build filter)
@@ -749,6 +914,143 @@ enum PrismaMigrateCommands {
     },
 }
 
+/// Display flags shared by cargo filters (override `config.toml` and `.rtk.toml`)
+#[derive(clap::Args)]
+struct CargoFlags {
+    /// Max error blocks to show (env: RTK_MAX_ERRORS, config: display.max_errors, default 15)
+    #[arg(long, global = true)]
+    max_errors: Option<usize>,
+    /// Max characters per test failure (env: RTK_FAILURE_WIDTH, config: display.failure_width, default: terminal width, 200 when piped)
+    #[arg(long, global = true)]
+    failure_width: Option<usize>,
+    /// Colorize rtk's summaries: auto (TTY only, honors NO_COLOR), always, never. Named apart from cargo's own `--color`, which is still passed to cargo
+    #[arg(
+        long = "rtk-color",
+        global = true,
+        value_name = "WHEN",
+        ignore_case = true
+    )]
+    color: Option<cargo_cmd::ColorChoice>,
+    /// Output format: text, github, sarif, json (one report document), markdown, jsonl, json-summary (default: github when GITHUB_ACTIONS=true)
+    #[arg(long, global = true, ignore_case = true)]
+    format: Option<cargo_report::OutputFormat>,
+    /// Print only the one-line summary (no error blocks). Named apart from cargo's -q/--quiet, which still pass through to cargo
+    #[arg(long, global = true)]
+    summary_only: bool,
+    /// Disable filtering: print cargo's output verbatim, still tracked (env: RTK_RAW=1)
+    #[arg(long, global = true)]
+    raw: bool,
+    /// Show new/fixed/unchanged diagnostics compared with the previous --diff run, which records the baseline (build/check/clippy)
+    #[arg(long, global = true)]
+    diff: bool,
+    /// Code-context lines kept per error block (build/check/install; 0 = header and location only)
+    #[arg(long, global = true, value_name = "N")]
+    context: Option<usize>,
+    /// Hide warnings, noting how many (build/check/install)
+    #[arg(long, global = true, conflicts_with = "warnings_only")]
+    errors_only: bool,
+    /// Hide errors, noting how many (build/check)
+    #[arg(long, global = true)]
+    warnings_only: bool,
+    /// Show warning details alongside errors (default: only once there are no errors)
+    #[arg(long, global = true)]
+    with_warnings: bool,
+    /// Only diagnostics whose `-->` file matches this glob, e.g. 'src/parser/*' (build/check/clippy)
+    #[arg(long, global = true, value_name = "GLOB")]
+    only: Option<String>,
+    /// Cargo executable to run (default: $CARGO, then `cargo` on PATH)
+    #[arg(long, global = true, value_name = "PATH")]
+    cargo_bin: Option<std::path::PathBuf>,
+    /// Absolute `-->` paths (default: relative to the workspace root)
+    #[arg(long, global = true)]
+    abs_paths: bool,
+    /// Show only the first error and the total count (build/check)
+    #[arg(long, global = true)]
+    first: bool,
+    /// Append the `rustc --explain` summary of each error code
+    #[arg(long, global = true)]
+    explain: bool,
+    /// Kill cargo and the tests/build scripts it started after this long (e.g. 90s, 10m, 1h)
+    #[arg(long, global = true, value_name = "DURATION")]
+    timeout: Option<cargo_cmd::Timeout>,
+    /// Start the summary with the `$ cargo ...` command line that produced it
+    #[arg(long, global = true)]
+    include_command: bool,
+    /// Append this run's savings to the summary line: [rtk: 4.2 KB -> 340 B, 92%]
+    #[arg(long, global = true)]
+    show_savings: bool,
+    /// Also save cargo's full raw output under the history dir and print its path
+    #[arg(long, global = true)]
+    save_raw: bool,
+    /// Also write the filtered result (in --format) to this file, e.g. a CI artifact
+    #[arg(long, global = true, value_name = "PATH")]
+    output: Option<std::path::PathBuf>,
+    /// With --output: write the file only, print nothing on stdout
+    #[arg(long, global = true, requires = "output")]
+    output_only: bool,
+    /// Exit nonzero if build/check/clippy report any warning, even when cargo succeeds
+    #[arg(long, global = true, conflicts_with = "fail_over")]
+    fail_on_warnings: bool,
+    /// Exit nonzero if build/check/clippy report more than N warnings
+    #[arg(long, global = true, value_name = "N")]
+    fail_over: Option<usize>,
+}
+
+impl CargoFlags {
+    /// Resolve filter options: defaults < global config < project config < flags
+    fn options(&self) -> cargo_cmd::FilterOptions {
+        let mut opts = cargo_cmd::FilterOptions::from_config();
+        if let Some(n) = self.max_errors {
+            opts.max_errors = n;
+        }
+        if let Some(n) = self.failure_width {
+            opts.failure_width = n;
+        }
+        if let Some(choice) = self.color {
+            opts.color = choice.enabled();
+        }
+        if let Some(format) = self.format {
+            opts.format = format;
+        }
+        opts.summary_only = self.summary_only;
+        if self.raw {
+            opts.raw = true;
+        }
+        opts.diff = self.diff;
+        if self.context.is_some() {
+            opts.context = self.context;
+        }
+        opts.errors_only = self.errors_only;
+        opts.warnings_only = self.warnings_only;
+        opts.with_warnings = self.with_warnings;
+        if self.only.is_some() {
+            opts.only = self.only.clone();
+        }
+        if self.cargo_bin.is_some() {
+            opts.cargo_bin = self.cargo_bin.clone();
+        }
+        opts.abs_paths = self.abs_paths;
+        opts.first = self.first;
+        opts.explain = self.explain;
+        if self.timeout.is_some() {
+            opts.timeout = self.timeout;
+        }
+        opts.include_command = self.include_command;
+        opts.show_savings = self.show_savings;
+        opts.save_raw = self.save_raw;
+        if self.output.is_some() {
+            opts.output = self.output.clone();
+        }
+        opts.output_only = self.output_only;
+        if self.fail_on_warnings {
+            opts.fail_over = Some(0);
+        } else if self.fail_over.is_some() {
+            opts.fail_over = self.fail_over;
+        }
+        opts
+    }
+}
+
 #[derive(Subcommand)]
 enum CargoCommands {
     /// Build with compact output (strip Compiling lines, keep errors)
@@ -759,12 +1061,18 @@ enum CargoCommands {
     },
     /// Test with failures-only output
     Test {
+        /// Rerun only the tests that failed in the previous run (all tests if none recorded)
+        #[arg(long)]
+        failed: bool,
         /// Additional cargo test arguments
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
     /// Clippy with warnings grouped by lint rule
     Clippy {
+        /// Show the first suggested fix per rule as "- before" / "+ after" lines
+        #[arg(long)]
+        suggest: bool,
         /// Additional cargo clippy arguments
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -787,6 +1095,102 @@ enum CargoCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Publish with phases collapsed (dry-run confirmation or blocking errors)
+    Publish {
+        /// Additional cargo publish arguments (e.g., --dry-run, --allow-dirty)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Clean with the removed file list collapsed to one line (space freed)
+    Clean {
+        /// Additional cargo clean arguments (e.g., -p, --release, -v)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Run with a filtered compile; the program gets the terminal (stdin/stdout/stderr) untouched
+    Run {
+        /// Additional cargo run arguments (e.g., --release, --bin, -p)
+        #[arg(allow_hyphen_values = true)]
+        args: Vec<String>,
+        /// Arguments for the program itself (after --)
+        #[arg(last = true)]
+        program_args: Vec<String>,
+    },
+    /// Search crates.io with one aligned `name = "x.y.z"  # description` row per crate
+    Search {
+        /// Search terms and additional cargo search arguments (e.g., --limit)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Uninstall with one confirmation line per removed binary (or "not installed")
+    Uninstall {
+        /// Packages and additional cargo uninstall arguments (e.g., --bin)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Metadata digest: root package, members, direct deps, target dir (--format json for the raw JSON)
+    Metadata {
+        /// List direct dependencies with resolved versions
+        #[arg(long)]
+        deps: bool,
+        /// Additional cargo metadata arguments (e.g., --no-deps, --manifest-path)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Audit with advisories grouped by severity (critical/high/medium/low)
+    Audit {
+        /// Exit nonzero only for advisories at or above this severity: low, medium, high, critical
+        #[arg(long, default_value = "low", ignore_case = true)]
+        fail_on: cargo_audit::AuditSeverity,
+        /// Additional cargo audit arguments (e.g., --json, --ignore RUSTSEC-...)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// cargo-deny findings grouped by check (advisories/bans/licenses/sources); exits 1 on errors
+    Deny {
+        /// Additional cargo deny arguments (e.g., check, check licenses, --format json)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Only the dependencies that are behind → "serde 1.0.100 -> 1.0.200 (compat) / 1.1.0 (latest)"
+    Outdated {
+        /// Only list semver-major upgrades (minor bumps for 0.x crates)
+        #[arg(long)]
+        major_only: bool,
+        /// Additional cargo outdated arguments (e.g., -R, --workspace, --depth 1)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Unused dependencies per crate → "foo: unused serde, regex" (exits 1 when any are found)
+    Machete {
+        /// Additional cargo machete arguments (e.g., --with-metadata, a path)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Vendor with the per-crate lines collapsed to a count; the config snippet is kept
+    Vendor {
+        /// Additional cargo vendor arguments (e.g., --versioned-dirs, a target directory)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Coverage (cargo-llvm-cov) reduced to the TOTAL percentages
+    LlvmCov {
+        /// Fail when total line coverage is under this percentage; also lists files under it
+        #[arg(long, value_name = "PCT")]
+        coverage_threshold: Option<f64>,
+        /// Additional cargo llvm-cov arguments (e.g., --workspace, --ignore-filename-regex)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Expand macros: full expansion passed through and tracked (needs cargo-expand)
+    Expand {
+        /// Print a `N lines, size (module: ...)` header above the expansion
+        #[arg(long)]
+        summary: bool,
+        /// Module path and additional cargo expand arguments (e.g., --lib, --test, -p)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Passthrough: runs any unsupported cargo subcommand directly
     #[command(external_subcommand)]
     Other(Vec<OsString>),
@@ -819,6 +1223,7 @@ enum GoCommands {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    utils::init_theme(cli.ascii);
 
     match cli.command {
         Commands::Ls { args } => {
@@ -860,8 +1265,8 @@ fn main() -> Result<()> {
             GitCommands::Status { args } => {
                 git::run(git::GitCommand::Status, &args, None, cli.verbose)?;
             }
-            GitCommands::Show { args } => {
-                git::run(git::GitCommand::Show, &args, None, cli.verbose)?;
+            GitCommands::Show { full, args } => {
+                git::run(git::GitCommand::Show { full }, &args, None, cli.verbose)?;
             }
             GitCommands::Add { args } => {
                 git::run(git::GitCommand::Add, &args, None, cli.verbose)?;
@@ -875,12 +1280,18 @@ fn main() -> Result<()> {
             GitCommands::Pull { args } => {
                 git::run(git::GitCommand::Pull, &args, None, cli.verbose)?;
             }
-            GitCommands::Branch { args } => {
-                git::run(git::GitCommand::Branch, &args, None, cli.verbose)?;
+            GitCommands::Branch { stale, args } => {
+                git::run(git::GitCommand::Branch { stale }, &args, None, cli.verbose)?;
             }
             GitCommands::Fetch { args } => {
                 git::run(git::GitCommand::Fetch, &args, None, cli.verbose)?;
             }
+            GitCommands::Tag { limit, args } => {
+                git::run(git::GitCommand::Tag { limit }, &args, None, cli.verbose)?;
+            }
+            GitCommands::Clone { args } => {
+                git::run(git::GitCommand::Clone, &args, None, cli.verbose)?;
+            }
             GitCommands::Stash { subcommand, args } => {
                 git::run(
                     git::GitCommand::Stash { subcommand },
@@ -892,6 +1303,9 @@ fn main() -> Result<()> {
             GitCommands::Worktree { args } => {
                 git::run(git::GitCommand::Worktree, &args, None, cli.verbose)?;
             }
+            GitCommands::Blame { args } => {
+                git::run(git::GitCommand::Blame, &args, None, cli.verbose)?;
+            }
             GitCommands::Other(args) => {
                 git::run_passthrough(&args, cli.verbose)?;
             }
@@ -1095,7 +1509,11 @@ fn main() -> Result<()> {
             monthly,
             all,
             format,
+            retention,
         } => {
+            if retention.is_some() {
+                cache_cmd::apply_retention(retention, None)?;
+            }
             gain::run(
                 graph,
                 history,
@@ -1120,15 +1538,77 @@ fn main() -> Result<()> {
             cc_economics::run(daily, weekly, monthly, all, &format, cli.verbose)?;
         }
 
-        Commands::Config { create } => {
-            if create {
-                let path = config::Config::create_default()?;
-                println!("Created: {}", path.display());
+        Commands::Cache { command } => match command {
+            CacheCommands::Clear { yes } => {
+                cache_cmd::clear(yes, cli.verbose)?;
+            }
+            CacheCommands::Path => {
+                cache_cmd::path()?;
+            }
+            CacheCommands::Prune {
+                retention,
+                max_entries,
+            } => {
+                cache_cmd::prune(retention, max_entries)?;
+            }
+        },
+
+        Commands::Doctor => {
+            doctor_cmd::run(cli.verbose)?;
+        }
+
+        Commands::Schema { format } => {
+            if format == "json" {
+                println!("{}", cargo_report::json_schema());
             } else {
-                config::show_config()?;
+                println!("{}", cargo_report::jsonl_schema());
             }
         }
 
+        Commands::Completions { shell } => {
+            completions::run(shell, Cli::command())?;
+        }
+
+        Commands::Config { create, command } => match command {
+            Some(ConfigCommands::Init { force }) => {
+                let path = config::init(force)?;
+                println!("Created: {}", path.display());
+            }
+            None if create => {
+                let path = config::init(false)?;
+                println!("Created: {}", path.display());
+            }
+            Some(ConfigCommands::Show {
+                max_errors,
+                failure_width,
+            }) => {
+                let mut flags: Vec<config::Override> = Vec::new();
+                if let Some(n) = max_errors {
+                    flags.push((
+                        "display.max_errors",
+                        toml::Value::Integer(n as i64),
+                        config::Source::Flag("--max-errors"),
+                    ));
+                }
+                if let Some(n) = failure_width {
+                    flags.push((
+                        "display.failure_width",
+                        toml::Value::Integer(n as i64),
+                        config::Source::Flag("--failure-width"),
+                    ));
+                }
+                if cli.ascii {
+                    flags.push((
+                        "display.emoji",
+                        toml::Value::Boolean(false),
+                        config::Source::Flag("--ascii"),
+                    ));
+                }
+                config::show_config(&flags)?;
+            }
+            None => config::show_config(&[])?,
+        },
+
         Commands::Vitest { command } => match command {
             VitestCommands::Run { args } => {
                 vitest_cmd::run(vitest_cmd::VitestCommand::Run, &args, cli.verbose)?;
@@ -1197,29 +1677,130 @@ fn main() -> Result<()> {
             playwright_cmd::run(&args, cli.verbose)?;
         }
 
-        Commands::Cargo { command } => match command {
-            CargoCommands::Build { args } => {
-                cargo_cmd::run(cargo_cmd::CargoCommand::Build, &args, cli.verbose)?;
-            }
-            CargoCommands::Test { args } => {
-                cargo_cmd::run(cargo_cmd::CargoCommand::Test, &args, cli.verbose)?;
-            }
-            CargoCommands::Clippy { args } => {
-                cargo_cmd::run(cargo_cmd::CargoCommand::Clippy, &args, cli.verbose)?;
-            }
-            CargoCommands::Check { args } => {
-                cargo_cmd::run(cargo_cmd::CargoCommand::Check, &args, cli.verbose)?;
-            }
-            CargoCommands::Install { args } => {
-                cargo_cmd::run(cargo_cmd::CargoCommand::Install, &args, cli.verbose)?;
-            }
-            CargoCommands::Nextest { args } => {
-                cargo_cmd::run(cargo_cmd::CargoCommand::Nextest, &args, cli.verbose)?;
+        Commands::Filter { kind, input, flags } => {
+            if let Some(path) = input {
+                cargo_cmd::run_file(kind, &path, &flags.options(), cli.verbose)?;
+            } else {
+                cargo_cmd::run_stdin(kind, &flags.options(), cli.verbose)?;
             }
-            CargoCommands::Other(args) => {
-                cargo_cmd::run_passthrough(&args, cli.verbose)?;
+        }
+
+        Commands::Ci {
+            keep_going,
+            parallel,
+            flags,
+        } => {
+            ci_cmd::run(keep_going, parallel, &flags.options(), cli.verbose)?;
+        }
+
+        Commands::Watch {
+            command,
+            debounce,
+            paths,
+            flags,
+            args,
+        } => {
+            watch_cmd::run(
+                command,
+                &args,
+                debounce,
+                &paths,
+                &flags.options(),
+                cli.verbose,
+            )?;
+        }
+
+        Commands::Cargo { flags, command } => {
+            let opts = flags.options();
+            match command {
+                CargoCommands::Build { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Build, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Test { failed, args } => {
+                    let args = if failed {
+                        cargo_cmd::failed_test_args(&args)
+                    } else {
+                        args
+                    };
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Test, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Clippy { suggest, args } => {
+                    let opts = cargo_cmd::FilterOptions { suggest, ..opts };
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Clippy, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Check { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Check, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Install { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Install, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Nextest { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Nextest, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Publish { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Publish, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Clean { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Clean, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Run { args, program_args } => {
+                    let mut args = args;
+                    if !program_args.is_empty() {
+                        args.push("--".to_string());
+                        args.extend(program_args);
+                    }
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Run, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Search { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Search, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Uninstall { args } => {
+                    cargo_cmd::run(
+                        cargo_cmd::CargoCommand::Uninstall,
+                        &args,
+                        &opts,
+                        cli.verbose,
+                    )?;
+                }
+                CargoCommands::Metadata { deps, args } => {
+                    cargo_metadata::run(&args, deps, &opts, cli.verbose)?;
+                }
+                CargoCommands::Audit { fail_on, args } => {
+                    cargo_audit::run(&args, fail_on, &opts, cli.verbose)?;
+                }
+                CargoCommands::Deny { args } => {
+                    cargo_deny::run(&args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Outdated { major_only, args } => {
+                    cargo_outdated::run(&args, major_only, &opts, cli.verbose)?;
+                }
+                CargoCommands::Machete { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Machete, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::Vendor { args } => {
+                    cargo_cmd::run(cargo_cmd::CargoCommand::Vendor, &args, &opts, cli.verbose)?;
+                }
+                CargoCommands::LlvmCov {
+                    coverage_threshold,
+                    args,
+                } => {
+                    cargo_cmd::run(
+                        cargo_cmd::CargoCommand::LlvmCov {
+                            threshold: coverage_threshold,
+                        },
+                        &args,
+                        &opts,
+                        cli.verbose,
+                    )?;
+                }
+                CargoCommands::Expand { summary, args } => {
+                    cargo_cmd::run_expand(&args, summary, &opts, cli.verbose)?;
+                }
+                CargoCommands::Other(args) => {
+                    cargo_cmd::run_passthrough(&args, &opts, cli.verbose)?;
+                }
             }
-        },
+        }
 
         Commands::Npm { args } => {
             npm_cmd::run(&args, cli.verbose, cli.skip_env)?;
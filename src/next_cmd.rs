@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::{strip_ansi, truncate};
+use crate::utils::{strip_ansi, theme, truncate};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::process::Command;
@@ -131,14 +131,17 @@ fn filter_next_build(output: &str) -> String {
     // Build filtered output
     let mut result = String::new();
     result.push_str("⚡ Next.js Build\n");
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     if already_built && routes_total == 0 {
-        result.push_str("✓ Already built (using cache)\n\n");
+        result.push_str(&format!("{} Already built (using cache)\n\n", theme().ok));
     } else if routes_total > 0 {
         result.push_str(&format!(
-            "✓ {} routes ({} static, {} dynamic)\n\n",
-            routes_total, routes_static, routes_dynamic
+            "{} {} routes ({} static, {} dynamic)\n\n",
+            theme().ok,
+            routes_total,
+            routes_static,
+            routes_dynamic
         ));
     }
 
@@ -151,7 +154,7 @@ fn filter_next_build(output: &str) -> String {
         for (route, size, pct_change) in bundles.iter().take(10) {
             let warning_marker = if let Some(pct) = pct_change {
                 if *pct > 10.0 {
-                    format!(" ⚠️ (+{:.0}%)", pct)
+                    format!(" {} (+{:.0}%)", theme().warn, pct)
                 } else {
                     String::new()
                 }
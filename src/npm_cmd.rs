@@ -1,4 +1,5 @@
 use crate::tracking;
+use crate::utils::theme;
 use anyhow::{Context, Result};
 use std::process::Command;
 
@@ -71,7 +72,7 @@ fn filter_npm_output(output: &str) -> String {
     }
 
     if result.is_empty() {
-        "ok ✓".to_string()
+        format!("ok {}", theme().ok)
     } else {
         result.join("\n")
     }
@@ -1,4 +1,5 @@
 use crate::tracking;
+use crate::utils::theme;
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::process::Command;
@@ -180,7 +181,7 @@ fn filter_pip_list(output: &str) -> String {
 
     let mut result = String::new();
     result.push_str(&format!("pip list: {} packages\n", packages.len()));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Group by first letter for easier scanning
     let mut by_letter: std::collections::HashMap<char, Vec<&Package>> =
@@ -220,12 +221,12 @@ fn filter_pip_outdated(output: &str) -> String {
     };
 
     if packages.is_empty() {
-        return "✓ pip outdated: All packages up to date".to_string();
+        return format!("{} pip outdated: All packages up to date", theme().ok);
     }
 
     let mut result = String::new();
     result.push_str(&format!("pip outdated: {} packages\n", packages.len()));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     for (i, pkg) in packages.iter().take(20).enumerate() {
         let latest = pkg
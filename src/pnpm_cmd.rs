@@ -1,4 +1,5 @@
 use crate::tracking;
+use crate::utils::theme;
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -388,7 +389,7 @@ fn run_outdated(args: &[String], verbose: u8) -> Result<()> {
     };
 
     if filtered.trim().is_empty() {
-        println!("All packages up-to-date ✓");
+        println!("All packages up-to-date {}", theme().ok);
     } else {
         println!("{}", filtered);
     }
@@ -482,7 +483,7 @@ fn filter_pnpm_install(output: &str) -> String {
     }
 
     if result.is_empty() {
-        "ok ✓".to_string()
+        format!("ok {}", theme().ok)
     } else {
         result.join("\n")
     }
@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::package_manager_exec;
+use crate::utils::{package_manager_exec, theme};
 use anyhow::{Context, Result};
 
 pub fn run(args: &[String], verbose: u8) -> Result<()> {
@@ -88,7 +88,7 @@ pub fn filter_prettier_output(output: &str) -> String {
 
     // Check if all files are formatted
     if files_to_format.is_empty() && output.contains("All matched files use Prettier") {
-        return "✓ Prettier: All files formatted correctly".to_string();
+        return format!("{} Prettier: All files formatted correctly", theme().ok);
     }
 
     // Check if files were written (write mode)
@@ -101,13 +101,16 @@ pub fn filter_prettier_output(output: &str) -> String {
     if is_check_mode {
         // Check mode: show files that need formatting
         if files_to_format.is_empty() {
-            result.push_str("✓ Prettier: All files formatted correctly\n");
+            result.push_str(&format!(
+                "{} Prettier: All files formatted correctly\n",
+                theme().ok
+            ));
         } else {
             result.push_str(&format!(
                 "Prettier: {} files need formatting\n",
                 files_to_format.len()
             ));
-            result.push_str("═══════════════════════════════════════\n");
+            result.push_str(&format!("{}\n", theme().separator));
 
             for (i, file) in files_to_format.iter().take(10).enumerate() {
                 result.push_str(&format!("{}. {}\n", i + 1, file));
@@ -122,7 +125,8 @@ pub fn filter_prettier_output(output: &str) -> String {
 
             if files_checked > 0 {
                 result.push_str(&format!(
-                    "\n✓ {} files already formatted\n",
+                    "\n{} {} files already formatted\n",
+                    theme().ok,
                     files_checked - files_to_format.len()
                 ));
             }
@@ -130,7 +134,8 @@ pub fn filter_prettier_output(output: &str) -> String {
     } else {
         // Write mode: show what was formatted
         result.push_str(&format!(
-            "✓ Prettier: {} files formatted\n",
+            "{} Prettier: {} files formatted\n",
+            theme().ok,
             files_to_format.len()
         ));
     }
@@ -1,4 +1,5 @@
 use crate::tracking;
+use crate::utils::theme;
 use anyhow::{Context, Result};
 use std::process::Command;
 
@@ -208,7 +209,7 @@ fn filter_prisma_generate(output: &str) -> String {
     }
 
     let mut result = String::new();
-    result.push_str("✓ Prisma Client generated\n");
+    result.push_str(&format!("{} Prisma Client generated\n", theme().ok));
 
     if models > 0 || enums > 0 || types > 0 {
         result.push_str(&format!(
@@ -271,7 +272,7 @@ fn filter_migrate_dev(output: &str) -> String {
 
     if !migration_name.is_empty() {
         result.push_str(&format!("🗃️  Migration: {}\n", migration_name));
-        result.push_str("═══════════════════════════════════════\n");
+        result.push_str(&format!("{}\n", theme().separator));
     }
 
     result.push_str("Changes:\n");
@@ -290,7 +291,7 @@ fn filter_migrate_dev(output: &str) -> String {
 
     result.push('\n');
     if applied {
-        result.push_str("✓ Applied | Pending: 0\n");
+        result.push_str(&format!("{} Applied | Pending: 0\n", theme().ok));
     }
 
     result.trim().to_string()
@@ -347,7 +348,11 @@ fn filter_migrate_deploy(output: &str) -> String {
     let mut result = String::new();
 
     if errors.is_empty() {
-        result.push_str(&format!("✓ {} migration(s) deployed\n", deployed));
+        result.push_str(&format!(
+            "{} {} migration(s) deployed\n",
+            theme().ok,
+            deployed
+        ));
     } else {
         result.push_str("❌ Deployment failed:\n");
         for err in errors.iter().take(5) {
@@ -377,7 +382,7 @@ fn filter_db_push(output: &str) -> String {
     }
 
     let mut result = String::new();
-    result.push_str("✓ Schema pushed to database\n");
+    result.push_str(&format!("{} Schema pushed to database\n", theme().ok));
 
     if tables_added > 0 || columns_modified > 0 || dropped > 0 {
         result.push_str(&format!(
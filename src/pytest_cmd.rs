@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{theme, truncate};
 use anyhow::{Context, Result};
 use std::process::Command;
 
@@ -180,7 +180,7 @@ fn build_pytest_summary(summary: &str, _test_files: &[String], failures: &[Strin
     let (passed, failed, skipped) = parse_summary_line(summary);
 
     if failed == 0 && passed > 0 {
-        return format!("✓ Pytest: {} passed", passed);
+        return format!("{} Pytest: {} passed", theme().ok, passed);
     }
 
     if passed == 0 && failed == 0 {
@@ -193,7 +193,7 @@ fn build_pytest_summary(summary: &str, _test_files: &[String], failures: &[Strin
         result.push_str(&format!(", {} skipped", skipped));
     }
     result.push('\n');
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     if failures.is_empty() {
         return result.trim().to_string();
@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{theme, truncate};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -128,7 +128,7 @@ pub fn filter_ruff_check_json(output: &str) -> String {
     };
 
     if diagnostics.is_empty() {
-        return "✓ Ruff: No issues found".to_string();
+        return format!("{} Ruff: No issues found", theme().ok);
     }
 
     let total_issues = diagnostics.len();
@@ -165,7 +165,7 @@ pub fn filter_ruff_check_json(output: &str) -> String {
         result.push_str(&format!(" ({} fixable)", fixable_count));
     }
     result.push('\n');
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Show top rules
     let mut rule_counts: Vec<_> = by_rule.iter().collect();
@@ -258,7 +258,7 @@ pub fn filter_ruff_format(output: &str) -> String {
 
     // Check if all files are formatted
     if files_to_format.is_empty() && output_lower.contains("left unchanged") {
-        return "✓ Ruff format: All files formatted correctly".to_string();
+        return format!("{} Ruff format: All files formatted correctly", theme().ok);
     }
 
     let mut result = String::new();
@@ -266,13 +266,16 @@ pub fn filter_ruff_format(output: &str) -> String {
     if output_lower.contains("would reformat") {
         // Check mode: show files that need formatting
         if files_to_format.is_empty() {
-            result.push_str("✓ Ruff format: All files formatted correctly\n");
+            result.push_str(&format!(
+                "{} Ruff format: All files formatted correctly\n",
+                theme().ok
+            ));
         } else {
             result.push_str(&format!(
                 "Ruff format: {} files need formatting\n",
                 files_to_format.len()
             ));
-            result.push_str("═══════════════════════════════════════\n");
+            result.push_str(&format!("{}\n", theme().separator));
 
             for (i, file) in files_to_format.iter().take(10).enumerate() {
                 result.push_str(&format!("{}. {}\n", i + 1, compact_path(file)));
@@ -286,7 +289,11 @@ pub fn filter_ruff_format(output: &str) -> String {
             }
 
             if files_checked > 0 {
-                result.push_str(&format!("\n✓ {} files already formatted\n", files_checked));
+                result.push_str(&format!(
+                    "\n{} {} files already formatted\n",
+                    theme().ok,
+                    files_checked
+                ));
             }
 
             result.push_str("\n💡 Run `ruff format` to format these files\n");
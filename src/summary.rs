@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{theme, truncate};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::process::{Command, Stdio};
@@ -189,7 +189,7 @@ fn summarize_build(output: &str, result: &mut Vec<String>) {
         result.push(format!("   ❌ {} errors", errors));
     }
     if warnings > 0 {
-        result.push(format!("   ⚠️  {} warnings", warnings));
+        result.push(format!("   {} {} warnings", theme().warn, warnings));
     }
     if errors == 0 && warnings == 0 {
         result.push("   ✅ Build successful".to_string());
@@ -223,7 +223,7 @@ fn summarize_logs_quick(output: &str, result: &mut Vec<String>) {
     }
 
     result.push(format!("   ❌ {} errors", errors));
-    result.push(format!("   ⚠️  {} warnings", warnings));
+    result.push(format!("   {} {} warnings", theme().warn, warnings));
     result.push(format!("   ℹ️  {} info", info));
 }
 
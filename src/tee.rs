@@ -13,7 +13,7 @@ const DEFAULT_MAX_FILE_SIZE: usize = 1_048_576;
 /// Sanitize a command slug for use in filenames.
 /// Replaces non-alphanumeric chars (except underscore/hyphen) with underscore,
 /// truncates at 40 chars.
-fn sanitize_slug(slug: &str) -> String {
+pub(crate) fn sanitize_slug(slug: &str) -> String {
     let sanitized: String = slug
         .chars()
         .map(|c| {
@@ -198,6 +198,7 @@ impl Default for TeeMode {
 
 /// Configuration for the tee feature.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct TeeConfig {
     pub enabled: bool,
     pub mode: TeeMode,
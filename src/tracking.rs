@@ -7,7 +7,7 @@
 //! # Architecture
 //!
 //! - Storage: SQLite database (~/.local/share/rtk/tracking.db)
-//! - Retention: 90-day automatic cleanup
+//! - Retention: 30 days / 10,000 entries by default, pruned every 100 writes
 //! - Metrics: Input/output tokens, savings %, execution time
 //!
 //! # Quick Start
@@ -29,16 +29,41 @@
 //!
 //! See [docs/tracking.md](../docs/tracking.md) for full documentation.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
 use serde::Serialize;
 use std::ffi::OsString;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-/// Number of days to retain tracking history before automatic cleanup.
-const HISTORY_DAYS: i64 = 90;
+/// Prune once every this many inserts rather than on every write.
+const PRUNE_INTERVAL: i64 = 100;
+
+/// How much history to keep (`[tracking] history_retention` / `history_max_entries`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Retention {
+    /// Entries older than this many days are dropped
+    pub days: u32,
+    /// Only the newest entries beyond this count are kept
+    pub max_entries: usize,
+}
+
+impl Retention {
+    pub fn from_config() -> Self {
+        let tracking = crate::config::Config::load_or_default().tracking;
+        Self {
+            days: tracking.history_retention,
+            max_entries: tracking.history_max_entries,
+        }
+    }
+}
+
+/// `tracking.enabled` from the config: false skips optional history writes such as
+/// `--diff` snapshots
+pub fn is_enabled() -> bool {
+    crate::config::Config::load_or_default().tracking.enabled
+}
 
 /// Main tracking interface for recording and querying command history.
 ///
@@ -67,6 +92,7 @@ const HISTORY_DAYS: i64 = 90;
 /// ```
 pub struct Tracker {
     conn: Connection,
+    retention: Retention,
 }
 
 /// Individual command record from tracking history.
@@ -221,12 +247,16 @@ impl Tracker {
     /// # Ok::<(), anyhow::Error>(())
     /// ```
     pub fn new() -> Result<Self> {
-        let db_path = get_db_path()?;
+        Self::open(&get_db_path()?, Retention::from_config())
+    }
+
+    /// Open (or create) the database at `db_path`, pruning to `retention` as it grows.
+    pub fn open(db_path: &Path, retention: Retention) -> Result<Self> {
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        let conn = Connection::open(&db_path)?;
+        let conn = Connection::open(db_path)?;
         conn.execute(
             "CREATE TABLE IF NOT EXISTS commands (
                 id INTEGER PRIMARY KEY,
@@ -252,13 +282,25 @@ impl Tracker {
             [],
         );
 
-        Ok(Self { conn })
+        // Latest per-project state used by `--diff` style features (one row per project + kind)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS snapshots (
+                project TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                items TEXT NOT NULL,
+                PRIMARY KEY (project, kind)
+            )",
+            [],
+        )?;
+
+        Ok(Self { conn, retention })
     }
 
     /// Record a command execution with token counts and timing.
     ///
     /// Calculates savings metrics and stores the record in the database.
-    /// Automatically cleans up records older than 90 days after insertion.
+    /// Every [`PRUNE_INTERVAL`] inserts, prunes the history to the configured [`Retention`].
     ///
     /// # Arguments
     ///
@@ -307,17 +349,59 @@ impl Tracker {
             ],
         )?;
 
-        self.cleanup_old()?;
+        if self.conn.last_insert_rowid() % PRUNE_INTERVAL == 0 {
+            self.prune(self.retention)?;
+        }
         Ok(())
     }
 
-    fn cleanup_old(&self) -> Result<()> {
-        let cutoff = Utc::now() - chrono::Duration::days(HISTORY_DAYS);
+    /// Replace the stored snapshot for `project` + `kind` (e.g. error fingerprints of the last build).
+    ///
+    /// Items must not contain newlines; they are stored newline-separated.
+    pub fn save_snapshot(&self, project: &str, kind: &str, items: &[String]) -> Result<()> {
         self.conn.execute(
+            "INSERT OR REPLACE INTO snapshots (project, kind, timestamp, items)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![project, kind, Utc::now().to_rfc3339(), items.join("\n")],
+        )?;
+        Ok(())
+    }
+
+    /// Load the last snapshot saved for `project` + `kind`, or `None` if there is none yet.
+    pub fn load_snapshot(&self, project: &str, kind: &str) -> Result<Option<Vec<String>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT items FROM snapshots WHERE project = ?1 AND kind = ?2")?;
+        let mut rows = stmt.query(params![project, kind])?;
+        match rows.next()? {
+            Some(row) => {
+                let items: String = row.get(0)?;
+                Ok(Some(
+                    items
+                        .lines()
+                        .filter(|l| !l.is_empty())
+                        .map(String::from)
+                        .collect(),
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Drop entries older than `retention.days`, then all but the newest
+    /// `retention.max_entries`. Returns how many entries were removed.
+    pub fn prune(&self, retention: Retention) -> Result<usize> {
+        let cutoff = Utc::now() - chrono::Duration::days(i64::from(retention.days));
+        let expired = self.conn.execute(
             "DELETE FROM commands WHERE timestamp < ?1",
             params![cutoff.to_rfc3339()],
         )?;
-        Ok(())
+        let excess = self.conn.execute(
+            "DELETE FROM commands WHERE id NOT IN
+                (SELECT id FROM commands ORDER BY id DESC LIMIT ?1)",
+            params![retention.max_entries as i64],
+        )?;
+        Ok(expired + excess)
     }
 
     /// Get overall summary statistics across all recorded commands.
@@ -677,7 +761,7 @@ impl Tracker {
     }
 }
 
-fn get_db_path() -> Result<PathBuf> {
+pub fn get_db_path() -> Result<PathBuf> {
     // Priority 1: Environment variable RTK_DB_PATH
     if let Ok(custom_path) = std::env::var("RTK_DB_PATH") {
         return Ok(PathBuf::from(custom_path));
@@ -695,6 +779,25 @@ fn get_db_path() -> Result<PathBuf> {
     Ok(data_dir.join("rtk").join("history.db"))
 }
 
+/// `--save-raw`: write the untouched output of `command` to `raw/` next to the history
+/// database, as `20240501-143012_cargo_build.log`. Files are kept until removed by hand.
+pub fn save_raw(command: &str, raw: &str) -> Result<PathBuf> {
+    let db_path = get_db_path()?;
+    let dir = db_path.parent().unwrap_or(Path::new(".")).join("raw");
+    write_raw(&dir, command, raw, chrono::Local::now().naive_local())
+}
+
+fn write_raw(dir: &Path, command: &str, raw: &str, now: chrono::NaiveDateTime) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!(
+        "{}_{}.log",
+        now.format("%Y%m%d-%H%M%S"),
+        crate::tee::sanitize_slug(command.trim())
+    ));
+    std::fs::write(&path, raw).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
 /// Estimate token count from text using ~4 chars = 1 token heuristic.
 ///
 /// This is a fast approximation suitable for tracking purposes.
@@ -762,6 +865,11 @@ impl TimedExecution {
         }
     }
 
+    /// Milliseconds since [`start`](Self::start), as recorded by [`track`](Self::track).
+    pub fn elapsed_ms(&self) -> u64 {
+        self.start.elapsed().as_millis() as u64
+    }
+
     /// Track the command with elapsed time and token counts.
     ///
     /// Records the command execution with:
@@ -787,7 +895,7 @@ impl TimedExecution {
     /// timer.track("ls -la", "rtk ls", input, output);
     /// ```
     pub fn track(&self, original_cmd: &str, rtk_cmd: &str, input: &str, output: &str) {
-        let elapsed_ms = self.start.elapsed().as_millis() as u64;
+        let elapsed_ms = self.elapsed_ms();
         let input_tokens = estimate_tokens(input);
         let output_tokens = estimate_tokens(output);
 
@@ -834,7 +942,8 @@ impl TimedExecution {
 /// Format OsString args for tracking display.
 ///
 /// Joins arguments with spaces, converting each to UTF-8 (lossy).
-/// Useful for displaying command arguments in tracking records.
+/// Arguments with spaces, quotes or other shell metacharacters are single-quoted
+/// (POSIX style) so the recorded command can be copy-pasted and re-run.
 ///
 /// # Examples
 ///
@@ -844,14 +953,30 @@ impl TimedExecution {
 ///
 /// let args = vec![OsString::from("status"), OsString::from("--short")];
 /// assert_eq!(args_display(&args), "status --short");
+///
+/// let args = vec![OsString::from("commit"), OsString::from("-m"), OsString::from("fix it")];
+/// assert_eq!(args_display(&args), "commit -m 'fix it'");
 /// ```
 pub fn args_display(args: &[OsString]) -> String {
     args.iter()
-        .map(|a| a.to_string_lossy())
+        .map(|a| shell_quote(&a.to_string_lossy()))
         .collect::<Vec<_>>()
         .join(" ")
 }
 
+/// Quote `arg` for a POSIX shell, leaving plain words untouched
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%^".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
 /// Track a command execution (legacy function, use [`TimedExecution`] for new code).
 ///
 /// # Deprecation Notice
@@ -901,6 +1026,33 @@ mod tests {
         assert_eq!(estimate_tokens("12345678"), 2); // 8 chars = 2 tokens
     }
 
+    // Snapshots — save/load round-trip, replaced on save
+    #[test]
+    fn test_tracker_snapshot_round_trip() {
+        let tracker = Tracker::new().expect("Failed to create tracker");
+        let project = format!("/tmp/rtk-snapshot-test-{}", std::process::id());
+
+        assert!(tracker
+            .load_snapshot(&project, "cargo build")
+            .unwrap()
+            .is_none());
+
+        let items = vec!["error[E0308] src/a.rs: mismatched types".to_string()];
+        tracker
+            .save_snapshot(&project, "cargo build", &items)
+            .unwrap();
+        assert_eq!(
+            tracker.load_snapshot(&project, "cargo build").unwrap(),
+            Some(items)
+        );
+
+        tracker.save_snapshot(&project, "cargo build", &[]).unwrap();
+        assert_eq!(
+            tracker.load_snapshot(&project, "cargo build").unwrap(),
+            Some(vec![])
+        );
+    }
+
     // 2. args_display — format OsString vec
     #[test]
     fn test_args_display() {
@@ -912,6 +1064,18 @@ mod tests {
         assert_eq!(args_display(&single), "log");
     }
 
+    #[test]
+    fn test_args_display_quotes_special_args() {
+        let args: Vec<OsString> = ["test", "--", "my test", "it's", "", "$HOME", "a=b"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        assert_eq!(
+            args_display(&args),
+            r#"test -- 'my test' 'it'\''s' '' '$HOME' a=b"#
+        );
+    }
+
     // 3. Tracker::record + get_recent — round-trip DB
     #[test]
     fn test_tracker_record_and_recent() {
@@ -1012,6 +1176,42 @@ mod tests {
         assert_eq!(pt.saved_tokens, 0);
     }
 
+    // Pruning — age cutoff first, then the entry cap keeps the newest rows
+    #[test]
+    fn test_prune_by_age_and_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let retention = Retention {
+            days: 30,
+            max_entries: 2,
+        };
+        let tracker = Tracker::open(&dir.path().join("history.db"), retention).unwrap();
+
+        let old = (Utc::now() - chrono::Duration::days(45)).to_rfc3339();
+        tracker
+            .conn
+            .execute(
+                "INSERT INTO commands (timestamp, original_cmd, rtk_cmd, input_tokens, output_tokens, saved_tokens, savings_pct)
+                 VALUES (?1, 'ls', 'rtk ls old', 10, 5, 5, 50.0)",
+                params![old],
+            )
+            .unwrap();
+        for i in 0..3 {
+            tracker
+                .record("ls", &format!("rtk ls {}", i), 10, 5, 1)
+                .unwrap();
+        }
+
+        assert_eq!(tracker.prune(retention).unwrap(), 2);
+        let kept: Vec<String> = tracker
+            .get_recent(10)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.rtk_cmd)
+            .collect();
+        assert_eq!(kept.len(), 2);
+        assert!(kept.contains(&"rtk ls 1".to_string()) && kept.contains(&"rtk ls 2".to_string()));
+    }
+
     // 7. get_db_path respects environment variable RTK_DB_PATH
     #[test]
     fn test_custom_db_path_env() {
@@ -1026,7 +1226,24 @@ mod tests {
         env::remove_var("RTK_DB_PATH");
     }
 
-    // 8. get_db_path falls back to default when no custom config
+    // 8. write_raw names the file after the time and command, content untouched
+    #[test]
+    fn test_write_raw() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(14, 30, 12)
+            .unwrap();
+        let raw = "   Compiling app v0.1.0\nerror[E0308]: mismatched types\n";
+        let path = write_raw(&dir.path().join("raw"), "cargo build --release", raw, now).unwrap();
+        assert_eq!(
+            path.file_name().unwrap(),
+            "20240501-143012_cargo_build_--release.log"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), raw);
+    }
+
+    // 9. get_db_path falls back to default when no custom config
     #[test]
     fn test_default_db_path() {
         use std::env;
@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{theme, truncate};
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::collections::HashMap;
@@ -114,7 +114,7 @@ fn filter_tsc_output(output: &str) -> String {
 
     if errors.is_empty() {
         if output.contains("Found 0 errors") {
-            return "✓ TypeScript: No errors found".to_string();
+            return format!("{} TypeScript: No errors found", theme().ok);
         }
         return "TypeScript compilation completed".to_string();
     }
@@ -137,7 +137,7 @@ fn filter_tsc_output(output: &str) -> String {
         errors.len(),
         by_file.len()
     ));
-    result.push_str("═══════════════════════════════════════\n");
+    result.push_str(&format!("{}\n", theme().separator));
 
     // Top error codes summary (compact, one line)
     let mut code_counts: Vec<_> = by_code.iter().collect();
@@ -4,10 +4,16 @@
 //! - ANSI color code stripping
 //! - Text truncation
 //! - Command execution with error context
+//! - Output theme (unicode vs ASCII decorations)
 
 use anyhow::{Context, Result};
 use regex::Regex;
+use std::io::{IsTerminal, Write};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 /// Tronque une chaîne à `max_len` caractères avec "..." si nécessaire.
 ///
@@ -51,6 +57,158 @@ pub fn strip_ansi(text: &str) -> String {
     ANSI_RE.replace_all(text, "").to_string()
 }
 
+/// Normalize Windows line endings: `\r\n` (and a stray trailing `\r`) become `\n`.
+///
+/// Filters compare line contents with `ends_with`/`trim`, so a leftover `\r`
+/// would make `"test foo ... ok\r"` miss the `" ok"` suffix.
+pub fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .trim_end_matches('\r')
+        .to_string()
+}
+
+/// Decorations used by filter renderers (success marker, warning marker, separator).
+///
+/// Centralized so `--ascii` / `RTK_ASCII=1` swaps every glyph at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub ok: &'static str,
+    pub warn: &'static str,
+    pub fail: &'static str,
+    pub separator: &'static str,
+    /// Spinner animation frames, one char each
+    pub spinner: &'static str,
+}
+
+impl Theme {
+    /// Default theme: `✓`, `⚠`, `✗` and a `═══` separator line
+    pub const fn unicode() -> Self {
+        Self {
+            ok: "✓",
+            warn: "⚠",
+            fail: "✗",
+            separator: "═══════════════════════════════════════",
+            spinner: "⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏",
+        }
+    }
+
+    /// ASCII-only theme for terminals and log viewers that mangle unicode
+    pub const fn ascii() -> Self {
+        Self {
+            ok: "OK",
+            warn: "WARN",
+            fail: "FAIL",
+            separator: "---------------------------------------",
+            spinner: "|/-\\",
+        }
+    }
+
+    /// A separator `width` chars wide, for tables wider or narrower than [`Theme::separator`]
+    pub fn rule(&self, width: usize) -> String {
+        self.separator
+            .chars()
+            .next()
+            .unwrap_or('-')
+            .to_string()
+            .repeat(width)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::unicode()
+    }
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Resolve the process theme once at startup: ASCII with `--ascii`, `RTK_ASCII=1` or
+/// `display.emoji = false`, unicode otherwise
+pub fn init_theme(ascii: bool) -> Theme {
+    let ascii = ascii
+        || std::env::var("RTK_ASCII").as_deref() == Ok("1")
+        || !crate::config::Config::load_or_default().display.emoji;
+    *THEME.get_or_init(|| {
+        if ascii {
+            Theme::ascii()
+        } else {
+            Theme::unicode()
+        }
+    })
+}
+
+/// The theme resolved by [`init_theme`] (unicode before that, e.g. in unit tests)
+pub fn theme() -> Theme {
+    THEME.get().copied().unwrap_or_default()
+}
+
+/// Elapsed-time spinner on stderr (`⠋ cargo build... 00:45`) while output is buffered.
+///
+/// Only animates when stderr is a TTY, so CI logs stay clean. `finish()` (or drop)
+/// stops the thread and clears the line before the caller prints its summary.
+pub struct Spinner {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Spinner {
+    pub fn start(label: &str, theme: &Theme) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        if !std::io::stderr().is_terminal() {
+            return Self { stop, handle: None };
+        }
+
+        let label = label.to_string();
+        let frames: Vec<char> = theme.spinner.chars().collect();
+        let flag = Arc::clone(&stop);
+        let handle = std::thread::spawn(move || {
+            let started = Instant::now();
+            let mut stderr = std::io::stderr();
+            for frame in frames.iter().cycle() {
+                if flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                let secs = started.elapsed().as_secs();
+                let _ = write!(
+                    stderr,
+                    "\r{} {}... {:02}:{:02}",
+                    frame,
+                    label,
+                    secs / 60,
+                    secs % 60
+                );
+                let _ = stderr.flush();
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        });
+
+        Self {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Stop animating and clear the spinner line
+    pub fn finish(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Spinner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
 /// Exécute une commande et retourne stdout/stderr nettoyés.
 ///
 /// # Arguments
@@ -80,6 +238,25 @@ pub fn execute_command(cmd: &str, args: &[&str]) -> Result<(String, String, i32)
     Ok((stdout, stderr, exit_code))
 }
 
+/// Exit code to propagate for a finished child process.
+///
+/// Returns the real exit code (e.g. 101 for failed `cargo test`). When the child
+/// was killed by a signal there is no code: on Unix this follows the shell
+/// convention `128 + signal` (e.g. 137 for SIGKILL), elsewhere it falls back to 1.
+pub fn exit_code(status: &std::process::ExitStatus) -> i32 {
+    if let Some(code) = status.code() {
+        return code;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    1
+}
+
 /// Formate un nombre de tokens avec suffixes K/M pour lisibilité.
 ///
 /// # Arguments
@@ -278,6 +455,33 @@ mod tests {
         assert_eq!(strip_ansi(input), "Green normal Red");
     }
 
+    #[test]
+    fn test_theme_ascii_has_no_unicode() {
+        let theme = Theme::ascii();
+        assert!(theme.ok.is_ascii());
+        assert!(theme.warn.is_ascii());
+        assert!(theme.separator.is_ascii());
+        assert!(theme.spinner.is_ascii());
+        assert_eq!(
+            theme.separator.len(),
+            Theme::unicode().separator.chars().count()
+        );
+    }
+
+    #[test]
+    fn test_theme_rule_repeats_separator_char() {
+        assert_eq!(Theme::unicode().rule(3), "═══");
+        assert_eq!(Theme::ascii().rule(5), "-----");
+    }
+
+    #[test]
+    fn test_spinner_is_inert_without_tty() {
+        // cargo test captures stderr, so no thread is spawned and finish() returns at once
+        let spinner = Spinner::start("cargo build", &Theme::unicode());
+        assert!(spinner.handle.is_none() || std::io::stderr().is_terminal());
+        spinner.finish();
+    }
+
     #[test]
     fn test_execute_command_success() {
         let result = execute_command("echo", &["test"]);
@@ -293,6 +497,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_exit_code_propagates_code() {
+        let status = Command::new("sh")
+            .args(["-c", "exit 101"])
+            .status()
+            .expect("sh should run");
+        assert_eq!(exit_code(&status), 101);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exit_code_signal() {
+        let status = Command::new("sh")
+            .args(["-c", "kill -9 $$"])
+            .status()
+            .expect("sh should run");
+        assert_eq!(exit_code(&status), 137);
+    }
+
     #[test]
     fn test_format_tokens_millions() {
         assert_eq!(format_tokens(1_234_567), "1.2M");
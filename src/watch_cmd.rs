@@ -0,0 +1,173 @@
+//! `rtk watch <build|check|test|clippy>`: re-run a filtered cargo command on file changes.
+//!
+//! Changes come from the platform's file watcher (inotify, FSEvents, ...) through
+//! `notify`, so nothing runs while the tree is idle. Ctrl-C ends the
+//! process (and the cargo child, which shares the terminal's process group) as usual.
+
+use crate::cargo_cmd::{self, FilterKind, FilterOptions};
+use crate::config::Config;
+use crate::tracking;
+use crate::utils::Spinner;
+use anyhow::{bail, Context, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+pub fn run(
+    kind: FilterKind,
+    args: &[String],
+    debounce_ms: Option<u64>,
+    paths: &[PathBuf],
+    opts: &FilterOptions,
+    verbose: u8,
+) -> Result<()> {
+    opts.reject_json("watch")?;
+    let subcommand = match kind {
+        FilterKind::Build | FilterKind::Check | FilterKind::Test | FilterKind::Clippy => {
+            kind.to_string()
+        }
+        other => bail!(
+            "rtk watch supports build, check, test and clippy (got {})",
+            other
+        ),
+    };
+
+    let config = Config::load_or_default();
+    let debounce = Duration::from_millis(debounce_ms.unwrap_or(config.watch.debounce_ms));
+    let paths: Vec<PathBuf> = if paths.is_empty() {
+        config.watch.paths.iter().map(PathBuf::from).collect()
+    } else {
+        paths.to_vec()
+    };
+
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+    for path in &paths {
+        if let Err(e) = watcher.watch(path, RecursiveMode::Recursive) {
+            eprintln!("rtk: not watching {}: {}", path.display(), e);
+        }
+    }
+
+    loop {
+        run_once(kind, &subcommand, args, &paths, opts, verbose)?;
+
+        // Block until a watched source file changes, then until it has been quiet for
+        // the debounce interval
+        loop {
+            match rx.recv() {
+                Ok(Ok(event)) if is_change(&event, &paths) => break,
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => eprintln!("rtk: file watcher error: {}", e),
+                Err(_) => bail!("file watcher stopped"),
+            }
+        }
+        while rx.recv_timeout(debounce).is_ok() {}
+    }
+}
+
+/// Clear the screen, run the command once and leave its filtered summary on screen
+fn run_once(
+    kind: FilterKind,
+    subcommand: &str,
+    args: &[String],
+    paths: &[PathBuf],
+    opts: &FilterOptions,
+    verbose: u8,
+) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if std::io::stdout().is_terminal() {
+        print!("\x1b[2J\x1b[H");
+        let _ = std::io::stdout().flush();
+    }
+
+    let spinner = Spinner::start(&format!("cargo {}", subcommand), &opts.theme);
+    let output = cargo_cmd::capture(subcommand, args, None, opts, verbose)?;
+    spinner.finish();
+
+    let raw = output.raw();
+    let filtered = cargo_cmd::filter(kind, &raw, opts);
+    println!("{}", opts.render(&filtered));
+    println!(
+        "\n[watch] {} — waiting for changes in {} (Ctrl-C to exit)",
+        chrono::Local::now().format("%H:%M:%S"),
+        format_paths(paths)
+    );
+
+    let cmd = format!("cargo {} {}", subcommand, args.join(" "));
+    timer.track(
+        cmd.trim(),
+        &format!("rtk watch {}", subcommand),
+        &raw,
+        &filtered,
+    );
+    Ok(())
+}
+
+/// A write, create, remove or rename of a relevant path. Plain opens and reads (cargo
+/// reading the sources it compiles) are reported too and must not trigger a re-run.
+fn is_change(event: &Event, roots: &[PathBuf]) -> bool {
+    !matches!(event.kind, EventKind::Access(_))
+        && event.paths.iter().any(|path| is_relevant(path, roots))
+}
+
+/// Whether a change under the watched `roots` should trigger a re-run: `target/` and
+/// hidden directories (`.git`, editor swap dirs) below a root are ignored
+fn is_relevant(path: &Path, roots: &[PathBuf]) -> bool {
+    // Events carry absolute paths, canonical on some platforms (macOS `/private/var`)
+    let relative = roots.iter().find_map(|root| {
+        let absolute = std::env::current_dir()
+            .map(|cwd| cwd.join(root))
+            .unwrap_or_else(|_| root.clone());
+        let canonical = std::fs::canonicalize(&absolute).unwrap_or_else(|_| absolute.clone());
+        path.strip_prefix(&absolute)
+            .or_else(|_| path.strip_prefix(&canonical))
+            .ok()
+    });
+    relative.is_none_or(|relative| {
+        !relative.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy();
+            name == "target" || name.starts_with('.')
+        })
+    })
+}
+
+fn format_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_change_ignores_reads() {
+        use notify::event::{AccessKind, CreateKind};
+        let dir = tempfile::tempdir().unwrap();
+        let roots = vec![dir.path().to_path_buf()];
+        let file = dir.path().join("src/main.rs");
+
+        let open = Event::new(EventKind::Access(AccessKind::Any)).add_path(file.clone());
+        assert!(!is_change(&open, &roots));
+        let create = Event::new(EventKind::Create(CreateKind::File)).add_path(file);
+        assert!(is_change(&create, &roots));
+    }
+
+    #[test]
+    fn test_is_relevant_skips_target_and_hidden_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        let roots = vec![root.clone()];
+
+        assert!(is_relevant(&root.join("src/main.rs"), &roots));
+        assert!(is_relevant(&root.join("Cargo.toml"), &roots));
+        assert!(!is_relevant(&root.join("target/debug/app"), &roots));
+        assert!(!is_relevant(&root.join(".git/HEAD"), &roots));
+    }
+}